@@ -85,6 +85,8 @@ pub fn metric_dispatcher_benchmark(c: &mut Criterion) {
         ping_lifetime_max_time: 0,
         max_pending_pings_count: None,
         max_pending_pings_directory_size: None,
+        database_size_budget: None,
+        event_store_limits: None,
         session_mode: glean_core::SessionMode::Auto,
         session_sample_rate: 1.0,
         session_inactivity_timeout_ms: 1_800_000,
@@ -2,6 +2,9 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+// `library_binding!` recurses once per generated FFI function.
+#![recursion_limit = "256"]
+
 use std::sync::LazyLock;
 
 #[doc(hidden)]
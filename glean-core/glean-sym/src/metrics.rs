@@ -88,6 +88,82 @@ impl CounterMetric {
     }
 }
 #[derive(uniffi::Record)]
+pub struct Counter64Metric {
+    handle: u64,
+}
+impl Counter64Metric {
+    unsafe fn clone_handle(&self) -> u64 {
+        unsafe {
+            let mut call_status = uniffi::RustCallStatus::default();
+            (crate::GLEAN
+                .uniffi_glean_core_fn_clone_counter64metric)(
+                self.handle,
+                &mut call_status,
+            )
+        }
+    }
+}
+impl Counter64Metric {
+    pub fn new(meta: CommonMetricData) -> Self {
+        unsafe {
+            let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let handle = (crate::GLEAN
+                .uniffi_glean_core_fn_constructor_counter64metric_new)(
+                meta.clone_for_ffi(),
+                &mut call_status,
+            );
+            meta.destroy();
+            Self { handle }
+        }
+    }
+    pub fn add(&self, amount: i64) -> () {
+        unsafe {
+            let this = self.clone_handle();
+            let amount = uniffi::FfiConverter::<crate::UniFfiTag>::lower(amount);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_counter64metric_add)(
+                this,
+                amount.clone_for_ffi(),
+                &mut call_status,
+            );
+            amount.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<i64> {
+        unsafe {
+            let this = self.clone_handle();
+            let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_counter64metric_test_get_value)(
+                this,
+                ping_name.clone_for_ffi(),
+                &mut call_status,
+            );
+            ping_name.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32 {
+        unsafe {
+            let this = self.clone_handle();
+            let error = uniffi::FfiConverter::<crate::UniFfiTag>::lower(error);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_counter64metric_test_get_num_recorded_errors)(
+                this,
+                error.clone_for_ffi(),
+                &mut call_status,
+            );
+            error.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+}
+#[derive(uniffi::Record)]
 pub struct TimespanMetric {
     handle: u64,
 }
@@ -440,6 +516,87 @@ impl StringListMetric {
     }
 }
 #[derive(uniffi::Record)]
+pub struct EnumStringMetric {
+    handle: u64,
+}
+impl EnumStringMetric {
+    unsafe fn clone_handle(&self) -> u64 {
+        unsafe {
+            let mut call_status = uniffi::RustCallStatus::default();
+            (crate::GLEAN
+                .uniffi_glean_core_fn_clone_enumstringmetric)(
+                self.handle,
+                &mut call_status,
+            )
+        }
+    }
+}
+impl EnumStringMetric {
+    pub fn new(meta: CommonMetricData, allowed_values: Vec<CowString>) -> Self {
+        unsafe {
+            let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
+            let allowed_values = uniffi::FfiConverter::<
+                crate::UniFfiTag,
+            >::lower(allowed_values);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let handle = (crate::GLEAN
+                .uniffi_glean_core_fn_constructor_enumstringmetric_new)(
+                meta.clone_for_ffi(),
+                allowed_values.clone_for_ffi(),
+                &mut call_status,
+            );
+            allowed_values.destroy();
+            meta.destroy();
+            Self { handle }
+        }
+    }
+    pub fn set(&self, value: String) -> () {
+        unsafe {
+            let this = self.clone_handle();
+            let value = uniffi::FfiConverter::<crate::UniFfiTag>::lower(value);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_enumstringmetric_set)(
+                this,
+                value.clone_for_ffi(),
+                &mut call_status,
+            );
+            value.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<String> {
+        unsafe {
+            let this = self.clone_handle();
+            let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_enumstringmetric_test_get_value)(
+                this,
+                ping_name.clone_for_ffi(),
+                &mut call_status,
+            );
+            ping_name.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32 {
+        unsafe {
+            let this = self.clone_handle();
+            let error = uniffi::FfiConverter::<crate::UniFfiTag>::lower(error);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_enumstringmetric_test_get_num_recorded_errors)(
+                this,
+                error.clone_for_ffi(),
+                &mut call_status,
+            );
+            error.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+}
+#[derive(uniffi::Record)]
 pub struct UrlMetric {
     handle: u64,
 }
@@ -466,6 +623,27 @@ impl UrlMetric {
             Self { handle }
         }
     }
+    pub fn new_with_sanitization(
+        meta: CommonMetricData,
+        sanitization: UrlSanitization,
+    ) -> Self {
+        unsafe {
+            let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
+            let sanitization = uniffi::FfiConverter::<
+                crate::UniFfiTag,
+            >::lower(sanitization);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let handle = (crate::GLEAN
+                .uniffi_glean_core_fn_constructor_urlmetric_new_with_sanitization)(
+                meta.clone_for_ffi(),
+                sanitization.clone_for_ffi(),
+                &mut call_status,
+            );
+            sanitization.destroy();
+            meta.destroy();
+            Self { handle }
+        }
+    }
     pub fn set(&self, value: String) -> () {
         unsafe {
             let this = self.clone_handle();
@@ -674,117 +852,130 @@ impl QuantityMetric {
     }
 }
 #[derive(uniffi::Record)]
-pub struct TimingDistributionMetric {
+pub struct GaugeMetric {
     handle: u64,
 }
-impl TimingDistributionMetric {
+impl GaugeMetric {
     unsafe fn clone_handle(&self) -> u64 {
         unsafe {
             let mut call_status = uniffi::RustCallStatus::default();
             (crate::GLEAN
-                .uniffi_glean_core_fn_clone_timingdistributionmetric)(
-                self.handle,
-                &mut call_status,
-            )
+                .uniffi_glean_core_fn_clone_gaugemetric)(self.handle, &mut call_status)
         }
     }
 }
-impl TimingDistributionMetric {
-    pub fn new(meta: CommonMetricData, time_unit: TimeUnit) -> Self {
+impl GaugeMetric {
+    pub fn new(meta: CommonMetricData) -> Self {
         unsafe {
             let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
-            let time_unit = uniffi::FfiConverter::<crate::UniFfiTag>::lower(time_unit);
             let mut call_status = uniffi::RustCallStatus::default();
             let handle = (crate::GLEAN
-                .uniffi_glean_core_fn_constructor_timingdistributionmetric_new)(
+                .uniffi_glean_core_fn_constructor_gaugemetric_new)(
                 meta.clone_for_ffi(),
-                time_unit.clone_for_ffi(),
                 &mut call_status,
             );
-            time_unit.destroy();
             meta.destroy();
             Self { handle }
         }
     }
-    pub fn start(&self) -> TimerId {
+    pub fn set(&self, value: i64) -> () {
         unsafe {
             let this = self.clone_handle();
+            let value = uniffi::FfiConverter::<crate::UniFfiTag>::lower(value);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_timingdistributionmetric_start)(
+                .uniffi_glean_core_fn_method_gaugemetric_set)(
                 this,
+                value.clone_for_ffi(),
                 &mut call_status,
             );
+            value.destroy();
             crate::util::LocalTryLift::try_lift(res).unwrap()
         }
     }
-    pub fn stop_and_accumulate(&self, timer_id: TimerId) -> () {
+    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<GaugeData> {
         unsafe {
             let this = self.clone_handle();
-            let timer_id = uniffi::FfiConverter::<crate::UniFfiTag>::lower(timer_id);
+            let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_timingdistributionmetric_stop_and_accumulate)(
+                .uniffi_glean_core_fn_method_gaugemetric_test_get_value)(
                 this,
-                timer_id.clone_for_ffi(),
+                ping_name.clone_for_ffi(),
                 &mut call_status,
             );
-            timer_id.destroy();
+            ping_name.destroy();
             crate::util::LocalTryLift::try_lift(res).unwrap()
         }
     }
-    pub fn cancel(&self, timer_id: TimerId) -> () {
+    pub fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32 {
         unsafe {
             let this = self.clone_handle();
-            let timer_id = uniffi::FfiConverter::<crate::UniFfiTag>::lower(timer_id);
+            let error = uniffi::FfiConverter::<crate::UniFfiTag>::lower(error);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_timingdistributionmetric_cancel)(
+                .uniffi_glean_core_fn_method_gaugemetric_test_get_num_recorded_errors)(
                 this,
-                timer_id.clone_for_ffi(),
+                error.clone_for_ffi(),
                 &mut call_status,
             );
-            timer_id.destroy();
+            error.destroy();
             crate::util::LocalTryLift::try_lift(res).unwrap()
         }
     }
-    pub fn accumulate_samples(&self, samples: Vec<i64>) -> () {
+}
+#[derive(uniffi::Record)]
+pub struct DistinctCountMetric {
+    handle: u64,
+}
+impl DistinctCountMetric {
+    unsafe fn clone_handle(&self) -> u64 {
         unsafe {
-            let this = self.clone_handle();
-            let samples = uniffi::FfiConverter::<crate::UniFfiTag>::lower(samples);
             let mut call_status = uniffi::RustCallStatus::default();
-            let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_timingdistributionmetric_accumulate_samples)(
-                this,
-                samples.clone_for_ffi(),
+            (crate::GLEAN
+                .uniffi_glean_core_fn_clone_distinctcountmetric)(
+                self.handle,
+                &mut call_status,
+            )
+        }
+    }
+}
+impl DistinctCountMetric {
+    pub fn new(meta: CommonMetricData) -> Self {
+        unsafe {
+            let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let handle = (crate::GLEAN
+                .uniffi_glean_core_fn_constructor_distinctcountmetric_new)(
+                meta.clone_for_ffi(),
                 &mut call_status,
             );
-            samples.destroy();
-            crate::util::LocalTryLift::try_lift(res).unwrap()
+            meta.destroy();
+            Self { handle }
         }
     }
-    pub fn accumulate_single_sample(&self, sample: i64) -> () {
+    pub fn add(&self, value: String) -> () {
         unsafe {
             let this = self.clone_handle();
-            let sample = uniffi::FfiConverter::<crate::UniFfiTag>::lower(sample);
+            let value = uniffi::FfiConverter::<crate::UniFfiTag>::lower(value);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_timingdistributionmetric_accumulate_single_sample)(
+                .uniffi_glean_core_fn_method_distinctcountmetric_add)(
                 this,
-                sample.clone_for_ffi(),
+                value.clone_for_ffi(),
                 &mut call_status,
             );
-            sample.destroy();
+            value.destroy();
             crate::util::LocalTryLift::try_lift(res).unwrap()
         }
     }
-    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<DistributionData> {
+    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<i64> {
         unsafe {
             let this = self.clone_handle();
             let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_timingdistributionmetric_test_get_value)(
+                .uniffi_glean_core_fn_method_distinctcountmetric_test_get_value)(
                 this,
                 ping_name.clone_for_ffi(),
                 &mut call_status,
@@ -799,7 +990,7 @@ impl TimingDistributionMetric {
             let error = uniffi::FfiConverter::<crate::UniFfiTag>::lower(error);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_timingdistributionmetric_test_get_num_recorded_errors)(
+                .uniffi_glean_core_fn_method_distinctcountmetric_test_get_num_recorded_errors)(
                 this,
                 error.clone_for_ffi(),
                 &mut call_status,
@@ -810,44 +1001,242 @@ impl TimingDistributionMetric {
     }
 }
 #[derive(uniffi::Record)]
-pub struct MemoryDistributionMetric {
+pub struct TimingDistributionMetric {
     handle: u64,
 }
-impl MemoryDistributionMetric {
+impl TimingDistributionMetric {
     unsafe fn clone_handle(&self) -> u64 {
         unsafe {
             let mut call_status = uniffi::RustCallStatus::default();
             (crate::GLEAN
-                .uniffi_glean_core_fn_clone_memorydistributionmetric)(
+                .uniffi_glean_core_fn_clone_timingdistributionmetric)(
                 self.handle,
                 &mut call_status,
             )
         }
     }
 }
-impl MemoryDistributionMetric {
-    pub fn new(meta: CommonMetricData, memory_unit: MemoryUnit) -> Self {
+impl TimingDistributionMetric {
+    pub fn new(meta: CommonMetricData, time_unit: TimeUnit) -> Self {
         unsafe {
             let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
-            let memory_unit = uniffi::FfiConverter::<
-                crate::UniFfiTag,
-            >::lower(memory_unit);
+            let time_unit = uniffi::FfiConverter::<crate::UniFfiTag>::lower(time_unit);
             let mut call_status = uniffi::RustCallStatus::default();
             let handle = (crate::GLEAN
-                .uniffi_glean_core_fn_constructor_memorydistributionmetric_new)(
+                .uniffi_glean_core_fn_constructor_timingdistributionmetric_new)(
                 meta.clone_for_ffi(),
-                memory_unit.clone_for_ffi(),
+                time_unit.clone_for_ffi(),
                 &mut call_status,
             );
-            memory_unit.destroy();
+            time_unit.destroy();
             meta.destroy();
             Self { handle }
         }
     }
-    pub fn accumulate(&self, sample: i64) -> () {
+    pub fn new_with_bucketing(
+        meta: CommonMetricData,
+        time_unit: TimeUnit,
+        buckets_per_magnitude: Option<u32>,
+        max_sample_time: Option<u64>,
+    ) -> Self {
         unsafe {
-            let this = self.clone_handle();
-            let sample = uniffi::FfiConverter::<crate::UniFfiTag>::lower(sample);
+            let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
+            let time_unit = uniffi::FfiConverter::<crate::UniFfiTag>::lower(time_unit);
+            let buckets_per_magnitude = uniffi::FfiConverter::<
+                crate::UniFfiTag,
+            >::lower(buckets_per_magnitude);
+            let max_sample_time = uniffi::FfiConverter::<
+                crate::UniFfiTag,
+            >::lower(max_sample_time);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let handle = (crate::GLEAN
+                .uniffi_glean_core_fn_constructor_timingdistributionmetric_new_with_bucketing)(
+                meta.clone_for_ffi(),
+                time_unit.clone_for_ffi(),
+                buckets_per_magnitude.clone_for_ffi(),
+                max_sample_time.clone_for_ffi(),
+                &mut call_status,
+            );
+            max_sample_time.destroy();
+            buckets_per_magnitude.destroy();
+            time_unit.destroy();
+            meta.destroy();
+            Self { handle }
+        }
+    }
+    pub fn start(&self) -> TimerId {
+        unsafe {
+            let this = self.clone_handle();
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_timingdistributionmetric_start)(
+                this,
+                &mut call_status,
+            );
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn stop_and_accumulate(&self, timer_id: TimerId) -> () {
+        unsafe {
+            let this = self.clone_handle();
+            let timer_id = uniffi::FfiConverter::<crate::UniFfiTag>::lower(timer_id);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_timingdistributionmetric_stop_and_accumulate)(
+                this,
+                timer_id.clone_for_ffi(),
+                &mut call_status,
+            );
+            timer_id.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn cancel(&self, timer_id: TimerId) -> () {
+        unsafe {
+            let this = self.clone_handle();
+            let timer_id = uniffi::FfiConverter::<crate::UniFfiTag>::lower(timer_id);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_timingdistributionmetric_cancel)(
+                this,
+                timer_id.clone_for_ffi(),
+                &mut call_status,
+            );
+            timer_id.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn accumulate_samples(&self, samples: Vec<i64>) -> () {
+        unsafe {
+            let this = self.clone_handle();
+            let samples = uniffi::FfiConverter::<crate::UniFfiTag>::lower(samples);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_timingdistributionmetric_accumulate_samples)(
+                this,
+                samples.clone_for_ffi(),
+                &mut call_status,
+            );
+            samples.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn accumulate_single_sample(&self, sample: i64) -> () {
+        unsafe {
+            let this = self.clone_handle();
+            let sample = uniffi::FfiConverter::<crate::UniFfiTag>::lower(sample);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_timingdistributionmetric_accumulate_single_sample)(
+                this,
+                sample.clone_for_ffi(),
+                &mut call_status,
+            );
+            sample.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<DistributionData> {
+        unsafe {
+            let this = self.clone_handle();
+            let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_timingdistributionmetric_test_get_value)(
+                this,
+                ping_name.clone_for_ffi(),
+                &mut call_status,
+            );
+            ping_name.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32 {
+        unsafe {
+            let this = self.clone_handle();
+            let error = uniffi::FfiConverter::<crate::UniFfiTag>::lower(error);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_timingdistributionmetric_test_get_num_recorded_errors)(
+                this,
+                error.clone_for_ffi(),
+                &mut call_status,
+            );
+            error.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+}
+#[derive(uniffi::Record)]
+pub struct MemoryDistributionMetric {
+    handle: u64,
+}
+impl MemoryDistributionMetric {
+    unsafe fn clone_handle(&self) -> u64 {
+        unsafe {
+            let mut call_status = uniffi::RustCallStatus::default();
+            (crate::GLEAN
+                .uniffi_glean_core_fn_clone_memorydistributionmetric)(
+                self.handle,
+                &mut call_status,
+            )
+        }
+    }
+}
+impl MemoryDistributionMetric {
+    pub fn new(meta: CommonMetricData, memory_unit: MemoryUnit) -> Self {
+        unsafe {
+            let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
+            let memory_unit = uniffi::FfiConverter::<
+                crate::UniFfiTag,
+            >::lower(memory_unit);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let handle = (crate::GLEAN
+                .uniffi_glean_core_fn_constructor_memorydistributionmetric_new)(
+                meta.clone_for_ffi(),
+                memory_unit.clone_for_ffi(),
+                &mut call_status,
+            );
+            memory_unit.destroy();
+            meta.destroy();
+            Self { handle }
+        }
+    }
+    pub fn new_with_bucketing(
+        meta: CommonMetricData,
+        memory_unit: MemoryUnit,
+        buckets_per_magnitude: Option<u32>,
+        max_bytes: Option<u64>,
+    ) -> Self {
+        unsafe {
+            let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
+            let memory_unit = uniffi::FfiConverter::<
+                crate::UniFfiTag,
+            >::lower(memory_unit);
+            let buckets_per_magnitude = uniffi::FfiConverter::<
+                crate::UniFfiTag,
+            >::lower(buckets_per_magnitude);
+            let max_bytes = uniffi::FfiConverter::<crate::UniFfiTag>::lower(max_bytes);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let handle = (crate::GLEAN
+                .uniffi_glean_core_fn_constructor_memorydistributionmetric_new_with_bucketing)(
+                meta.clone_for_ffi(),
+                memory_unit.clone_for_ffi(),
+                buckets_per_magnitude.clone_for_ffi(),
+                max_bytes.clone_for_ffi(),
+                &mut call_status,
+            );
+            max_bytes.destroy();
+            buckets_per_magnitude.destroy();
+            memory_unit.destroy();
+            meta.destroy();
+            Self { handle }
+        }
+    }
+    pub fn accumulate(&self, sample: i64) -> () {
+        unsafe {
+            let this = self.clone_handle();
+            let sample = uniffi::FfiConverter::<crate::UniFfiTag>::lower(sample);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
                 .uniffi_glean_core_fn_method_memorydistributionmetric_accumulate)(
@@ -874,13 +1263,710 @@ impl MemoryDistributionMetric {
             crate::util::LocalTryLift::try_lift(res).unwrap()
         }
     }
-    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<DistributionData> {
+    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<DistributionData> {
+        unsafe {
+            let this = self.clone_handle();
+            let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_memorydistributionmetric_test_get_value)(
+                this,
+                ping_name.clone_for_ffi(),
+                &mut call_status,
+            );
+            ping_name.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32 {
+        unsafe {
+            let this = self.clone_handle();
+            let error = uniffi::FfiConverter::<crate::UniFfiTag>::lower(error);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_memorydistributionmetric_test_get_num_recorded_errors)(
+                this,
+                error.clone_for_ffi(),
+                &mut call_status,
+            );
+            error.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+}
+#[derive(uniffi::Record)]
+pub struct CustomDistributionMetric {
+    handle: u64,
+}
+impl CustomDistributionMetric {
+    unsafe fn clone_handle(&self) -> u64 {
+        unsafe {
+            let mut call_status = uniffi::RustCallStatus::default();
+            (crate::GLEAN
+                .uniffi_glean_core_fn_clone_customdistributionmetric)(
+                self.handle,
+                &mut call_status,
+            )
+        }
+    }
+}
+impl CustomDistributionMetric {
+    pub fn new(
+        meta: CommonMetricData,
+        range_min: i64,
+        range_max: i64,
+        bucket_count: i64,
+        histogram_type: HistogramType,
+    ) -> Self {
+        unsafe {
+            let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
+            let range_min = uniffi::FfiConverter::<crate::UniFfiTag>::lower(range_min);
+            let range_max = uniffi::FfiConverter::<crate::UniFfiTag>::lower(range_max);
+            let bucket_count = uniffi::FfiConverter::<
+                crate::UniFfiTag,
+            >::lower(bucket_count);
+            let histogram_type = uniffi::FfiConverter::<
+                crate::UniFfiTag,
+            >::lower(histogram_type);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let handle = (crate::GLEAN
+                .uniffi_glean_core_fn_constructor_customdistributionmetric_new)(
+                meta.clone_for_ffi(),
+                range_min.clone_for_ffi(),
+                range_max.clone_for_ffi(),
+                bucket_count.clone_for_ffi(),
+                histogram_type.clone_for_ffi(),
+                &mut call_status,
+            );
+            histogram_type.destroy();
+            bucket_count.destroy();
+            range_max.destroy();
+            range_min.destroy();
+            meta.destroy();
+            Self { handle }
+        }
+    }
+    pub fn accumulate_samples(&self, samples: Vec<i64>) -> () {
+        unsafe {
+            let this = self.clone_handle();
+            let samples = uniffi::FfiConverter::<crate::UniFfiTag>::lower(samples);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_customdistributionmetric_accumulate_samples)(
+                this,
+                samples.clone_for_ffi(),
+                &mut call_status,
+            );
+            samples.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn accumulate_single_sample(&self, sample: i64) -> () {
+        unsafe {
+            let this = self.clone_handle();
+            let sample = uniffi::FfiConverter::<crate::UniFfiTag>::lower(sample);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_customdistributionmetric_accumulate_single_sample)(
+                this,
+                sample.clone_for_ffi(),
+                &mut call_status,
+            );
+            sample.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<DistributionData> {
+        unsafe {
+            let this = self.clone_handle();
+            let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_customdistributionmetric_test_get_value)(
+                this,
+                ping_name.clone_for_ffi(),
+                &mut call_status,
+            );
+            ping_name.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32 {
+        unsafe {
+            let this = self.clone_handle();
+            let error = uniffi::FfiConverter::<crate::UniFfiTag>::lower(error);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_customdistributionmetric_test_get_num_recorded_errors)(
+                this,
+                error.clone_for_ffi(),
+                &mut call_status,
+            );
+            error.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+}
+#[derive(uniffi::Record)]
+pub struct QuantileDistributionMetric {
+    handle: u64,
+}
+impl QuantileDistributionMetric {
+    unsafe fn clone_handle(&self) -> u64 {
+        unsafe {
+            let mut call_status = uniffi::RustCallStatus::default();
+            (crate::GLEAN
+                .uniffi_glean_core_fn_clone_quantiledistributionmetric)(
+                self.handle,
+                &mut call_status,
+            )
+        }
+    }
+}
+impl QuantileDistributionMetric {
+    pub fn new(meta: CommonMetricData, relative_accuracy: f64) -> Self {
+        unsafe {
+            let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
+            let relative_accuracy = uniffi::FfiConverter::<
+                crate::UniFfiTag,
+            >::lower(relative_accuracy);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let handle = (crate::GLEAN
+                .uniffi_glean_core_fn_constructor_quantiledistributionmetric_new)(
+                meta.clone_for_ffi(),
+                relative_accuracy.clone_for_ffi(),
+                &mut call_status,
+            );
+            relative_accuracy.destroy();
+            meta.destroy();
+            Self { handle }
+        }
+    }
+    pub fn accumulate_samples(&self, samples: Vec<i64>) -> () {
+        unsafe {
+            let this = self.clone_handle();
+            let samples = uniffi::FfiConverter::<crate::UniFfiTag>::lower(samples);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_quantiledistributionmetric_accumulate_samples)(
+                this,
+                samples.clone_for_ffi(),
+                &mut call_status,
+            );
+            samples.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn accumulate_single_sample(&self, sample: i64) -> () {
+        unsafe {
+            let this = self.clone_handle();
+            let sample = uniffi::FfiConverter::<crate::UniFfiTag>::lower(sample);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_quantiledistributionmetric_accumulate_single_sample)(
+                this,
+                sample.clone_for_ffi(),
+                &mut call_status,
+            );
+            sample.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<DistributionData> {
+        unsafe {
+            let this = self.clone_handle();
+            let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_quantiledistributionmetric_test_get_value)(
+                this,
+                ping_name.clone_for_ffi(),
+                &mut call_status,
+            );
+            ping_name.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn test_get_quantile(
+        &self,
+        quantile: f64,
+        ping_name: Option<String>,
+    ) -> Option<f64> {
+        unsafe {
+            let this = self.clone_handle();
+            let quantile = uniffi::FfiConverter::<crate::UniFfiTag>::lower(quantile);
+            let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_quantiledistributionmetric_test_get_quantile)(
+                this,
+                quantile.clone_for_ffi(),
+                ping_name.clone_for_ffi(),
+                &mut call_status,
+            );
+            ping_name.destroy();
+            quantile.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32 {
+        unsafe {
+            let this = self.clone_handle();
+            let error = uniffi::FfiConverter::<crate::UniFfiTag>::lower(error);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_quantiledistributionmetric_test_get_num_recorded_errors)(
+                this,
+                error.clone_for_ffi(),
+                &mut call_status,
+            );
+            error.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+}
+#[derive(uniffi::Record)]
+pub struct DatetimeMetric {
+    handle: u64,
+}
+impl DatetimeMetric {
+    unsafe fn clone_handle(&self) -> u64 {
+        unsafe {
+            let mut call_status = uniffi::RustCallStatus::default();
+            (crate::GLEAN
+                .uniffi_glean_core_fn_clone_datetimemetric)(
+                self.handle,
+                &mut call_status,
+            )
+        }
+    }
+}
+impl DatetimeMetric {
+    pub fn new(meta: CommonMetricData, time_unit: TimeUnit) -> Self {
+        unsafe {
+            let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
+            let time_unit = uniffi::FfiConverter::<crate::UniFfiTag>::lower(time_unit);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let handle = (crate::GLEAN
+                .uniffi_glean_core_fn_constructor_datetimemetric_new)(
+                meta.clone_for_ffi(),
+                time_unit.clone_for_ffi(),
+                &mut call_status,
+            );
+            time_unit.destroy();
+            meta.destroy();
+            Self { handle }
+        }
+    }
+    pub fn set(&self, value: Option<Datetime>) -> () {
+        unsafe {
+            let this = self.clone_handle();
+            let value = uniffi::FfiConverter::<crate::UniFfiTag>::lower(value);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_datetimemetric_set)(
+                this,
+                value.clone_for_ffi(),
+                &mut call_status,
+            );
+            value.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<Datetime> {
+        unsafe {
+            let this = self.clone_handle();
+            let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_datetimemetric_test_get_value)(
+                this,
+                ping_name.clone_for_ffi(),
+                &mut call_status,
+            );
+            ping_name.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn test_get_value_as_string(&self, ping_name: Option<String>) -> Option<String> {
+        unsafe {
+            let this = self.clone_handle();
+            let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_datetimemetric_test_get_value_as_string)(
+                this,
+                ping_name.clone_for_ffi(),
+                &mut call_status,
+            );
+            ping_name.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32 {
+        unsafe {
+            let this = self.clone_handle();
+            let error = uniffi::FfiConverter::<crate::UniFfiTag>::lower(error);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_datetimemetric_test_get_num_recorded_errors)(
+                this,
+                error.clone_for_ffi(),
+                &mut call_status,
+            );
+            error.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+}
+#[derive(uniffi::Record)]
+pub struct EventMetric {
+    handle: u64,
+}
+impl EventMetric {
+    unsafe fn clone_handle(&self) -> u64 {
+        unsafe {
+            let mut call_status = uniffi::RustCallStatus::default();
+            (crate::GLEAN
+                .uniffi_glean_core_fn_clone_eventmetric)(self.handle, &mut call_status)
+        }
+    }
+}
+impl EventMetric {
+    pub fn new(meta: CommonMetricData, allowed_extra_keys: Vec<String>) -> Self {
+        unsafe {
+            let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
+            let allowed_extra_keys = uniffi::FfiConverter::<
+                crate::UniFfiTag,
+            >::lower(allowed_extra_keys);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let handle = (crate::GLEAN
+                .uniffi_glean_core_fn_constructor_eventmetric_new)(
+                meta.clone_for_ffi(),
+                allowed_extra_keys.clone_for_ffi(),
+                &mut call_status,
+            );
+            allowed_extra_keys.destroy();
+            meta.destroy();
+            Self { handle }
+        }
+    }
+    pub fn new_with_extra_types(
+        meta: CommonMetricData,
+        allowed_extra_keys: ::std::collections::HashMap<String, ExtraKeyType>,
+    ) -> Self {
+        unsafe {
+            let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
+            let allowed_extra_keys = uniffi::FfiConverter::<
+                crate::UniFfiTag,
+            >::lower(allowed_extra_keys);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let handle = (crate::GLEAN
+                .uniffi_glean_core_fn_constructor_eventmetric_new_with_extra_types)(
+                meta.clone_for_ffi(),
+                allowed_extra_keys.clone_for_ffi(),
+                &mut call_status,
+            );
+            allowed_extra_keys.destroy();
+            meta.destroy();
+            Self { handle }
+        }
+    }
+    pub fn record(&self, extra: ::std::collections::HashMap<String, String>) -> () {
+        unsafe {
+            let this = self.clone_handle();
+            let extra = uniffi::FfiConverter::<crate::UniFfiTag>::lower(extra);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_eventmetric_record)(
+                this,
+                extra.clone_for_ffi(),
+                &mut call_status,
+            );
+            extra.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn test_get_value(
+        &self,
+        ping_name: Option<String>,
+    ) -> Option<Vec<RecordedEvent>> {
+        unsafe {
+            let this = self.clone_handle();
+            let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_eventmetric_test_get_value)(
+                this,
+                ping_name.clone_for_ffi(),
+                &mut call_status,
+            );
+            ping_name.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32 {
+        unsafe {
+            let this = self.clone_handle();
+            let error = uniffi::FfiConverter::<crate::UniFfiTag>::lower(error);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_eventmetric_test_get_num_recorded_errors)(
+                this,
+                error.clone_for_ffi(),
+                &mut call_status,
+            );
+            error.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+}
+#[derive(uniffi::Record)]
+pub struct SpanMetric {
+    handle: u64,
+}
+impl SpanMetric {
+    unsafe fn clone_handle(&self) -> u64 {
+        unsafe {
+            let mut call_status = uniffi::RustCallStatus::default();
+            (crate::GLEAN
+                .uniffi_glean_core_fn_clone_spanmetric)(self.handle, &mut call_status)
+        }
+    }
+}
+impl SpanMetric {
+    pub fn new(meta: CommonMetricData, allowed_extra_keys: Vec<String>) -> Self {
+        unsafe {
+            let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
+            let allowed_extra_keys = uniffi::FfiConverter::<
+                crate::UniFfiTag,
+            >::lower(allowed_extra_keys);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let handle = (crate::GLEAN
+                .uniffi_glean_core_fn_constructor_spanmetric_new)(
+                meta.clone_for_ffi(),
+                allowed_extra_keys.clone_for_ffi(),
+                &mut call_status,
+            );
+            allowed_extra_keys.destroy();
+            meta.destroy();
+            Self { handle }
+        }
+    }
+    pub fn start(&self, parent: Option<SpanId>) -> SpanId {
+        unsafe {
+            let this = self.clone_handle();
+            let parent = uniffi::FfiConverter::<crate::UniFfiTag>::lower(parent);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_spanmetric_start)(
+                this,
+                parent.clone_for_ffi(),
+                &mut call_status,
+            );
+            parent.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn end(
+        &self,
+        span: SpanId,
+        extra: ::std::collections::HashMap<String, String>,
+    ) -> () {
+        unsafe {
+            let this = self.clone_handle();
+            let span = uniffi::FfiConverter::<crate::UniFfiTag>::lower(span);
+            let extra = uniffi::FfiConverter::<crate::UniFfiTag>::lower(extra);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_spanmetric_end)(
+                this,
+                span.clone_for_ffi(),
+                extra.clone_for_ffi(),
+                &mut call_status,
+            );
+            extra.destroy();
+            span.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn test_get_value(
+        &self,
+        ping_name: Option<String>,
+    ) -> Option<Vec<RecordedEvent>> {
+        unsafe {
+            let this = self.clone_handle();
+            let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_spanmetric_test_get_value)(
+                this,
+                ping_name.clone_for_ffi(),
+                &mut call_status,
+            );
+            ping_name.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32 {
+        unsafe {
+            let this = self.clone_handle();
+            let error = uniffi::FfiConverter::<crate::UniFfiTag>::lower(error);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_spanmetric_test_get_num_recorded_errors)(
+                this,
+                error.clone_for_ffi(),
+                &mut call_status,
+            );
+            error.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+}
+#[derive(uniffi::Record)]
+pub struct RateMetric {
+    handle: u64,
+}
+impl RateMetric {
+    unsafe fn clone_handle(&self) -> u64 {
+        unsafe {
+            let mut call_status = uniffi::RustCallStatus::default();
+            (crate::GLEAN
+                .uniffi_glean_core_fn_clone_ratemetric)(self.handle, &mut call_status)
+        }
+    }
+}
+impl RateMetric {
+    pub fn new(meta: CommonMetricData) -> Self {
+        unsafe {
+            let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let handle = (crate::GLEAN
+                .uniffi_glean_core_fn_constructor_ratemetric_new)(
+                meta.clone_for_ffi(),
+                &mut call_status,
+            );
+            meta.destroy();
+            Self { handle }
+        }
+    }
+    pub fn add_to_numerator(&self, amount: i32) -> () {
+        unsafe {
+            let this = self.clone_handle();
+            let amount = uniffi::FfiConverter::<crate::UniFfiTag>::lower(amount);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_ratemetric_add_to_numerator)(
+                this,
+                amount.clone_for_ffi(),
+                &mut call_status,
+            );
+            amount.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn add_to_denominator(&self, amount: i32) -> () {
+        unsafe {
+            let this = self.clone_handle();
+            let amount = uniffi::FfiConverter::<crate::UniFfiTag>::lower(amount);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_ratemetric_add_to_denominator)(
+                this,
+                amount.clone_for_ffi(),
+                &mut call_status,
+            );
+            amount.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<Rate> {
+        unsafe {
+            let this = self.clone_handle();
+            let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_ratemetric_test_get_value)(
+                this,
+                ping_name.clone_for_ffi(),
+                &mut call_status,
+            );
+            ping_name.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32 {
+        unsafe {
+            let this = self.clone_handle();
+            let error = uniffi::FfiConverter::<crate::UniFfiTag>::lower(error);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_ratemetric_test_get_num_recorded_errors)(
+                this,
+                error.clone_for_ffi(),
+                &mut call_status,
+            );
+            error.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+}
+#[derive(uniffi::Record)]
+pub struct DenominatorMetric {
+    handle: u64,
+}
+impl DenominatorMetric {
+    unsafe fn clone_handle(&self) -> u64 {
+        unsafe {
+            let mut call_status = uniffi::RustCallStatus::default();
+            (crate::GLEAN
+                .uniffi_glean_core_fn_clone_denominatormetric)(
+                self.handle,
+                &mut call_status,
+            )
+        }
+    }
+}
+impl DenominatorMetric {
+    pub fn new(meta: CommonMetricData, numerators: Vec<CommonMetricData>) -> Self {
+        unsafe {
+            let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
+            let numerators = uniffi::FfiConverter::<crate::UniFfiTag>::lower(numerators);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let handle = (crate::GLEAN
+                .uniffi_glean_core_fn_constructor_denominatormetric_new)(
+                meta.clone_for_ffi(),
+                numerators.clone_for_ffi(),
+                &mut call_status,
+            );
+            numerators.destroy();
+            meta.destroy();
+            Self { handle }
+        }
+    }
+    pub fn add(&self, amount: i32) -> () {
+        unsafe {
+            let this = self.clone_handle();
+            let amount = uniffi::FfiConverter::<crate::UniFfiTag>::lower(amount);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_denominatormetric_add)(
+                this,
+                amount.clone_for_ffi(),
+                &mut call_status,
+            );
+            amount.destroy();
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
+    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<i32> {
         unsafe {
             let this = self.clone_handle();
             let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_memorydistributionmetric_test_get_value)(
+                .uniffi_glean_core_fn_method_denominatormetric_test_get_value)(
                 this,
                 ping_name.clone_for_ffi(),
                 &mut call_status,
@@ -895,7 +1981,7 @@ impl MemoryDistributionMetric {
             let error = uniffi::FfiConverter::<crate::UniFfiTag>::lower(error);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_memorydistributionmetric_test_get_num_recorded_errors)(
+                .uniffi_glean_core_fn_method_denominatormetric_test_get_num_recorded_errors)(
                 this,
                 error.clone_for_ffi(),
                 &mut call_status,
@@ -906,94 +1992,57 @@ impl MemoryDistributionMetric {
     }
 }
 #[derive(uniffi::Record)]
-pub struct CustomDistributionMetric {
+pub struct NumeratorMetric {
     handle: u64,
 }
-impl CustomDistributionMetric {
+impl NumeratorMetric {
     unsafe fn clone_handle(&self) -> u64 {
         unsafe {
             let mut call_status = uniffi::RustCallStatus::default();
             (crate::GLEAN
-                .uniffi_glean_core_fn_clone_customdistributionmetric)(
+                .uniffi_glean_core_fn_clone_numeratormetric)(
                 self.handle,
                 &mut call_status,
             )
         }
     }
 }
-impl CustomDistributionMetric {
-    pub fn new(
-        meta: CommonMetricData,
-        range_min: i64,
-        range_max: i64,
-        bucket_count: i64,
-        histogram_type: HistogramType,
-    ) -> Self {
+impl NumeratorMetric {
+    pub fn new(meta: CommonMetricData) -> Self {
         unsafe {
             let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
-            let range_min = uniffi::FfiConverter::<crate::UniFfiTag>::lower(range_min);
-            let range_max = uniffi::FfiConverter::<crate::UniFfiTag>::lower(range_max);
-            let bucket_count = uniffi::FfiConverter::<
-                crate::UniFfiTag,
-            >::lower(bucket_count);
-            let histogram_type = uniffi::FfiConverter::<
-                crate::UniFfiTag,
-            >::lower(histogram_type);
             let mut call_status = uniffi::RustCallStatus::default();
             let handle = (crate::GLEAN
-                .uniffi_glean_core_fn_constructor_customdistributionmetric_new)(
+                .uniffi_glean_core_fn_constructor_numeratormetric_new)(
                 meta.clone_for_ffi(),
-                range_min.clone_for_ffi(),
-                range_max.clone_for_ffi(),
-                bucket_count.clone_for_ffi(),
-                histogram_type.clone_for_ffi(),
                 &mut call_status,
             );
-            histogram_type.destroy();
-            bucket_count.destroy();
-            range_max.destroy();
-            range_min.destroy();
             meta.destroy();
             Self { handle }
         }
     }
-    pub fn accumulate_samples(&self, samples: Vec<i64>) -> () {
-        unsafe {
-            let this = self.clone_handle();
-            let samples = uniffi::FfiConverter::<crate::UniFfiTag>::lower(samples);
-            let mut call_status = uniffi::RustCallStatus::default();
-            let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_customdistributionmetric_accumulate_samples)(
-                this,
-                samples.clone_for_ffi(),
-                &mut call_status,
-            );
-            samples.destroy();
-            crate::util::LocalTryLift::try_lift(res).unwrap()
-        }
-    }
-    pub fn accumulate_single_sample(&self, sample: i64) -> () {
+    pub fn add_to_numerator(&self, amount: i32) -> () {
         unsafe {
             let this = self.clone_handle();
-            let sample = uniffi::FfiConverter::<crate::UniFfiTag>::lower(sample);
+            let amount = uniffi::FfiConverter::<crate::UniFfiTag>::lower(amount);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_customdistributionmetric_accumulate_single_sample)(
+                .uniffi_glean_core_fn_method_numeratormetric_add_to_numerator)(
                 this,
-                sample.clone_for_ffi(),
+                amount.clone_for_ffi(),
                 &mut call_status,
             );
-            sample.destroy();
+            amount.destroy();
             crate::util::LocalTryLift::try_lift(res).unwrap()
         }
     }
-    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<DistributionData> {
+    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<Rate> {
         unsafe {
             let this = self.clone_handle();
             let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_customdistributionmetric_test_get_value)(
+                .uniffi_glean_core_fn_method_numeratormetric_test_get_value)(
                 this,
                 ping_name.clone_for_ffi(),
                 &mut call_status,
@@ -1008,7 +2057,7 @@ impl CustomDistributionMetric {
             let error = uniffi::FfiConverter::<crate::UniFfiTag>::lower(error);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_customdistributionmetric_test_get_num_recorded_errors)(
+                .uniffi_glean_core_fn_method_numeratormetric_test_get_num_recorded_errors)(
                 this,
                 error.clone_for_ffi(),
                 &mut call_status,
@@ -1019,45 +2068,39 @@ impl CustomDistributionMetric {
     }
 }
 #[derive(uniffi::Record)]
-pub struct DatetimeMetric {
+pub struct TextMetric {
     handle: u64,
 }
-impl DatetimeMetric {
+impl TextMetric {
     unsafe fn clone_handle(&self) -> u64 {
         unsafe {
             let mut call_status = uniffi::RustCallStatus::default();
             (crate::GLEAN
-                .uniffi_glean_core_fn_clone_datetimemetric)(
-                self.handle,
-                &mut call_status,
-            )
+                .uniffi_glean_core_fn_clone_textmetric)(self.handle, &mut call_status)
         }
     }
 }
-impl DatetimeMetric {
-    pub fn new(meta: CommonMetricData, time_unit: TimeUnit) -> Self {
+impl TextMetric {
+    pub fn new(meta: CommonMetricData) -> Self {
         unsafe {
             let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
-            let time_unit = uniffi::FfiConverter::<crate::UniFfiTag>::lower(time_unit);
             let mut call_status = uniffi::RustCallStatus::default();
             let handle = (crate::GLEAN
-                .uniffi_glean_core_fn_constructor_datetimemetric_new)(
+                .uniffi_glean_core_fn_constructor_textmetric_new)(
                 meta.clone_for_ffi(),
-                time_unit.clone_for_ffi(),
                 &mut call_status,
             );
-            time_unit.destroy();
             meta.destroy();
             Self { handle }
         }
     }
-    pub fn set(&self, value: Option<Datetime>) -> () {
+    pub fn set(&self, value: String) -> () {
         unsafe {
             let this = self.clone_handle();
             let value = uniffi::FfiConverter::<crate::UniFfiTag>::lower(value);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_datetimemetric_set)(
+                .uniffi_glean_core_fn_method_textmetric_set)(
                 this,
                 value.clone_for_ffi(),
                 &mut call_status,
@@ -1066,28 +2109,13 @@ impl DatetimeMetric {
             crate::util::LocalTryLift::try_lift(res).unwrap()
         }
     }
-    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<Datetime> {
-        unsafe {
-            let this = self.clone_handle();
-            let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
-            let mut call_status = uniffi::RustCallStatus::default();
-            let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_datetimemetric_test_get_value)(
-                this,
-                ping_name.clone_for_ffi(),
-                &mut call_status,
-            );
-            ping_name.destroy();
-            crate::util::LocalTryLift::try_lift(res).unwrap()
-        }
-    }
-    pub fn test_get_value_as_string(&self, ping_name: Option<String>) -> Option<String> {
+    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<String> {
         unsafe {
             let this = self.clone_handle();
             let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_datetimemetric_test_get_value_as_string)(
+                .uniffi_glean_core_fn_method_textmetric_test_get_value)(
                 this,
                 ping_name.clone_for_ffi(),
                 &mut call_status,
@@ -1102,7 +2130,7 @@ impl DatetimeMetric {
             let error = uniffi::FfiConverter::<crate::UniFfiTag>::lower(error);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_datetimemetric_test_get_num_recorded_errors)(
+                .uniffi_glean_core_fn_method_textmetric_test_get_num_recorded_errors)(
                 this,
                 error.clone_for_ffi(),
                 &mut call_status,
@@ -1113,62 +2141,70 @@ impl DatetimeMetric {
     }
 }
 #[derive(uniffi::Record)]
-pub struct EventMetric {
+pub struct ObjectMetric {
     handle: u64,
 }
-impl EventMetric {
+impl ObjectMetric {
     unsafe fn clone_handle(&self) -> u64 {
         unsafe {
             let mut call_status = uniffi::RustCallStatus::default();
             (crate::GLEAN
-                .uniffi_glean_core_fn_clone_eventmetric)(self.handle, &mut call_status)
+                .uniffi_glean_core_fn_clone_objectmetric)(self.handle, &mut call_status)
         }
     }
 }
-impl EventMetric {
-    pub fn new(meta: CommonMetricData, allowed_extra_keys: Vec<String>) -> Self {
+impl ObjectMetric {
+    pub fn new(meta: CommonMetricData) -> Self {
         unsafe {
             let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
-            let allowed_extra_keys = uniffi::FfiConverter::<
-                crate::UniFfiTag,
-            >::lower(allowed_extra_keys);
             let mut call_status = uniffi::RustCallStatus::default();
             let handle = (crate::GLEAN
-                .uniffi_glean_core_fn_constructor_eventmetric_new)(
+                .uniffi_glean_core_fn_constructor_objectmetric_new)(
                 meta.clone_for_ffi(),
-                allowed_extra_keys.clone_for_ffi(),
                 &mut call_status,
             );
-            allowed_extra_keys.destroy();
             meta.destroy();
             Self { handle }
         }
     }
-    pub fn record(&self, extra: ::std::collections::HashMap<String, String>) -> () {
+    pub fn new_with_schema(meta: CommonMetricData, schema: String) -> Self {
+        unsafe {
+            let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
+            let schema = uniffi::FfiConverter::<crate::UniFfiTag>::lower(schema);
+            let mut call_status = uniffi::RustCallStatus::default();
+            let handle = (crate::GLEAN
+                .uniffi_glean_core_fn_constructor_objectmetric_new_with_schema)(
+                meta.clone_for_ffi(),
+                schema.clone_for_ffi(),
+                &mut call_status,
+            );
+            schema.destroy();
+            meta.destroy();
+            Self { handle }
+        }
+    }
+    pub fn set_string(&self, object: String) -> () {
         unsafe {
             let this = self.clone_handle();
-            let extra = uniffi::FfiConverter::<crate::UniFfiTag>::lower(extra);
+            let object = uniffi::FfiConverter::<crate::UniFfiTag>::lower(object);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_eventmetric_record)(
+                .uniffi_glean_core_fn_method_objectmetric_set_string)(
                 this,
-                extra.clone_for_ffi(),
+                object.clone_for_ffi(),
                 &mut call_status,
             );
-            extra.destroy();
+            object.destroy();
             crate::util::LocalTryLift::try_lift(res).unwrap()
         }
     }
-    pub fn test_get_value(
-        &self,
-        ping_name: Option<String>,
-    ) -> Option<Vec<RecordedEvent>> {
+    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<JsonValue> {
         unsafe {
             let this = self.clone_handle();
             let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_eventmetric_test_get_value)(
+                .uniffi_glean_core_fn_method_objectmetric_test_get_value)(
                 this,
                 ping_name.clone_for_ffi(),
                 &mut call_status,
@@ -1183,7 +2219,7 @@ impl EventMetric {
             let error = uniffi::FfiConverter::<crate::UniFfiTag>::lower(error);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_eventmetric_test_get_num_recorded_errors)(
+                .uniffi_glean_core_fn_method_objectmetric_test_get_num_recorded_errors)(
                 this,
                 error.clone_for_ffi(),
                 &mut call_status,
@@ -1192,71 +2228,89 @@ impl EventMetric {
             crate::util::LocalTryLift::try_lift(res).unwrap()
         }
     }
+    pub fn record_schema_error(&self) -> () {
+        unsafe {
+            let this = self.clone_handle();
+            let mut call_status = uniffi::RustCallStatus::default();
+            let res = (crate::GLEAN
+                .uniffi_glean_core_fn_method_objectmetric_record_schema_error)(
+                this,
+                &mut call_status,
+            );
+            crate::util::LocalTryLift::try_lift(res).unwrap()
+        }
+    }
 }
 #[derive(uniffi::Record)]
-pub struct RateMetric {
+pub struct DualLabeledCounterMetric {
     handle: u64,
 }
-impl RateMetric {
+impl DualLabeledCounterMetric {
     unsafe fn clone_handle(&self) -> u64 {
         unsafe {
             let mut call_status = uniffi::RustCallStatus::default();
             (crate::GLEAN
-                .uniffi_glean_core_fn_clone_ratemetric)(self.handle, &mut call_status)
+                .uniffi_glean_core_fn_clone_duallabeledcountermetric)(
+                self.handle,
+                &mut call_status,
+            )
         }
     }
 }
-impl RateMetric {
-    pub fn new(meta: CommonMetricData) -> Self {
+impl DualLabeledCounterMetric {
+    pub fn new(
+        meta: CommonMetricData,
+        keys: Option<Vec<CowString>>,
+        categories: Option<Vec<CowString>>,
+    ) -> Self {
         unsafe {
             let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
+            let keys = uniffi::FfiConverter::<crate::UniFfiTag>::lower(keys);
+            let categories = uniffi::FfiConverter::<crate::UniFfiTag>::lower(categories);
             let mut call_status = uniffi::RustCallStatus::default();
             let handle = (crate::GLEAN
-                .uniffi_glean_core_fn_constructor_ratemetric_new)(
+                .uniffi_glean_core_fn_constructor_duallabeledcountermetric_new)(
                 meta.clone_for_ffi(),
+                keys.clone_for_ffi(),
+                categories.clone_for_ffi(),
                 &mut call_status,
             );
+            categories.destroy();
+            keys.destroy();
             meta.destroy();
             Self { handle }
         }
     }
-    pub fn add_to_numerator(&self, amount: i32) -> () {
-        unsafe {
-            let this = self.clone_handle();
-            let amount = uniffi::FfiConverter::<crate::UniFfiTag>::lower(amount);
-            let mut call_status = uniffi::RustCallStatus::default();
-            let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_ratemetric_add_to_numerator)(
-                this,
-                amount.clone_for_ffi(),
-                &mut call_status,
-            );
-            amount.destroy();
-            crate::util::LocalTryLift::try_lift(res).unwrap()
-        }
-    }
-    pub fn add_to_denominator(&self, amount: i32) -> () {
+    pub fn get(&self, key: String, category: String) -> CounterMetric {
         unsafe {
             let this = self.clone_handle();
-            let amount = uniffi::FfiConverter::<crate::UniFfiTag>::lower(amount);
+            let key = uniffi::FfiConverter::<crate::UniFfiTag>::lower(key);
+            let category = uniffi::FfiConverter::<crate::UniFfiTag>::lower(category);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_ratemetric_add_to_denominator)(
+                .uniffi_glean_core_fn_method_duallabeledcountermetric_get)(
                 this,
-                amount.clone_for_ffi(),
+                key.clone_for_ffi(),
+                category.clone_for_ffi(),
                 &mut call_status,
             );
-            amount.destroy();
+            category.destroy();
+            key.destroy();
             crate::util::LocalTryLift::try_lift(res).unwrap()
         }
     }
-    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<Rate> {
+    pub fn test_get_value(
+        &self,
+        ping_name: Option<String>,
+    ) -> Option<
+        ::std::collections::HashMap<String, ::std::collections::HashMap<String, i32>>,
+    > {
         unsafe {
             let this = self.clone_handle();
             let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_ratemetric_test_get_value)(
+                .uniffi_glean_core_fn_method_duallabeledcountermetric_test_get_value)(
                 this,
                 ping_name.clone_for_ffi(),
                 &mut call_status,
@@ -1271,7 +2325,7 @@ impl RateMetric {
             let error = uniffi::FfiConverter::<crate::UniFfiTag>::lower(error);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_ratemetric_test_get_num_recorded_errors)(
+                .uniffi_glean_core_fn_method_duallabeledcountermetric_test_get_num_recorded_errors)(
                 this,
                 error.clone_for_ffi(),
                 &mut call_status,
@@ -1282,60 +2336,75 @@ impl RateMetric {
     }
 }
 #[derive(uniffi::Record)]
-pub struct DenominatorMetric {
+pub struct DualLabeledCounter64Metric {
     handle: u64,
 }
-impl DenominatorMetric {
+impl DualLabeledCounter64Metric {
     unsafe fn clone_handle(&self) -> u64 {
         unsafe {
             let mut call_status = uniffi::RustCallStatus::default();
             (crate::GLEAN
-                .uniffi_glean_core_fn_clone_denominatormetric)(
+                .uniffi_glean_core_fn_clone_duallabeledcounter64metric)(
                 self.handle,
                 &mut call_status,
             )
         }
     }
 }
-impl DenominatorMetric {
-    pub fn new(meta: CommonMetricData, numerators: Vec<CommonMetricData>) -> Self {
+impl DualLabeledCounter64Metric {
+    pub fn new(
+        meta: CommonMetricData,
+        keys: Option<Vec<CowString>>,
+        categories: Option<Vec<CowString>>,
+    ) -> Self {
         unsafe {
             let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
-            let numerators = uniffi::FfiConverter::<crate::UniFfiTag>::lower(numerators);
+            let keys = uniffi::FfiConverter::<crate::UniFfiTag>::lower(keys);
+            let categories = uniffi::FfiConverter::<crate::UniFfiTag>::lower(categories);
             let mut call_status = uniffi::RustCallStatus::default();
             let handle = (crate::GLEAN
-                .uniffi_glean_core_fn_constructor_denominatormetric_new)(
+                .uniffi_glean_core_fn_constructor_duallabeledcounter64metric_new)(
                 meta.clone_for_ffi(),
-                numerators.clone_for_ffi(),
+                keys.clone_for_ffi(),
+                categories.clone_for_ffi(),
                 &mut call_status,
             );
-            numerators.destroy();
+            categories.destroy();
+            keys.destroy();
             meta.destroy();
             Self { handle }
         }
     }
-    pub fn add(&self, amount: i32) -> () {
+    pub fn get(&self, key: String, category: String) -> Counter64Metric {
         unsafe {
             let this = self.clone_handle();
-            let amount = uniffi::FfiConverter::<crate::UniFfiTag>::lower(amount);
+            let key = uniffi::FfiConverter::<crate::UniFfiTag>::lower(key);
+            let category = uniffi::FfiConverter::<crate::UniFfiTag>::lower(category);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_denominatormetric_add)(
+                .uniffi_glean_core_fn_method_duallabeledcounter64metric_get)(
                 this,
-                amount.clone_for_ffi(),
+                key.clone_for_ffi(),
+                category.clone_for_ffi(),
                 &mut call_status,
             );
-            amount.destroy();
+            category.destroy();
+            key.destroy();
             crate::util::LocalTryLift::try_lift(res).unwrap()
         }
     }
-    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<i32> {
+    pub fn test_get_value(
+        &self,
+        ping_name: Option<String>,
+    ) -> Option<
+        ::std::collections::HashMap<String, ::std::collections::HashMap<String, i64>>,
+    > {
         unsafe {
             let this = self.clone_handle();
             let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_denominatormetric_test_get_value)(
+                .uniffi_glean_core_fn_method_duallabeledcounter64metric_test_get_value)(
                 this,
                 ping_name.clone_for_ffi(),
                 &mut call_status,
@@ -1350,7 +2419,7 @@ impl DenominatorMetric {
             let error = uniffi::FfiConverter::<crate::UniFfiTag>::lower(error);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_denominatormetric_test_get_num_recorded_errors)(
+                .uniffi_glean_core_fn_method_duallabeledcounter64metric_test_get_num_recorded_errors)(
                 this,
                 error.clone_for_ffi(),
                 &mut call_status,
@@ -1361,57 +2430,75 @@ impl DenominatorMetric {
     }
 }
 #[derive(uniffi::Record)]
-pub struct NumeratorMetric {
+pub struct DualLabeledQuantityMetric {
     handle: u64,
 }
-impl NumeratorMetric {
+impl DualLabeledQuantityMetric {
     unsafe fn clone_handle(&self) -> u64 {
         unsafe {
             let mut call_status = uniffi::RustCallStatus::default();
             (crate::GLEAN
-                .uniffi_glean_core_fn_clone_numeratormetric)(
+                .uniffi_glean_core_fn_clone_duallabeledquantitymetric)(
                 self.handle,
                 &mut call_status,
             )
         }
     }
 }
-impl NumeratorMetric {
-    pub fn new(meta: CommonMetricData) -> Self {
+impl DualLabeledQuantityMetric {
+    pub fn new(
+        meta: CommonMetricData,
+        keys: Option<Vec<CowString>>,
+        categories: Option<Vec<CowString>>,
+    ) -> Self {
         unsafe {
             let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
+            let keys = uniffi::FfiConverter::<crate::UniFfiTag>::lower(keys);
+            let categories = uniffi::FfiConverter::<crate::UniFfiTag>::lower(categories);
             let mut call_status = uniffi::RustCallStatus::default();
             let handle = (crate::GLEAN
-                .uniffi_glean_core_fn_constructor_numeratormetric_new)(
+                .uniffi_glean_core_fn_constructor_duallabeledquantitymetric_new)(
                 meta.clone_for_ffi(),
+                keys.clone_for_ffi(),
+                categories.clone_for_ffi(),
                 &mut call_status,
             );
+            categories.destroy();
+            keys.destroy();
             meta.destroy();
             Self { handle }
         }
     }
-    pub fn add_to_numerator(&self, amount: i32) -> () {
+    pub fn get(&self, key: String, category: String) -> QuantityMetric {
         unsafe {
             let this = self.clone_handle();
-            let amount = uniffi::FfiConverter::<crate::UniFfiTag>::lower(amount);
+            let key = uniffi::FfiConverter::<crate::UniFfiTag>::lower(key);
+            let category = uniffi::FfiConverter::<crate::UniFfiTag>::lower(category);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_numeratormetric_add_to_numerator)(
+                .uniffi_glean_core_fn_method_duallabeledquantitymetric_get)(
                 this,
-                amount.clone_for_ffi(),
+                key.clone_for_ffi(),
+                category.clone_for_ffi(),
                 &mut call_status,
             );
-            amount.destroy();
+            category.destroy();
+            key.destroy();
             crate::util::LocalTryLift::try_lift(res).unwrap()
         }
     }
-    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<Rate> {
+    pub fn test_get_value(
+        &self,
+        ping_name: Option<String>,
+    ) -> Option<
+        ::std::collections::HashMap<String, ::std::collections::HashMap<String, i64>>,
+    > {
         unsafe {
             let this = self.clone_handle();
             let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_numeratormetric_test_get_value)(
+                .uniffi_glean_core_fn_method_duallabeledquantitymetric_test_get_value)(
                 this,
                 ping_name.clone_for_ffi(),
                 &mut call_status,
@@ -1426,7 +2513,7 @@ impl NumeratorMetric {
             let error = uniffi::FfiConverter::<crate::UniFfiTag>::lower(error);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_numeratormetric_test_get_num_recorded_errors)(
+                .uniffi_glean_core_fn_method_duallabeledquantitymetric_test_get_num_recorded_errors)(
                 this,
                 error.clone_for_ffi(),
                 &mut call_status,
@@ -1437,54 +2524,75 @@ impl NumeratorMetric {
     }
 }
 #[derive(uniffi::Record)]
-pub struct TextMetric {
+pub struct DualLabeledBooleanMetric {
     handle: u64,
 }
-impl TextMetric {
+impl DualLabeledBooleanMetric {
     unsafe fn clone_handle(&self) -> u64 {
         unsafe {
             let mut call_status = uniffi::RustCallStatus::default();
             (crate::GLEAN
-                .uniffi_glean_core_fn_clone_textmetric)(self.handle, &mut call_status)
+                .uniffi_glean_core_fn_clone_duallabeledbooleanmetric)(
+                self.handle,
+                &mut call_status,
+            )
         }
     }
 }
-impl TextMetric {
-    pub fn new(meta: CommonMetricData) -> Self {
+impl DualLabeledBooleanMetric {
+    pub fn new(
+        meta: CommonMetricData,
+        keys: Option<Vec<CowString>>,
+        categories: Option<Vec<CowString>>,
+    ) -> Self {
         unsafe {
             let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
+            let keys = uniffi::FfiConverter::<crate::UniFfiTag>::lower(keys);
+            let categories = uniffi::FfiConverter::<crate::UniFfiTag>::lower(categories);
             let mut call_status = uniffi::RustCallStatus::default();
             let handle = (crate::GLEAN
-                .uniffi_glean_core_fn_constructor_textmetric_new)(
+                .uniffi_glean_core_fn_constructor_duallabeledbooleanmetric_new)(
                 meta.clone_for_ffi(),
+                keys.clone_for_ffi(),
+                categories.clone_for_ffi(),
                 &mut call_status,
             );
+            categories.destroy();
+            keys.destroy();
             meta.destroy();
             Self { handle }
         }
     }
-    pub fn set(&self, value: String) -> () {
+    pub fn get(&self, key: String, category: String) -> BooleanMetric {
         unsafe {
             let this = self.clone_handle();
-            let value = uniffi::FfiConverter::<crate::UniFfiTag>::lower(value);
+            let key = uniffi::FfiConverter::<crate::UniFfiTag>::lower(key);
+            let category = uniffi::FfiConverter::<crate::UniFfiTag>::lower(category);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_textmetric_set)(
+                .uniffi_glean_core_fn_method_duallabeledbooleanmetric_get)(
                 this,
-                value.clone_for_ffi(),
+                key.clone_for_ffi(),
+                category.clone_for_ffi(),
                 &mut call_status,
             );
-            value.destroy();
+            category.destroy();
+            key.destroy();
             crate::util::LocalTryLift::try_lift(res).unwrap()
         }
     }
-    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<String> {
+    pub fn test_get_value(
+        &self,
+        ping_name: Option<String>,
+    ) -> Option<
+        ::std::collections::HashMap<String, ::std::collections::HashMap<String, bool>>,
+    > {
         unsafe {
             let this = self.clone_handle();
             let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_textmetric_test_get_value)(
+                .uniffi_glean_core_fn_method_duallabeledbooleanmetric_test_get_value)(
                 this,
                 ping_name.clone_for_ffi(),
                 &mut call_status,
@@ -1499,7 +2607,7 @@ impl TextMetric {
             let error = uniffi::FfiConverter::<crate::UniFfiTag>::lower(error);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_textmetric_test_get_num_recorded_errors)(
+                .uniffi_glean_core_fn_method_duallabeledbooleanmetric_test_get_num_recorded_errors)(
                 this,
                 error.clone_for_ffi(),
                 &mut call_status,
@@ -1510,54 +2618,98 @@ impl TextMetric {
     }
 }
 #[derive(uniffi::Record)]
-pub struct ObjectMetric {
+pub struct DualLabeledCustomDistributionMetric {
     handle: u64,
 }
-impl ObjectMetric {
+impl DualLabeledCustomDistributionMetric {
     unsafe fn clone_handle(&self) -> u64 {
         unsafe {
             let mut call_status = uniffi::RustCallStatus::default();
             (crate::GLEAN
-                .uniffi_glean_core_fn_clone_objectmetric)(self.handle, &mut call_status)
+                .uniffi_glean_core_fn_clone_duallabeledcustomdistributionmetric)(
+                self.handle,
+                &mut call_status,
+            )
         }
     }
 }
-impl ObjectMetric {
-    pub fn new(meta: CommonMetricData) -> Self {
+impl DualLabeledCustomDistributionMetric {
+    pub fn new(
+        meta: CommonMetricData,
+        range_min: i64,
+        range_max: i64,
+        bucket_count: i64,
+        histogram_type: HistogramType,
+        keys: Option<Vec<CowString>>,
+        categories: Option<Vec<CowString>>,
+    ) -> Self {
         unsafe {
             let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
+            let range_min = uniffi::FfiConverter::<crate::UniFfiTag>::lower(range_min);
+            let range_max = uniffi::FfiConverter::<crate::UniFfiTag>::lower(range_max);
+            let bucket_count = uniffi::FfiConverter::<
+                crate::UniFfiTag,
+            >::lower(bucket_count);
+            let histogram_type = uniffi::FfiConverter::<
+                crate::UniFfiTag,
+            >::lower(histogram_type);
+            let keys = uniffi::FfiConverter::<crate::UniFfiTag>::lower(keys);
+            let categories = uniffi::FfiConverter::<crate::UniFfiTag>::lower(categories);
             let mut call_status = uniffi::RustCallStatus::default();
             let handle = (crate::GLEAN
-                .uniffi_glean_core_fn_constructor_objectmetric_new)(
+                .uniffi_glean_core_fn_constructor_duallabeledcustomdistributionmetric_new)(
                 meta.clone_for_ffi(),
+                range_min.clone_for_ffi(),
+                range_max.clone_for_ffi(),
+                bucket_count.clone_for_ffi(),
+                histogram_type.clone_for_ffi(),
+                keys.clone_for_ffi(),
+                categories.clone_for_ffi(),
                 &mut call_status,
             );
+            categories.destroy();
+            keys.destroy();
+            histogram_type.destroy();
+            bucket_count.destroy();
+            range_max.destroy();
+            range_min.destroy();
             meta.destroy();
             Self { handle }
         }
     }
-    pub fn set_string(&self, object: String) -> () {
+    pub fn get(&self, key: String, category: String) -> CustomDistributionMetric {
         unsafe {
             let this = self.clone_handle();
-            let object = uniffi::FfiConverter::<crate::UniFfiTag>::lower(object);
+            let key = uniffi::FfiConverter::<crate::UniFfiTag>::lower(key);
+            let category = uniffi::FfiConverter::<crate::UniFfiTag>::lower(category);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_objectmetric_set_string)(
+                .uniffi_glean_core_fn_method_duallabeledcustomdistributionmetric_get)(
                 this,
-                object.clone_for_ffi(),
+                key.clone_for_ffi(),
+                category.clone_for_ffi(),
                 &mut call_status,
             );
-            object.destroy();
+            category.destroy();
+            key.destroy();
             crate::util::LocalTryLift::try_lift(res).unwrap()
         }
     }
-    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<JsonValue> {
+    pub fn test_get_value(
+        &self,
+        ping_name: Option<String>,
+    ) -> Option<
+        ::std::collections::HashMap<
+            String,
+            ::std::collections::HashMap<String, DistributionData>,
+        >,
+    > {
         unsafe {
             let this = self.clone_handle();
             let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_objectmetric_test_get_value)(
+                .uniffi_glean_core_fn_method_duallabeledcustomdistributionmetric_test_get_value)(
                 this,
                 ping_name.clone_for_ffi(),
                 &mut call_status,
@@ -1572,7 +2724,7 @@ impl ObjectMetric {
             let error = uniffi::FfiConverter::<crate::UniFfiTag>::lower(error);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_objectmetric_test_get_num_recorded_errors)(
+                .uniffi_glean_core_fn_method_duallabeledcustomdistributionmetric_test_get_num_recorded_errors)(
                 this,
                 error.clone_for_ffi(),
                 &mut call_status,
@@ -1581,67 +2733,59 @@ impl ObjectMetric {
             crate::util::LocalTryLift::try_lift(res).unwrap()
         }
     }
-    pub fn record_schema_error(&self) -> () {
-        unsafe {
-            let this = self.clone_handle();
-            let mut call_status = uniffi::RustCallStatus::default();
-            let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_objectmetric_record_schema_error)(
-                this,
-                &mut call_status,
-            );
-            crate::util::LocalTryLift::try_lift(res).unwrap()
-        }
-    }
 }
 #[derive(uniffi::Record)]
-pub struct DualLabeledCounterMetric {
+pub struct DualLabeledTimingDistributionMetric {
     handle: u64,
 }
-impl DualLabeledCounterMetric {
+impl DualLabeledTimingDistributionMetric {
     unsafe fn clone_handle(&self) -> u64 {
         unsafe {
             let mut call_status = uniffi::RustCallStatus::default();
             (crate::GLEAN
-                .uniffi_glean_core_fn_clone_duallabeledcountermetric)(
+                .uniffi_glean_core_fn_clone_duallabeledtimingdistributionmetric)(
                 self.handle,
                 &mut call_status,
             )
         }
     }
 }
-impl DualLabeledCounterMetric {
+impl DualLabeledTimingDistributionMetric {
     pub fn new(
         meta: CommonMetricData,
+        time_unit: TimeUnit,
         keys: Option<Vec<CowString>>,
         categories: Option<Vec<CowString>>,
     ) -> Self {
         unsafe {
             let meta = uniffi::FfiConverter::<crate::UniFfiTag>::lower(meta);
+            let time_unit = uniffi::FfiConverter::<crate::UniFfiTag>::lower(time_unit);
             let keys = uniffi::FfiConverter::<crate::UniFfiTag>::lower(keys);
             let categories = uniffi::FfiConverter::<crate::UniFfiTag>::lower(categories);
             let mut call_status = uniffi::RustCallStatus::default();
             let handle = (crate::GLEAN
-                .uniffi_glean_core_fn_constructor_duallabeledcountermetric_new)(
+                .uniffi_glean_core_fn_constructor_duallabeledtimingdistributionmetric_new)(
                 meta.clone_for_ffi(),
+                time_unit.clone_for_ffi(),
                 keys.clone_for_ffi(),
                 categories.clone_for_ffi(),
                 &mut call_status,
             );
             categories.destroy();
             keys.destroy();
+            time_unit.destroy();
             meta.destroy();
             Self { handle }
         }
     }
-    pub fn get(&self, key: String, category: String) -> CounterMetric {
+    pub fn get(&self, key: String, category: String) -> TimingDistributionMetric {
         unsafe {
             let this = self.clone_handle();
             let key = uniffi::FfiConverter::<crate::UniFfiTag>::lower(key);
             let category = uniffi::FfiConverter::<crate::UniFfiTag>::lower(category);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_duallabeledcountermetric_get)(
+                .uniffi_glean_core_fn_method_duallabeledtimingdistributionmetric_get)(
                 this,
                 key.clone_for_ffi(),
                 category.clone_for_ffi(),
@@ -1656,14 +2800,17 @@ impl DualLabeledCounterMetric {
         &self,
         ping_name: Option<String>,
     ) -> Option<
-        ::std::collections::HashMap<String, ::std::collections::HashMap<String, i32>>,
+        ::std::collections::HashMap<
+            String,
+            ::std::collections::HashMap<String, DistributionData>,
+        >,
     > {
         unsafe {
             let this = self.clone_handle();
             let ping_name = uniffi::FfiConverter::<crate::UniFfiTag>::lower(ping_name);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_duallabeledcountermetric_test_get_value)(
+                .uniffi_glean_core_fn_method_duallabeledtimingdistributionmetric_test_get_value)(
                 this,
                 ping_name.clone_for_ffi(),
                 &mut call_status,
@@ -1678,7 +2825,7 @@ impl DualLabeledCounterMetric {
             let error = uniffi::FfiConverter::<crate::UniFfiTag>::lower(error);
             let mut call_status = uniffi::RustCallStatus::default();
             let res = (crate::GLEAN
-                .uniffi_glean_core_fn_method_duallabeledcountermetric_test_get_num_recorded_errors)(
+                .uniffi_glean_core_fn_method_duallabeledtimingdistributionmetric_test_get_num_recorded_errors)(
                 this,
                 error.clone_for_ffi(),
                 &mut call_status,
@@ -1704,7 +2851,18 @@ library_binding! {
     uniffi::RustBuffer; fn
     uniffi_glean_core_fn_method_countermetric_test_get_num_recorded_errors(handle : u64,
     error : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) -> i32; fn
-    uniffi_glean_core_fn_clone_timespanmetric(handle : u64, call_status : & mut
+    uniffi_glean_core_fn_clone_counter64metric(handle : u64, call_status : & mut
+    ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_constructor_counter64metric_new(meta : uniffi::RustBuffer,
+    call_status : & mut ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_method_counter64metric_add(handle : u64, amount : i64,
+    call_status : & mut ::uniffi::RustCallStatus) -> (); fn
+    uniffi_glean_core_fn_method_counter64metric_test_get_value(handle : u64, ping_name :
+    uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
+    uniffi::RustBuffer; fn
+    uniffi_glean_core_fn_method_counter64metric_test_get_num_recorded_errors(handle :
+    u64, error : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
+    i32; fn uniffi_glean_core_fn_clone_timespanmetric(handle : u64, call_status : & mut
     ::uniffi::RustCallStatus) -> u64; fn
     uniffi_glean_core_fn_constructor_timespanmetric_new(meta : uniffi::RustBuffer,
     time_unit : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) -> u64;
@@ -1756,13 +2914,26 @@ library_binding! {
     uniffi::RustBuffer; fn
     uniffi_glean_core_fn_method_stringlistmetric_test_get_num_recorded_errors(handle :
     u64, error : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
+    i32; fn uniffi_glean_core_fn_clone_enumstringmetric(handle : u64, call_status : & mut
+    ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_constructor_enumstringmetric_new(meta : uniffi::RustBuffer,
+    allowed_values : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
+    u64; fn uniffi_glean_core_fn_method_enumstringmetric_set(handle : u64, value :
+    uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) -> (); fn
+    uniffi_glean_core_fn_method_enumstringmetric_test_get_value(handle : u64, ping_name :
+    uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
+    uniffi::RustBuffer; fn
+    uniffi_glean_core_fn_method_enumstringmetric_test_get_num_recorded_errors(handle :
+    u64, error : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
     i32; fn uniffi_glean_core_fn_clone_urlmetric(handle : u64, call_status : & mut
     ::uniffi::RustCallStatus) -> u64; fn
     uniffi_glean_core_fn_constructor_urlmetric_new(meta : uniffi::RustBuffer, call_status
     : & mut ::uniffi::RustCallStatus) -> u64; fn
-    uniffi_glean_core_fn_method_urlmetric_set(handle : u64, value : uniffi::RustBuffer,
-    call_status : & mut ::uniffi::RustCallStatus) -> (); fn
-    uniffi_glean_core_fn_method_urlmetric_test_get_value(handle : u64, ping_name :
+    uniffi_glean_core_fn_constructor_urlmetric_new_with_sanitization(meta :
+    uniffi::RustBuffer, sanitization : uniffi::RustBuffer, call_status : & mut
+    ::uniffi::RustCallStatus) -> u64; fn uniffi_glean_core_fn_method_urlmetric_set(handle
+    : u64, value : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
+    (); fn uniffi_glean_core_fn_method_urlmetric_test_get_value(handle : u64, ping_name :
     uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
     uniffi::RustBuffer; fn
     uniffi_glean_core_fn_method_urlmetric_test_get_num_recorded_errors(handle : u64,
@@ -1791,11 +2962,37 @@ library_binding! {
     uniffi::RustBuffer; fn
     uniffi_glean_core_fn_method_quantitymetric_test_get_num_recorded_errors(handle : u64,
     error : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) -> i32; fn
-    uniffi_glean_core_fn_clone_timingdistributionmetric(handle : u64, call_status : & mut
+    uniffi_glean_core_fn_clone_gaugemetric(handle : u64, call_status : & mut
     ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_constructor_gaugemetric_new(meta : uniffi::RustBuffer,
+    call_status : & mut ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_method_gaugemetric_set(handle : u64, value : i64, call_status :
+    & mut ::uniffi::RustCallStatus) -> (); fn
+    uniffi_glean_core_fn_method_gaugemetric_test_get_value(handle : u64, ping_name :
+    uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
+    uniffi::RustBuffer; fn
+    uniffi_glean_core_fn_method_gaugemetric_test_get_num_recorded_errors(handle : u64,
+    error : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) -> i32; fn
+    uniffi_glean_core_fn_clone_distinctcountmetric(handle : u64, call_status : & mut
+    ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_constructor_distinctcountmetric_new(meta : uniffi::RustBuffer,
+    call_status : & mut ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_method_distinctcountmetric_add(handle : u64, value :
+    uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) -> (); fn
+    uniffi_glean_core_fn_method_distinctcountmetric_test_get_value(handle : u64,
+    ping_name : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
+    uniffi::RustBuffer; fn
+    uniffi_glean_core_fn_method_distinctcountmetric_test_get_num_recorded_errors(handle :
+    u64, error : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
+    i32; fn uniffi_glean_core_fn_clone_timingdistributionmetric(handle : u64, call_status
+    : & mut ::uniffi::RustCallStatus) -> u64; fn
     uniffi_glean_core_fn_constructor_timingdistributionmetric_new(meta :
     uniffi::RustBuffer, time_unit : uniffi::RustBuffer, call_status : & mut
     ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_constructor_timingdistributionmetric_new_with_bucketing(meta :
+    uniffi::RustBuffer, time_unit : uniffi::RustBuffer, buckets_per_magnitude :
+    uniffi::RustBuffer, max_sample_time : uniffi::RustBuffer, call_status : & mut
+    ::uniffi::RustCallStatus) -> u64; fn
     uniffi_glean_core_fn_method_timingdistributionmetric_start(handle : u64, call_status
     : & mut ::uniffi::RustCallStatus) -> uniffi::RustBuffer; fn
     uniffi_glean_core_fn_method_timingdistributionmetric_stop_and_accumulate(handle :
@@ -1817,6 +3014,10 @@ library_binding! {
     uniffi_glean_core_fn_constructor_memorydistributionmetric_new(meta :
     uniffi::RustBuffer, memory_unit : uniffi::RustBuffer, call_status : & mut
     ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_constructor_memorydistributionmetric_new_with_bucketing(meta :
+    uniffi::RustBuffer, memory_unit : uniffi::RustBuffer, buckets_per_magnitude :
+    uniffi::RustBuffer, max_bytes : uniffi::RustBuffer, call_status : & mut
+    ::uniffi::RustCallStatus) -> u64; fn
     uniffi_glean_core_fn_method_memorydistributionmetric_accumulate(handle : u64, sample
     : i64, call_status : & mut ::uniffi::RustCallStatus) -> (); fn
     uniffi_glean_core_fn_method_memorydistributionmetric_accumulate_samples(handle : u64,
@@ -1841,6 +3042,24 @@ library_binding! {
     uniffi::RustBuffer; fn
     uniffi_glean_core_fn_method_customdistributionmetric_test_get_num_recorded_errors(handle
     : u64, error : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
+    i32; fn uniffi_glean_core_fn_clone_quantiledistributionmetric(handle : u64,
+    call_status : & mut ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_constructor_quantiledistributionmetric_new(meta :
+    uniffi::RustBuffer, relative_accuracy : f64, call_status : & mut
+    ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_method_quantiledistributionmetric_accumulate_samples(handle :
+    u64, samples : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
+    (); fn
+    uniffi_glean_core_fn_method_quantiledistributionmetric_accumulate_single_sample(handle
+    : u64, sample : i64, call_status : & mut ::uniffi::RustCallStatus) -> (); fn
+    uniffi_glean_core_fn_method_quantiledistributionmetric_test_get_value(handle : u64,
+    ping_name : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
+    uniffi::RustBuffer; fn
+    uniffi_glean_core_fn_method_quantiledistributionmetric_test_get_quantile(handle :
+    u64, quantile : f64, ping_name : uniffi::RustBuffer, call_status : & mut
+    ::uniffi::RustCallStatus) -> uniffi::RustBuffer; fn
+    uniffi_glean_core_fn_method_quantiledistributionmetric_test_get_num_recorded_errors(handle
+    : u64, error : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
     i32; fn uniffi_glean_core_fn_clone_datetimemetric(handle : u64, call_status : & mut
     ::uniffi::RustCallStatus) -> u64; fn
     uniffi_glean_core_fn_constructor_datetimemetric_new(meta : uniffi::RustBuffer,
@@ -1860,6 +3079,9 @@ library_binding! {
     uniffi_glean_core_fn_constructor_eventmetric_new(meta : uniffi::RustBuffer,
     allowed_extra_keys : uniffi::RustBuffer, call_status : & mut
     ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_constructor_eventmetric_new_with_extra_types(meta :
+    uniffi::RustBuffer, allowed_extra_keys : uniffi::RustBuffer, call_status : & mut
+    ::uniffi::RustCallStatus) -> u64; fn
     uniffi_glean_core_fn_method_eventmetric_record(handle : u64, extra :
     uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) -> (); fn
     uniffi_glean_core_fn_method_eventmetric_test_get_value(handle : u64, ping_name :
@@ -1867,6 +3089,21 @@ library_binding! {
     uniffi::RustBuffer; fn
     uniffi_glean_core_fn_method_eventmetric_test_get_num_recorded_errors(handle : u64,
     error : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) -> i32; fn
+    uniffi_glean_core_fn_clone_spanmetric(handle : u64, call_status : & mut
+    ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_constructor_spanmetric_new(meta : uniffi::RustBuffer,
+    allowed_extra_keys : uniffi::RustBuffer, call_status : & mut
+    ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_method_spanmetric_start(handle : u64, parent :
+    uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
+    uniffi::RustBuffer; fn uniffi_glean_core_fn_method_spanmetric_end(handle : u64, span
+    : uniffi::RustBuffer, extra : uniffi::RustBuffer, call_status : & mut
+    ::uniffi::RustCallStatus) -> (); fn
+    uniffi_glean_core_fn_method_spanmetric_test_get_value(handle : u64, ping_name :
+    uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
+    uniffi::RustBuffer; fn
+    uniffi_glean_core_fn_method_spanmetric_test_get_num_recorded_errors(handle : u64,
+    error : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) -> i32; fn
     uniffi_glean_core_fn_clone_ratemetric(handle : u64, call_status : & mut
     ::uniffi::RustCallStatus) -> u64; fn
     uniffi_glean_core_fn_constructor_ratemetric_new(meta : uniffi::RustBuffer,
@@ -1917,6 +3154,9 @@ library_binding! {
     ::uniffi::RustCallStatus) -> u64; fn
     uniffi_glean_core_fn_constructor_objectmetric_new(meta : uniffi::RustBuffer,
     call_status : & mut ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_constructor_objectmetric_new_with_schema(meta :
+    uniffi::RustBuffer, schema : uniffi::RustBuffer, call_status : & mut
+    ::uniffi::RustCallStatus) -> u64; fn
     uniffi_glean_core_fn_method_objectmetric_set_string(handle : u64, object :
     uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) -> (); fn
     uniffi_glean_core_fn_method_objectmetric_test_get_value(handle : u64, ping_name :
@@ -1939,6 +3179,72 @@ library_binding! {
     uniffi::RustBuffer; fn
     uniffi_glean_core_fn_method_duallabeledcountermetric_test_get_num_recorded_errors(handle
     : u64, error : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
+    i32; fn uniffi_glean_core_fn_clone_duallabeledcounter64metric(handle : u64,
+    call_status : & mut ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_constructor_duallabeledcounter64metric_new(meta :
+    uniffi::RustBuffer, keys : uniffi::RustBuffer, categories : uniffi::RustBuffer,
+    call_status : & mut ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_method_duallabeledcounter64metric_get(handle : u64, key :
+    uniffi::RustBuffer, category : uniffi::RustBuffer, call_status : & mut
+    ::uniffi::RustCallStatus) -> uniffi::RustBuffer; fn
+    uniffi_glean_core_fn_method_duallabeledcounter64metric_test_get_value(handle : u64,
+    ping_name : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
+    uniffi::RustBuffer; fn
+    uniffi_glean_core_fn_method_duallabeledcounter64metric_test_get_num_recorded_errors(handle
+    : u64, error : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
+    i32; fn uniffi_glean_core_fn_clone_duallabeledquantitymetric(handle : u64,
+    call_status : & mut ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_constructor_duallabeledquantitymetric_new(meta :
+    uniffi::RustBuffer, keys : uniffi::RustBuffer, categories : uniffi::RustBuffer,
+    call_status : & mut ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_method_duallabeledquantitymetric_get(handle : u64, key :
+    uniffi::RustBuffer, category : uniffi::RustBuffer, call_status : & mut
+    ::uniffi::RustCallStatus) -> uniffi::RustBuffer; fn
+    uniffi_glean_core_fn_method_duallabeledquantitymetric_test_get_value(handle : u64,
+    ping_name : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
+    uniffi::RustBuffer; fn
+    uniffi_glean_core_fn_method_duallabeledquantitymetric_test_get_num_recorded_errors(handle
+    : u64, error : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
+    i32; fn uniffi_glean_core_fn_clone_duallabeledbooleanmetric(handle : u64, call_status
+    : & mut ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_constructor_duallabeledbooleanmetric_new(meta :
+    uniffi::RustBuffer, keys : uniffi::RustBuffer, categories : uniffi::RustBuffer,
+    call_status : & mut ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_method_duallabeledbooleanmetric_get(handle : u64, key :
+    uniffi::RustBuffer, category : uniffi::RustBuffer, call_status : & mut
+    ::uniffi::RustCallStatus) -> uniffi::RustBuffer; fn
+    uniffi_glean_core_fn_method_duallabeledbooleanmetric_test_get_value(handle : u64,
+    ping_name : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
+    uniffi::RustBuffer; fn
+    uniffi_glean_core_fn_method_duallabeledbooleanmetric_test_get_num_recorded_errors(handle
+    : u64, error : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
+    i32; fn uniffi_glean_core_fn_clone_duallabeledcustomdistributionmetric(handle : u64,
+    call_status : & mut ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_constructor_duallabeledcustomdistributionmetric_new(meta :
+    uniffi::RustBuffer, range_min : i64, range_max : i64, bucket_count : i64,
+    histogram_type : uniffi::RustBuffer, keys : uniffi::RustBuffer, categories :
+    uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_method_duallabeledcustomdistributionmetric_get(handle : u64, key
+    : uniffi::RustBuffer, category : uniffi::RustBuffer, call_status : & mut
+    ::uniffi::RustCallStatus) -> uniffi::RustBuffer; fn
+    uniffi_glean_core_fn_method_duallabeledcustomdistributionmetric_test_get_value(handle
+    : u64, ping_name : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus)
+    -> uniffi::RustBuffer; fn
+    uniffi_glean_core_fn_method_duallabeledcustomdistributionmetric_test_get_num_recorded_errors(handle
+    : u64, error : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
+    i32; fn uniffi_glean_core_fn_clone_duallabeledtimingdistributionmetric(handle : u64,
+    call_status : & mut ::uniffi::RustCallStatus) -> u64; fn
+    uniffi_glean_core_fn_constructor_duallabeledtimingdistributionmetric_new(meta :
+    uniffi::RustBuffer, time_unit : uniffi::RustBuffer, keys : uniffi::RustBuffer,
+    categories : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
+    u64; fn uniffi_glean_core_fn_method_duallabeledtimingdistributionmetric_get(handle :
+    u64, key : uniffi::RustBuffer, category : uniffi::RustBuffer, call_status : & mut
+    ::uniffi::RustCallStatus) -> uniffi::RustBuffer; fn
+    uniffi_glean_core_fn_method_duallabeledtimingdistributionmetric_test_get_value(handle
+    : u64, ping_name : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus)
+    -> uniffi::RustBuffer; fn
+    uniffi_glean_core_fn_method_duallabeledtimingdistributionmetric_test_get_num_recorded_errors(handle
+    : u64, error : uniffi::RustBuffer, call_status : & mut ::uniffi::RustCallStatus) ->
     i32;
 }
 
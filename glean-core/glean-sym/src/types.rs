@@ -34,6 +34,8 @@ pub struct CommonMetricData {
     pub disabled: bool,
     pub dynamic_label: Option<DynamicLabelType>,
     pub in_session: bool,
+    pub ldp_epsilon: Option<f64>,
+    pub ldp_max_increment: Option<u32>,
 }
 
 #[derive(uniffi::Record)]
@@ -42,6 +44,30 @@ pub struct Rate {
     denominator: i32,
 }
 
+#[derive(uniffi::Record)]
+pub struct GaugeData {
+    count: i64,
+    sum: i64,
+    min: i64,
+    max: i64,
+    last: i64,
+}
+
+#[derive(uniffi::Enum, Default)]
+pub enum UrlReduction {
+    #[default]
+    Full,
+    Origin,
+    RegistrableDomain,
+}
+
+#[derive(uniffi::Record, Default)]
+pub struct UrlSanitization {
+    pub strip_query_and_fragment: bool,
+    pub strip_userinfo: bool,
+    pub reduce_to: UrlReduction,
+}
+
 pub type JsonValue = String;
 
 #[derive(uniffi::Record, Debug)]
@@ -79,6 +105,10 @@ pub struct DistributionData {
     values: ::std::collections::HashMap<i64, i64>,
     sum: i64,
     count: i64,
+    buckets_per_magnitude: Option<u32>,
+    upper_bounds: ::std::collections::HashMap<i64, i64>,
+    max_sample_time: Option<u64>,
+    max_bytes: Option<u64>,
 }
 
 #[derive(uniffi::Record)]
@@ -86,12 +116,25 @@ pub struct TimerId {
     id: u64,
 }
 
+#[derive(uniffi::Record)]
+pub struct SpanId {
+    id: u64,
+}
+
+#[derive(uniffi::Enum)]
+pub enum ExtraKeyType {
+    String,
+    Quantity,
+    Boolean,
+}
+
 #[derive(uniffi::Enum)]
 pub enum ErrorType {
     InvalidValue,
     InvalidLabel,
     InvalidState,
     InvalidOverflow,
+    InvalidType,
 }
 
 #[derive(uniffi::Enum)]
@@ -79,17 +79,27 @@ forward_ffi_converter!(Option<String>);
 forward_ffi_converter!(Vec<String>);
 forward_ffi_converter!(Vec<i64>);
 forward_ffi_converter!(Vec<types::CommonMetricData>);
+forward_ffi_converter!(Vec<types::CowString>);
 forward_ffi_converter!(Option<types::DistributionData>);
 forward_ffi_converter!(Option<types::Datetime>);
 forward_ffi_converter!(Option<Vec<String>>);
 forward_ffi_converter!(Option<Vec<types::RecordedEvent>>);
 forward_ffi_converter!(Option<types::Rate>);
+forward_ffi_converter!(Option<types::SpanId>);
+forward_ffi_converter!(Option<types::GaugeData>);
 forward_ffi_converter!(Option<Vec<types::CowString>>);
 forward_ffi_converter!(HashMap<String, String>);
+forward_ffi_converter!(HashMap<String, types::ExtraKeyType>);
 forward_ffi_converter!(Option<HashMap<String, HashMap<String, i32>>>);
+forward_ffi_converter!(Option<HashMap<String, HashMap<String, i64>>>);
+forward_ffi_converter!(Option<HashMap<String, HashMap<String, bool>>>);
+forward_ffi_converter!(Option<HashMap<String, HashMap<String, types::DistributionData>>>);
 forward_ffi_converter!(Option<i8>);
 forward_ffi_converter!(Option<i32>);
 forward_ffi_converter!(Option<i64>);
+forward_ffi_converter!(Option<u32>);
+forward_ffi_converter!(Option<u64>);
+forward_ffi_converter!(Option<f64>);
 forward_ffi_converter!(Option<bool>);
 uniffi::derive_ffi_traits!(local types::CowString);
 
@@ -139,7 +149,7 @@ macro_rules! impl_clone_ffi_arg_primitive {
     }
 }
 
-impl_clone_ffi_arg_primitive!(i8, i32, i64);
+impl_clone_ffi_arg_primitive!(i8, i32, i64, f64);
 
 impl CloneFfiArg<RustBuffer> for RustBuffer {
     fn clone_for_ffi(&self) -> RustBuffer {
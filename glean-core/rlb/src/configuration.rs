@@ -65,6 +65,13 @@ pub struct Configuration {
     pub session_sample_rate: f64,
     /// Inactivity timeout for AUTO mode sessions. Default: 30 minutes.
     pub session_inactivity_timeout: Duration,
+    /// Maximum size in bytes of the database on disk.
+    /// If exceeded, the least recently written user-lifetime labeled entries are evicted.
+    /// Default: `None` (no limit).
+    pub database_size_budget: Option<u64>,
+    /// Caps on the number and size of events queued per event store.
+    /// Default: `None`
+    pub event_store_limits: Option<crate::EventStoreLimits>,
 }
 
 /// Configuration builder.
@@ -127,6 +134,13 @@ pub struct Builder {
     pub session_sample_rate: f64,
     /// Inactivity timeout for AUTO mode sessions. Default: 30 minutes.
     pub session_inactivity_timeout: Duration,
+    /// Maximum size in bytes of the database on disk.
+    /// If exceeded, the least recently written user-lifetime labeled entries are evicted.
+    /// Default: `None` (no limit).
+    pub database_size_budget: Option<u64>,
+    /// Caps on the number and size of events queued per event store.
+    /// Default: `None`
+    pub event_store_limits: Option<crate::EventStoreLimits>,
 }
 
 impl Builder {
@@ -157,6 +171,8 @@ impl Builder {
             session_mode: SessionMode::Auto,
             session_sample_rate: 1.0,
             session_inactivity_timeout: Duration::from_secs(30 * 60),
+            database_size_budget: None,
+            event_store_limits: None,
         }
     }
 
@@ -183,6 +199,8 @@ impl Builder {
             session_mode: self.session_mode,
             session_sample_rate: self.session_sample_rate,
             session_inactivity_timeout: self.session_inactivity_timeout,
+            database_size_budget: self.database_size_budget,
+            event_store_limits: self.event_store_limits,
         }
     }
 
@@ -275,4 +293,16 @@ impl Builder {
         self.ping_lifetime_max_time = value;
         self
     }
+
+    /// Set the maximum size in bytes of the database on disk.
+    pub fn with_database_size_budget(mut self, value: u64) -> Self {
+        self.database_size_budget = Some(value);
+        self
+    }
+
+    /// Set the caps on the number and size of events queued per event store.
+    pub fn with_event_store_limits(mut self, value: crate::EventStoreLimits) -> Self {
+        self.event_store_limits = Some(value);
+        self
+    }
 }
@@ -39,8 +39,9 @@ pub use glean_core::{
         Datetime, DistributionData, MemoryUnit, MetricIdentifier, Rate, RecordedEvent,
         TestGetValue, TimeUnit, TimerId,
     },
-    traits, AttributionMetrics, CommonMetricData, DistributionMetrics, Error, ErrorType, Glean,
-    HistogramType, LabeledMetricData, Lifetime, PingRateLimit, RecordedExperiment, Result,
+    traits, AttributionMetrics, CommonMetricData, DistributionMetrics, Error, ErrorType,
+    EventStoreLimits, Glean, HistogramType, LabeledMetricData, Lifetime, PingRateLimit,
+    RecordedExperiment, Result,
 };
 
 mod configuration;
@@ -131,6 +132,8 @@ fn initialize_internal(cfg: Configuration, client_info: ClientInfoMetrics) -> Op
         ping_lifetime_max_time: cfg.ping_lifetime_max_time.as_millis() as u64,
         max_pending_pings_count: None,
         max_pending_pings_directory_size: None,
+        database_size_budget: cfg.database_size_budget,
+        event_store_limits: cfg.event_store_limits,
         session_mode: cfg.session_mode,
         session_sample_rate: cfg.session_sample_rate,
         session_inactivity_timeout_ms: cfg.session_inactivity_timeout.as_millis() as u64,
@@ -365,6 +368,52 @@ pub fn persist_ping_lifetime_data() {
     glean_core::glean_persist_ping_lifetime_data();
 }
 
+/// Exports all user-lifetime metrics into a versioned bundle for device migration.
+///
+/// **WARNING** This function will block if Glean hasn't been initialized.
+///
+/// See [`glean_core::Glean::export_user_data`].
+///
+/// # Returns
+///
+/// The JSON-encoded bundle or `None` if exporting failed.
+pub fn export_user_data(include_client_id: bool) -> Option<String> {
+    glean_core::glean_export_user_data(include_client_id)
+}
+
+/// Imports a user data bundle created by [`export_user_data`] on another device.
+///
+/// This only succeeds on the first run of a profile.
+///
+/// **WARNING** This function will block if Glean hasn't been initialized.
+///
+/// See [`glean_core::Glean::import_user_data`].
+///
+/// # Returns
+///
+/// Whether the bundle was imported.
+pub fn import_user_data(bundle: String) -> bool {
+    glean_core::glean_import_user_data(bundle)
+}
+
+/// Collects the payload of a ping (by its name) without submitting it.
+///
+/// No stores are cleared, the ping's sequence number is not incremented
+/// and no upload is queued.
+///
+/// **WARNING** This function will block if Glean hasn't been initialized and
+/// should only be used for debug purposes.
+///
+/// See [`glean_core::Glean::preview_ping`].
+///
+/// # Returns
+///
+/// The pretty-printed JSON payload,
+/// or `None` if the ping is unknown or would not be sent because it is empty.
+pub fn preview_ping(ping: &str) -> Option<String> {
+    glean_core::glean_preview_ping(ping.to_string())
+}
+
 /// Gets a list of currently registered ping names.
 ///
 /// **WARNING** This function will block if Glean hasn't been initialized and
@@ -16,9 +16,11 @@ use glean_core::upload::PingUploadTask;
 pub use glean_core::upload::{PingRequest, UploadResult, UploadTaskAction};
 
 pub use http_uploader::*;
+pub use otlp_uploader::OtlpUploader;
 use thread_state::{AtomicState, State};
 
 mod http_uploader;
+mod otlp_uploader;
 
 /// Everything you need to request a ping to be uploaded.
 pub struct PingUploadRequest {
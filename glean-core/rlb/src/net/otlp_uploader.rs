@@ -0,0 +1,182 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use glean_core::upload::OtlpExport;
+
+use crate::net::{CapablePingUploadRequest, PingUploadRequest, PingUploader, UploadResult};
+
+/// An uploader exporting pings to an OpenTelemetry (OTLP) collector.
+///
+/// Each ping is converted into OTLP metrics and logs,
+/// which are posted to the `/v1/metrics` and `/v1/logs` endpoints of the collector
+/// using the [OTLP/HTTP JSON encoding](https://opentelemetry.io/docs/specs/otlp/#json-protobuf-encoding).
+/// The actual delivery is left to the wrapped `transport`.
+///
+/// See [`OtlpExport`] for how metric types are converted.
+/// The client id is not sent, unless enabled with [`with_client_id`](Self::with_client_id).
+#[derive(Debug)]
+pub struct OtlpUploader {
+    endpoint: String,
+    service_name: String,
+    include_client_id: bool,
+    transport: Box<dyn PingUploader + 'static>,
+}
+
+impl OtlpUploader {
+    /// Creates a new OTLP uploader.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - The base URL of the collector, e.g. `http://localhost:4318`.
+    /// * `service_name` - The `service.name` to report the data for, usually the application id.
+    /// * `transport` - The uploader to deliver the OTLP requests with.
+    pub fn new(
+        endpoint: impl Into<String>,
+        service_name: impl Into<String>,
+        transport: impl PingUploader + 'static,
+    ) -> Self {
+        Self {
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            service_name: service_name.into(),
+            include_client_id: false,
+            transport: Box::new(transport),
+        }
+    }
+
+    /// Sets whether to report the client id as the `glean.client_id` resource attribute.
+    ///
+    /// Only enable this for collectors that are allowed to receive the client id.
+    pub fn with_client_id(mut self, value: bool) -> Self {
+        self.include_client_id = value;
+        self
+    }
+}
+
+impl PingUploader for OtlpUploader {
+    /// Converts a ping and sends it to the collector.
+    ///
+    /// Pings without any exportable data are not sent.
+    /// If the collector fails to accept a request, its result is returned.
+    /// Once the collector accepted a request, later failures are unrecoverable,
+    /// as retrying the ping would deliver the accepted data again.
+    ///
+    /// # Arguments
+    ///
+    /// * `upload_request` - the requested upload.
+    fn upload(&self, upload_request: CapablePingUploadRequest) -> UploadResult {
+        let CapablePingUploadRequest {
+            request,
+            capabilities,
+        } = upload_request;
+
+        let Some(export) = OtlpExport::from_body(
+            &self.service_name,
+            &request.ping_name,
+            &request.body,
+            self.include_client_id,
+        ) else {
+            log::warn!("Ping {} can't be converted to OTLP", request.ping_name);
+            return UploadResult::unrecoverable_failure();
+        };
+
+        let payloads = [("metrics", export.metrics), ("logs", export.logs)];
+        let mut accepted = false;
+        for (signal, payload) in payloads {
+            let Some(payload) = payload else {
+                continue;
+            };
+
+            let otlp_request = CapablePingUploadRequest {
+                request: PingUploadRequest {
+                    url: format!("{}/v1/{}", self.endpoint, signal),
+                    body: payload.to_string().into_bytes(),
+                    headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+                    body_has_info_sections: false,
+                    ping_name: request.ping_name.clone(),
+                },
+                capabilities: capabilities.clone(),
+            };
+
+            match self.transport.upload(otlp_request) {
+                UploadResult::HttpStatus { code: 200..=299 } => accepted = true,
+                result if accepted => {
+                    log::warn!(
+                        "Collector failed to accept the {} of ping {} after accepting other data: {:?}",
+                        signal,
+                        request.ping_name,
+                        result
+                    );
+                    return UploadResult::unrecoverable_failure();
+                }
+                result => return result,
+            }
+        }
+
+        UploadResult::http_status(200)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    /// A collector that accepts the first request and fails the others.
+    #[derive(Debug)]
+    struct FlakyCollector {
+        urls: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl PingUploader for FlakyCollector {
+        fn upload(&self, upload_request: CapablePingUploadRequest) -> UploadResult {
+            let request = upload_request.capable(|_| true).unwrap();
+            let mut urls = self.urls.lock().unwrap();
+            urls.push(request.url);
+            if urls.len() == 1 {
+                UploadResult::http_status(200)
+            } else {
+                UploadResult::recoverable_failure()
+            }
+        }
+    }
+
+    #[test]
+    fn failures_after_accepted_data_are_not_retried() {
+        let urls = Arc::new(Mutex::new(Vec::new()));
+        let collector = FlakyCollector {
+            urls: Arc::clone(&urls),
+        };
+        let uploader = OtlpUploader::new("http://localhost:4318", "app", collector);
+
+        let ping = json!({
+            "ping_info": { "start_time": "2024-01-01T00:00+00:00", "end_time": "2024-01-01T01:00+00:00" },
+            "client_info": { "telemetry_sdk_build": "1.0.0" },
+            "metrics": { "counter": { "app.clicks": 1 } },
+            "events": [{ "timestamp": 0, "category": "app", "name": "opened" }],
+        });
+        let request = CapablePingUploadRequest {
+            request: PingUploadRequest {
+                url: "/submit/app/custom/1/id".to_string(),
+                body: ping.to_string().into_bytes(),
+                headers: vec![],
+                body_has_info_sections: true,
+                ping_name: "custom".to_string(),
+            },
+            capabilities: vec![],
+        };
+
+        assert!(matches!(
+            uploader.upload(request),
+            UploadResult::UnrecoverableFailure { .. }
+        ));
+        assert_eq!(
+            vec![
+                "http://localhost:4318/v1/metrics".to_string(),
+                "http://localhost:4318/v1/logs".to_string()
+            ],
+            *urls.lock().unwrap()
+        );
+    }
+}
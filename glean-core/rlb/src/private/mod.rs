@@ -10,10 +10,20 @@ mod ping;
 
 pub use event::EventMetric;
 pub use glean_core::BooleanMetric;
+pub use glean_core::Counter64Metric;
 pub use glean_core::CounterMetric;
 pub use glean_core::DenominatorMetric;
+pub use glean_core::DistinctCountMetric;
+pub use glean_core::DualLabeledBooleanMetric;
+pub use glean_core::DualLabeledCounter64Metric;
 pub use glean_core::DualLabeledCounterMetric;
+pub use glean_core::DualLabeledCustomDistributionMetric;
+pub use glean_core::DualLabeledQuantityMetric;
+pub use glean_core::DualLabeledTimingDistributionMetric;
+pub use glean_core::EnumStringMetric;
+pub use glean_core::GaugeMetric;
 pub use glean_core::NumeratorMetric;
+pub use glean_core::QuantileDistributionMetric;
 pub use glean_core::QuantityMetric;
 pub use glean_core::RateMetric;
 pub use glean_core::RecordedExperiment;
@@ -28,6 +38,8 @@ pub use glean_core::{CustomDistributionMetric, LocalCustomDistribution};
 pub use glean_core::{Datetime, DatetimeMetric};
 pub use glean_core::{LocalMemoryDistribution, MemoryDistributionMetric};
 pub use glean_core::{LocalTimingDistribution, TimingDistributionMetric};
+pub use glean_core::{PingOptions, PingSnapshotFilter, PingTimeSchedule, PseudonymousIdRotation};
+pub use glean_core::{SpanId, SpanMetric};
 pub use object::ObjectMetric;
 pub use ping::PingType;
 
@@ -7,6 +7,7 @@ use std::{
     sync::{Arc, Mutex},
 };
 
+use glean_core::metrics::PingOptions;
 use malloc_size_of::MallocSizeOf;
 
 type BoxedCallback = Box<dyn FnOnce(Option<&str>) + Send + 'static>;
@@ -85,6 +86,44 @@ impl PingType {
         }
     }
 
+    /// Creates a new ping type with additional options,
+    /// e.g. a schedule to submit the ping on.
+    ///
+    /// See [`PingType::new`] for the other arguments.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_options<A: Into<String>>(
+        name: A,
+        include_client_id: bool,
+        send_if_empty: bool,
+        precise_timestamps: bool,
+        include_info_sections: bool,
+        enabled: bool,
+        schedules_pings: Vec<String>,
+        reason_codes: Vec<String>,
+        follows_collection_enabled: bool,
+        uploader_capabilities: Vec<String>,
+        options: PingOptions,
+    ) -> Self {
+        let inner = glean_core::metrics::PingType::new_with_options(
+            name.into(),
+            include_client_id,
+            send_if_empty,
+            precise_timestamps,
+            include_info_sections,
+            enabled,
+            schedules_pings,
+            reason_codes,
+            follows_collection_enabled,
+            uploader_capabilities,
+            options,
+        );
+
+        Self {
+            inner,
+            test_callback: Arc::new(Default::default()),
+        }
+    }
+
     /// Enable or disable a ping.
     ///
     /// Disabling a ping causes all data for that ping to be removed from storage
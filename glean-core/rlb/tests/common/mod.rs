@@ -8,7 +8,10 @@
 
 use std::{panic, process};
 
-use glean::{private::PingType, ClientInfoMetrics, Configuration};
+use glean::{
+    private::{PingOptions, PingType},
+    ClientInfoMetrics, Configuration,
+};
 
 /// Initialize the env logger for a test environment.
 ///
@@ -64,6 +67,7 @@ pub struct PingBuilder {
     reason_codes: Vec<String>,
     follows_collection_enabled: bool,
     uploader_capabilities: Vec<String>,
+    options: PingOptions,
 }
 
 impl PingBuilder {
@@ -79,11 +83,12 @@ impl PingBuilder {
             reason_codes: vec![],
             follows_collection_enabled: true,
             uploader_capabilities: vec![],
+            options: PingOptions::default(),
         }
     }
 
     pub fn build(self) -> PingType {
-        PingType::new(
+        PingType::new_with_options(
             self.name,
             self.include_client_id,
             self.send_if_empty,
@@ -94,6 +99,7 @@ impl PingBuilder {
             self.reason_codes,
             self.follows_collection_enabled,
             self.uploader_capabilities,
+            self.options,
         )
     }
 
@@ -126,4 +132,14 @@ impl PingBuilder {
         self.include_client_id = value;
         self
     }
+
+    pub fn with_reason_codes(mut self, value: Vec<String>) -> Self {
+        self.reason_codes = value;
+        self
+    }
+
+    pub fn with_options(mut self, value: PingOptions) -> Self {
+        self.options = value;
+        self
+    }
 }
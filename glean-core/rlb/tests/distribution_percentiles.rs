@@ -0,0 +1,83 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! This integration test should model how the RLB is used when embedded in another Rust application
+//! (e.g. FOG/Firefox Desktop).
+//!
+//! We write a single test scenario per file to avoid any state keeping across runs
+//! (different files run as different processes).
+
+mod common;
+
+use glean::ConfigurationBuilder;
+
+mod metrics {
+    use glean::private::*;
+    use glean::{Lifetime, MemoryUnit, TimeUnit};
+    use glean_core::CommonMetricData;
+    use once_cell::sync::Lazy;
+
+    #[allow(non_upper_case_globals)]
+    pub static measure: Lazy<MemoryDistributionMetric> = Lazy::new(|| {
+        MemoryDistributionMetric::new(
+            CommonMetricData {
+                name: "measure".into(),
+                category: "sample".into(),
+                send_in_pings: vec!["store1".into()],
+                lifetime: Lifetime::Ping,
+                disabled: false,
+                ..Default::default()
+            },
+            MemoryUnit::Kilobyte,
+        )
+    });
+
+    #[allow(non_upper_case_globals)]
+    pub static duration: Lazy<TimingDistributionMetric> = Lazy::new(|| {
+        TimingDistributionMetric::new(
+            CommonMetricData {
+                name: "duration".into(),
+                category: "sample".into(),
+                send_in_pings: vec!["store1".into()],
+                lifetime: Lifetime::Ping,
+                disabled: false,
+                ..Default::default()
+            },
+            TimeUnit::Millisecond,
+        )
+    });
+}
+
+/// Test scenario: Percentiles of timing and memory distributions are estimated within their buckets.
+#[test]
+fn distribution_percentiles_are_estimated() {
+    common::enable_test_logging();
+
+    let dir = tempfile::tempdir().unwrap();
+    let tmpname = dir.path().to_path_buf();
+
+    let cfg = ConfigurationBuilder::new(true, tmpname, "firefox-desktop")
+        .with_server_endpoint("invalid-test-host")
+        .build();
+    common::initialize(cfg);
+
+    assert_eq!(None, metrics::measure.test_get_percentile(50.0, None));
+    assert_eq!(None, metrics::duration.test_get_percentile(50.0, None));
+
+    metrics::measure.accumulate_samples((1..=100).collect());
+    metrics::duration.accumulate_samples((1..=100).collect());
+
+    // 50 kB and 50 ms, within a few percent.
+    let measure = metrics::measure.test_get_percentile(50.0, None).unwrap();
+    assert!((measure - 50 * 1024).abs() < 50 * 1024 / 25, "{measure}");
+    let duration = metrics::duration.test_get_percentile(50.0, None).unwrap();
+    assert!(
+        (duration - 50_000_000).abs() < 50_000_000 / 25,
+        "{duration}"
+    );
+
+    assert_eq!(None, metrics::duration.test_get_percentile(101.0, None));
+
+    glean::shutdown(); // Cleanly shut down at the end of the test.
+}
@@ -0,0 +1,154 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! This integration test should model how the RLB is used when embedded in another Rust application
+//! (e.g. FOG/Firefox Desktop).
+//!
+//! We write a single test scenario per file to avoid any state keeping across runs
+//! (different files run as different processes).
+
+mod common;
+
+use crossbeam_channel::{unbounded, Sender};
+use serde_json::Value as JsonValue;
+
+use glean::net;
+use glean::ConfigurationBuilder;
+
+/// Some user metrics.
+mod metrics {
+    use glean::private::*;
+    use glean::traits::NoExtraKeys;
+    use glean::{Lifetime, TimeUnit};
+    use glean_core::CommonMetricData;
+    use once_cell::sync::Lazy;
+
+    fn meta(name: &str) -> CommonMetricData {
+        CommonMetricData {
+            name: name.into(),
+            category: "otlp".into(),
+            send_in_pings: vec!["exported".into()],
+            lifetime: Lifetime::Ping,
+            disabled: false,
+            ..Default::default()
+        }
+    }
+
+    #[allow(non_upper_case_globals)]
+    pub static clicks: Lazy<CounterMetric> = Lazy::new(|| CounterMetric::new(meta("clicks")));
+
+    #[allow(non_upper_case_globals)]
+    pub static load_time: Lazy<TimingDistributionMetric> =
+        Lazy::new(|| TimingDistributionMetric::new(meta("load_time"), TimeUnit::Nanosecond));
+
+    #[allow(non_upper_case_globals)]
+    pub static opened: Lazy<EventMetric<NoExtraKeys>> =
+        Lazy::new(|| EventMetric::new(meta("opened")));
+}
+
+mod pings {
+    use super::*;
+    use glean::private::PingType;
+    use once_cell::sync::Lazy;
+
+    #[allow(non_upper_case_globals)]
+    pub static exported: Lazy<PingType> =
+        Lazy::new(|| common::PingBuilder::new("exported").build());
+}
+
+/// A stand-in for an OTLP collector that reports the requests it receives.
+#[derive(Debug)]
+struct FakeCollector {
+    sender: Sender<(String, JsonValue)>,
+}
+
+impl net::PingUploader for FakeCollector {
+    fn upload(&self, upload_request: net::CapablePingUploadRequest) -> net::UploadResult {
+        let upload_request = upload_request.capable(|_| true).unwrap();
+        assert!(upload_request
+            .headers
+            .contains(&("Content-Type".to_string(), "application/json".to_string())));
+
+        let body = serde_json::from_slice(&upload_request.body).unwrap();
+        self.sender.send((upload_request.url, body)).unwrap();
+        net::UploadResult::http_status(200)
+    }
+}
+
+/// Test scenario: Pings are exported to an OTLP collector.
+///
+/// The counter is exported as a sum, the timing distribution as a histogram
+/// and the event as a log record.
+#[test]
+fn exports_pings_as_otlp() {
+    common::enable_test_logging();
+
+    let dir = tempfile::tempdir().unwrap();
+    let tmpname = dir.path().to_path_buf();
+    let (tx, rx) = unbounded();
+
+    let uploader = net::OtlpUploader::new(
+        "http://localhost:4318/",
+        "glean-otlp-export",
+        FakeCollector { sender: tx },
+    );
+    let cfg = ConfigurationBuilder::new(true, tmpname, "glean-otlp-export")
+        .with_server_endpoint("invalid-test-host")
+        .with_use_core_mps(false)
+        .with_uploader(uploader)
+        .build();
+    _ = &*pings::exported;
+    common::initialize(cfg);
+
+    metrics::clicks.add(3);
+    metrics::load_time.accumulate_samples(vec![10, 1000]);
+    metrics::opened.record(None);
+    pings::exported.submit(None);
+
+    let (url, metrics_request) = rx.recv().unwrap();
+    assert_eq!("http://localhost:4318/v1/metrics", url);
+    let resource_metrics = &metrics_request["resourceMetrics"][0];
+    let attributes = resource_metrics["resource"]["attributes"]
+        .as_array()
+        .unwrap();
+    assert!(attributes
+        .iter()
+        .any(|attr| attr["key"] == "service.name"
+            && attr["value"]["stringValue"] == "glean-otlp-export"));
+    assert!(!attributes
+        .iter()
+        .any(|attr| attr["key"] == "glean.client_id"));
+
+    let metrics = resource_metrics["scopeMetrics"][0]["metrics"]
+        .as_array()
+        .unwrap();
+    let clicks = metrics.iter().find(|m| m["name"] == "otlp.clicks").unwrap();
+    assert_eq!("3", clicks["sum"]["dataPoints"][0]["asInt"]);
+    assert_eq!(true, clicks["sum"]["isMonotonic"]);
+    assert_eq!(1, clicks["sum"]["aggregationTemporality"]);
+    assert!(clicks["sum"]["dataPoints"][0]["startTimeUnixNano"].is_string());
+
+    let load_time = metrics
+        .iter()
+        .find(|m| m["name"] == "otlp.load_time")
+        .unwrap();
+    assert_eq!("ns", load_time["unit"]);
+    let data_point = &load_time["histogram"]["dataPoints"][0];
+    assert_eq!("2", data_point["count"]);
+    assert_eq!(1010.0, data_point["sum"]);
+    assert_eq!(
+        data_point["bucketCounts"].as_array().unwrap().len(),
+        data_point["explicitBounds"].as_array().unwrap().len() + 1
+    );
+
+    let (url, logs_request) = rx.recv().unwrap();
+    assert_eq!("http://localhost:4318/v1/logs", url);
+    let log_records = logs_request["resourceLogs"][0]["scopeLogs"][0]["logRecords"]
+        .as_array()
+        .unwrap();
+    assert_eq!(1, log_records.len());
+    assert_eq!("otlp.opened", log_records[0]["body"]["stringValue"]);
+
+    glean::shutdown();
+}
@@ -0,0 +1,91 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! This integration test should model how the RLB is used when embedded in another Rust application
+//! (e.g. FOG/Firefox Desktop).
+//!
+//! We write a single test scenario per file to avoid any state keeping across runs
+//! (different files run as different processes).
+
+mod common;
+
+use std::io::Read;
+
+use crossbeam_channel::{unbounded, Sender};
+use flate2::read::GzDecoder;
+use serde_json::Value as JsonValue;
+
+use glean::net;
+use glean::ConfigurationBuilder;
+
+mod pings {
+    use super::*;
+    use glean::private::{PingOptions, PingTimeSchedule, PingType};
+    use once_cell::sync::Lazy;
+
+    #[allow(non_upper_case_globals)]
+    pub static daily: Lazy<PingType> = Lazy::new(|| {
+        common::PingBuilder::new("daily")
+            .with_send_if_empty(true)
+            .with_reason_codes(vec!["scheduled".into()])
+            .with_options(PingOptions {
+                // Any time of the day is past midnight,
+                // so the ping is due right away.
+                time_schedule: Some(PingTimeSchedule::Daily { hour: 0 }),
+                ..Default::default()
+            })
+            .build()
+    });
+}
+
+// Define a fake uploader that reports back the submitted pings.
+#[derive(Debug)]
+struct ReportingUploader {
+    sender: Sender<(String, JsonValue)>,
+}
+
+impl net::PingUploader for ReportingUploader {
+    fn upload(&self, upload_request: net::CapablePingUploadRequest) -> net::UploadResult {
+        let upload_request = upload_request.capable(|_| true).unwrap();
+        let mut gzip_decoder = GzDecoder::new(&upload_request.body[..]);
+        let mut s = String::with_capacity(upload_request.body.len());
+        let payload = gzip_decoder
+            .read_to_string(&mut s)
+            .ok()
+            .map(|_| &s[..])
+            .or_else(|| std::str::from_utf8(&upload_request.body).ok())
+            .and_then(|payload| serde_json::from_str(payload).ok());
+        if let Some(payload) = payload {
+            self.sender.send((upload_request.url, payload)).unwrap();
+        }
+        net::UploadResult::http_status(200)
+    }
+}
+
+/// Test scenario: A ping with a daily schedule is submitted without being asked to.
+///
+/// It was never sent before, and it is past the scheduled hour,
+/// so the ping is submitted on initialization.
+#[test]
+fn scheduled_pings_are_submitted() {
+    common::enable_test_logging();
+
+    let dir = tempfile::tempdir().unwrap();
+    let tmpname = dir.path().to_path_buf();
+    let (tx, rx) = unbounded();
+
+    let cfg = ConfigurationBuilder::new(true, tmpname, "glean-scheduled-ping")
+        .with_server_endpoint("invalid-test-host")
+        .with_use_core_mps(false)
+        .with_uploader(ReportingUploader { sender: tx })
+        .build();
+    _ = &*pings::daily;
+    common::initialize(cfg);
+
+    let (url, payload) = rx.recv().unwrap();
+    assert!(url.contains("/daily/"), "Unexpected ping: {url}");
+    assert_eq!("scheduled", payload["ping_info"]["reason"]);
+
+    glean::shutdown();
+}
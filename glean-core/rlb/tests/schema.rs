@@ -18,6 +18,7 @@ use glean::{
     traits, ClientInfoMetrics, CommonMetricData, ConfigurationBuilder, HistogramType, MemoryUnit,
     TimeUnit,
 };
+use glean_core::ping::{Ping, PingMaker};
 
 const SCHEMA_JSON: &str = include_str!("../../../glean.1.schema.json");
 
@@ -105,6 +106,12 @@ fn validate_against_schema() {
     let counter_metric = CounterMetric::new(common("counter"));
     counter_metric.add(42);
 
+    let privatized_counter = CounterMetric::new(CommonMetricData {
+        ldp_epsilon: Some(1.0),
+        ..common("privatized_counter")
+    });
+    privatized_counter.add(1);
+
     let bool_metric = BooleanMetric::new(common("bool"));
     bool_metric.set(true);
 
@@ -195,17 +202,37 @@ fn validate_against_schema() {
 
     // Now validate against the vendored schema
     let cfg = jsonschema_valid::Config::from_schema(&schema, Some(Draft::Draft6)).unwrap();
-    let validation = cfg.validate(&data);
-    match validation {
-        Ok(()) => {}
-        Err(errors) => {
-            let mut msg = format!("Data: {data:#?}\n Errors:\n");
-            for (idx, error) in errors.enumerate() {
-                msg.push_str(&format!("Error {}: ", idx + 1));
-                msg.push_str(&error.to_string());
-                msg.push('\n');
+    let validate = |data: &Value| {
+        let validation = cfg.validate(data);
+        match validation {
+            Ok(()) => {}
+            Err(errors) => {
+                let mut msg = format!("Data: {data:#?}\n Errors:\n");
+                for (idx, error) in errors.enumerate() {
+                    msg.push_str(&format!("Error {}: ", idx + 1));
+                    msg.push_str(&error.to_string());
+                    msg.push('\n');
+                }
+                panic!("{}", msg);
             }
-            panic!("{}", msg);
         }
+    };
+    validate(&data);
+
+    // The parts of a ping too large to be uploaded carry additional `ping_info`.
+    let ping = Ping {
+        doc_id: "9f5d4c6a-2f5e-4b1e-8d6f-2a3c4b5d6e7f",
+        name: PING_NAME,
+        url_path: "",
+        content: data,
+        headers: HashMap::new(),
+        includes_info_sections: true,
+        schedules_pings: vec![],
+        uploader_capabilities: vec![],
+    };
+    let parts = PingMaker::new().split_oversized(&ping, 512).unwrap();
+    assert!(parts.len() > 1);
+    for part in &parts {
+        validate(part);
     }
 }
@@ -82,6 +82,19 @@ pub struct CommonMetricData {
     /// for now. Event metrics that participate in session tracking must
     /// explicitly set this to `true`.
     pub in_session: bool,
+    /// The privacy parameter for local differential privacy.
+    ///
+    /// If set, noise is added on-device before any value is stored,
+    /// and the epsilon is reported in the ping's `ldp_epsilon` section.
+    /// Only supported by boolean (randomized response) and counter (geometric noise) metrics.
+    /// The epsilon applies to each recording. A counter reports the sum of the epsilons
+    /// of its increments, as the privacy cost composes across them.
+    pub ldp_epsilon: Option<f64>,
+    /// The largest increment of a counter with local differential privacy, `1` if not set.
+    ///
+    /// Larger increments are clamped to it. The noise is scaled to this bound,
+    /// so that it doesn't depend on the recorded values.
+    pub ldp_max_increment: Option<u32>,
 }
 
 /// The type of dynamic label applied to a base metric. Used to help identify
@@ -0,0 +1,436 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Export and import of user-lifetime data for device migration.
+//!
+//! A bundle is a self-describing JSON document carrying all metrics stored with
+//! [`Lifetime::User`] and, optionally, the client ID.
+//! It is produced on the old device by [`Glean::export_user_data`]
+//! and consumed on the new device by [`Glean::import_user_data`].
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::metrics::Metric;
+use crate::session::EventSessionContext;
+use crate::storage::INTERNAL_STORAGE;
+use crate::util::local_now_with_offset;
+use crate::{CommonMetricData, ErrorKind, Glean, Lifetime, Result, GLEAN_VERSION, KNOWN_CLIENT_ID};
+
+/// Identifies a document as a Glean user data bundle.
+const BUNDLE_FORMAT: &str = "glean-user-data";
+/// The current version of the bundle layout.
+///
+/// Bump this whenever the layout changes in a backwards-incompatible way.
+const BUNDLE_VERSION: u32 = 1;
+
+/// The storage containing the client ID and other client information.
+const CLIENT_INFO_STORAGE: &str = "glean_client_info";
+
+/// A versioned bundle of user-lifetime data.
+//
+// Note: Be careful when changing this structure.
+// Bundles might be produced by an older version of Glean.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct UserDataBundle {
+    /// Always [`BUNDLE_FORMAT`].
+    format: String,
+    /// The layout version, see [`BUNDLE_VERSION`].
+    version: u32,
+    /// The Glean version that produced this bundle.
+    glean_version: String,
+    /// The (sanitized) application ID of the exporting application.
+    application_id: String,
+    /// When this bundle was created.
+    created: DateTime<FixedOffset>,
+    /// The client ID, only present if requested on export.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    client_id: Option<String>,
+    /// All exported metrics.
+    metrics: Vec<BundleEntry>,
+}
+
+/// A single stored metric in a [`UserDataBundle`].
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BundleEntry {
+    /// The storage (ping) name this metric was stored for.
+    store: String,
+    /// The full metric identifier, including any label.
+    id: String,
+    /// The stored value.
+    metric: Metric,
+}
+
+fn invalid_bundle(msg: impl Into<String>) -> crate::Error {
+    ErrorKind::InvalidUserDataBundle(msg.into()).into()
+}
+
+impl UserDataBundle {
+    /// Checks that this bundle can be imported into the given Glean instance.
+    fn validate(&self, glean: &Glean) -> Result<()> {
+        if self.format != BUNDLE_FORMAT {
+            return Err(invalid_bundle(format!("unknown format '{}'", self.format)));
+        }
+        if self.version != BUNDLE_VERSION {
+            return Err(invalid_bundle(format!(
+                "unsupported version {}",
+                self.version
+            )));
+        }
+        if self.application_id != glean.get_application_id() {
+            return Err(invalid_bundle(format!(
+                "bundle is for application '{}'",
+                self.application_id
+            )));
+        }
+
+        if let Some(client_id) = &self.client_id {
+            let uuid = Uuid::try_parse(client_id)?;
+            if uuid == *KNOWN_CLIENT_ID {
+                return Err(invalid_bundle("bundle contains the known client ID"));
+            }
+        }
+
+        for entry in &self.metrics {
+            if entry.store.is_empty() || entry.store.contains('#') || entry.id.is_empty() {
+                return Err(invalid_bundle(format!(
+                    "invalid metric key '{}#{}'",
+                    entry.store, entry.id
+                )));
+            }
+            if !is_exportable(&entry.store, &entry.id) {
+                return Err(invalid_bundle(format!(
+                    "metric '{}#{}' cannot be imported",
+                    entry.store, entry.id
+                )));
+            }
+            if let Metric::Experiment(_) = entry.metric {
+                return Err(invalid_bundle(format!(
+                    "metric '{}#{}' is an experiment",
+                    entry.store, entry.id
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether a user-lifetime metric is part of an exported bundle.
+///
+/// Internal state (sequence numbers, session state, the dirty bit) is specific
+/// to a device and never exported.
+/// The client ID is handled separately.
+fn is_exportable(store: &str, id: &str) -> bool {
+    store != INTERNAL_STORAGE && !(store == CLIENT_INFO_STORAGE && id == "client_id")
+}
+
+impl Glean {
+    /// Exports all user-lifetime metrics into a versioned bundle.
+    ///
+    /// The bundle can be imported on a new device using [`Glean::import_user_data`].
+    ///
+    /// # Arguments
+    ///
+    /// * `include_client_id` - Whether to include the client ID in the bundle.
+    ///
+    /// # Returns
+    ///
+    /// The bundle, encoded as JSON.
+    ///
+    /// # Errors
+    ///
+    /// If upload is disabled or there is no database.
+    pub fn export_user_data(&self, include_client_id: bool) -> Result<String> {
+        if !self.is_upload_enabled() {
+            return Err(invalid_bundle("upload is disabled"));
+        }
+        let storage = self
+            .storage_opt()
+            .ok_or_else(crate::Error::not_initialized)?;
+
+        let mut metrics = Vec::new();
+        storage.iter_lifetime(Lifetime::User, |store, id, metric| {
+            if is_exportable(store, id) {
+                metrics.push(BundleEntry {
+                    store: store.to_string(),
+                    id: id.to_string(),
+                    metric: metric.clone(),
+                });
+            }
+        });
+
+        let client_id = if include_client_id {
+            self.core_metrics
+                .client_id
+                .get_value(self, Some(CLIENT_INFO_STORAGE))
+                .filter(|uuid| *uuid != *KNOWN_CLIENT_ID)
+                .map(|uuid| uuid.to_string())
+        } else {
+            None
+        };
+
+        let bundle = UserDataBundle {
+            format: BUNDLE_FORMAT.to_string(),
+            version: BUNDLE_VERSION,
+            glean_version: GLEAN_VERSION.to_string(),
+            application_id: self.get_application_id().to_string(),
+            created: local_now_with_offset(),
+            client_id,
+            metrics,
+        };
+
+        Ok(serde_json::to_string(&bundle)?)
+    }
+
+    /// Imports a bundle previously created by [`Glean::export_user_data`].
+    ///
+    /// This is only allowed on the first run of a profile.
+    /// The bundle is validated as a whole before any data is written.
+    ///
+    /// Client information (e.g. `first_run_date`) and the client ID, if present,
+    /// replace the values generated on this first run.
+    /// All other metrics are only imported if no value was recorded locally yet.
+    ///
+    /// A `glean.migration` event is recorded on success.
+    ///
+    /// # Arguments
+    ///
+    /// * `bundle` - The JSON-encoded bundle.
+    ///
+    /// # Returns
+    ///
+    /// The number of imported metrics.
+    ///
+    /// # Errors
+    ///
+    /// If this is not the first run, upload is disabled or the bundle is invalid.
+    pub fn import_user_data(&self, bundle: &str) -> Result<usize> {
+        if !self.is_first_run() {
+            return Err(invalid_bundle("import is only allowed on first run"));
+        }
+        if !self.is_upload_enabled() {
+            return Err(invalid_bundle("upload is disabled"));
+        }
+        let storage = self
+            .storage_opt()
+            .ok_or_else(crate::Error::not_initialized)?;
+
+        let bundle: UserDataBundle = serde_json::from_str(bundle)?;
+        bundle.validate(self)?;
+
+        let mut imported = 0;
+        for entry in &bundle.metrics {
+            let overwrite = entry.store == CLIENT_INFO_STORAGE;
+            if !overwrite && storage.has_metric(Lifetime::User, &entry.store, &entry.id) {
+                log::debug!(
+                    "Not importing {}#{}, it was recorded locally already.",
+                    entry.store,
+                    entry.id
+                );
+                continue;
+            }
+
+            storage.record_per_lifetime(Lifetime::User, &entry.store, &entry.id, &entry.metric)?;
+            imported += 1;
+        }
+
+        let client_id_imported = match &bundle.client_id {
+            Some(client_id) => {
+                // Already validated.
+                let uuid = Uuid::try_parse(client_id)?;
+                self.core_metrics.client_id.set_from_uuid_sync(self, uuid);
+                self.store_client_id_with_reporting(uuid, "Imported client ID in database only.");
+                true
+            }
+            None => false,
+        };
+
+        self.record_migration_event(&bundle, imported, client_id_imported);
+
+        Ok(imported)
+    }
+
+    /// Records a `glean.migration` event after a successful import.
+    fn record_migration_event(
+        &self,
+        bundle: &UserDataBundle,
+        imported: usize,
+        client_id_imported: bool,
+    ) {
+        let meta = CommonMetricData {
+            name: "migration".into(),
+            category: "glean".into(),
+            send_in_pings: vec!["events".into()],
+            lifetime: Lifetime::Ping,
+            ..Default::default()
+        };
+        let timestamp = crate::get_timestamp_ms();
+        let mut extra = HashMap::new();
+        extra.insert("bundle_version".to_string(), bundle.version.to_string());
+        extra.insert(
+            "source_glean_version".to_string(),
+            bundle.glean_version.clone(),
+        );
+        extra.insert("metrics_imported".to_string(), imported.to_string());
+        extra.insert(
+            "client_id_imported".to_string(),
+            client_id_imported.to_string(),
+        );
+        if self.with_timestamps() {
+            extra.insert("glean_timestamp".to_string(), timestamp.to_string());
+        }
+        self.event_storage().record(
+            self,
+            &meta.into(),
+            timestamp,
+            Some(extra),
+            EventSessionContext::OutOfSession,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::metrics::{CounterMetric, StringMetric};
+    use crate::tests::new_glean;
+
+    fn user_counter(name: &str) -> CounterMetric {
+        CounterMetric::new(CommonMetricData {
+            name: name.into(),
+            category: "migration".into(),
+            send_in_pings: vec!["store1".into()],
+            lifetime: Lifetime::User,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn export_contains_user_lifetime_data_only() {
+        let (glean, _t) = new_glean(None);
+
+        user_counter("user").add_sync(&glean, 3);
+        let app_string = StringMetric::new(CommonMetricData {
+            name: "app".into(),
+            category: "migration".into(),
+            send_in_pings: vec!["store1".into()],
+            lifetime: Lifetime::Application,
+            ..Default::default()
+        });
+        app_string.set_sync(&glean, "value");
+
+        let bundle = glean.export_user_data(false).unwrap();
+        let bundle: UserDataBundle = serde_json::from_str(&bundle).unwrap();
+
+        assert_eq!(BUNDLE_FORMAT, bundle.format);
+        assert_eq!(BUNDLE_VERSION, bundle.version);
+        assert!(bundle.client_id.is_none());
+        assert!(bundle
+            .metrics
+            .iter()
+            .any(|e| e.store == "store1" && e.id == "migration.user"));
+        assert!(!bundle.metrics.iter().any(|e| e.id == "migration.app"));
+        assert!(!bundle.metrics.iter().any(|e| e.store == INTERNAL_STORAGE));
+        assert!(!bundle.metrics.iter().any(|e| e.id == "client_id"));
+    }
+
+    #[test]
+    fn import_restores_metrics_and_client_id() {
+        let (old_glean, _t) = new_glean(None);
+        user_counter("user").add_sync(&old_glean, 3);
+        let old_client_id = old_glean
+            .core_metrics
+            .client_id
+            .get_value(&old_glean, Some(CLIENT_INFO_STORAGE))
+            .unwrap();
+        let bundle = old_glean.export_user_data(true).unwrap();
+
+        let (new_glean, _t) = new_glean(None);
+        assert!(new_glean.is_first_run());
+        let imported = new_glean.import_user_data(&bundle).unwrap();
+        assert!(imported > 0);
+
+        assert_eq!(
+            Some(3),
+            user_counter("user").get_value(&new_glean, Some("store1"))
+        );
+        assert_eq!(
+            Some(old_client_id),
+            new_glean
+                .core_metrics
+                .client_id
+                .get_value(&new_glean, Some(CLIENT_INFO_STORAGE))
+        );
+
+        let events = new_glean
+            .event_storage()
+            .test_get_value(
+                &CommonMetricData {
+                    name: "migration".into(),
+                    category: "glean".into(),
+                    send_in_pings: vec!["events".into()],
+                    ..Default::default()
+                }
+                .into(),
+                "events",
+            )
+            .unwrap();
+        assert_eq!(1, events.len());
+        let extra = events[0].extra.as_ref().unwrap();
+        assert_eq!("true", extra["client_id_imported"]);
+    }
+
+    #[test]
+    fn import_keeps_locally_recorded_data() {
+        let (old_glean, _t) = new_glean(None);
+        user_counter("user").add_sync(&old_glean, 3);
+        let bundle = old_glean.export_user_data(false).unwrap();
+
+        let (new_glean, _t) = new_glean(None);
+        user_counter("user").add_sync(&new_glean, 1);
+        new_glean.import_user_data(&bundle).unwrap();
+
+        assert_eq!(
+            Some(1),
+            user_counter("user").get_value(&new_glean, Some("store1"))
+        );
+    }
+
+    #[test]
+    fn import_rejects_invalid_bundles() {
+        let (glean, _t) = new_glean(None);
+        let bundle = glean.export_user_data(false).unwrap();
+
+        let mut value: serde_json::Value = serde_json::from_str(&bundle).unwrap();
+        value["version"] = 99.into();
+        assert!(glean.import_user_data(&value.to_string()).is_err());
+
+        let mut value: serde_json::Value = serde_json::from_str(&bundle).unwrap();
+        value["application_id"] = "another-app".into();
+        assert!(glean.import_user_data(&value.to_string()).is_err());
+
+        let mut value: serde_json::Value = serde_json::from_str(&bundle).unwrap();
+        value["client_id"] = KNOWN_CLIENT_ID.to_string().into();
+        assert!(glean.import_user_data(&value.to_string()).is_err());
+
+        assert!(glean.import_user_data("{}").is_err());
+    }
+
+    #[test]
+    fn import_is_only_allowed_on_first_run() {
+        let (glean, t) = new_glean(None);
+        let bundle = glean.export_user_data(false).unwrap();
+        drop(glean);
+
+        let (glean, _t) = new_glean(Some(t));
+        assert!(!glean.is_first_run());
+        assert!(glean.import_user_data(&bundle).is_err());
+    }
+}
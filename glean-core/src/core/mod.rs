@@ -13,6 +13,7 @@ use std::time::Duration;
 use chrono::{DateTime, FixedOffset, SecondsFormat};
 use malloc_size_of_derive::MallocSizeOf;
 use once_cell::sync::OnceCell;
+use serde_json::Value as JsonValue;
 use uuid::Uuid;
 
 use crate::database::Database;
@@ -26,17 +27,19 @@ use crate::internal_pings::InternalPings;
 use crate::metrics::{
     self, ExperimentMetric, Metric, MetricType, PingType, RecordedExperiment, RemoteSettingsConfig,
 };
-use crate::ping::PingMaker;
+use crate::ping::{PingMaker, PingSnapshotFilter};
 use crate::session::{self, EventSessionContext, SessionManager, SessionMode, SessionState};
 use crate::storage::{StorageManager, INTERNAL_STORAGE};
 use crate::upload::{PingUploadManager, PingUploadTask, UploadResult, UploadTaskAction};
 use crate::util::{local_now_with_offset, sanitize_application_id};
 use crate::{
-    scheduler, system, AttributionMetrics, CommonMetricData, DistributionMetrics, ErrorKind,
-    InternalConfiguration, Lifetime, PingRateLimit, Result, DEFAULT_MAX_EVENTS,
+    ping_scheduler, scheduler, system, AttributionMetrics, CommonMetricData, DistributionMetrics,
+    ErrorKind, InternalConfiguration, Lifetime, PingRateLimit, Result, DEFAULT_MAX_EVENTS,
     GLEAN_SCHEMA_VERSION, GLEAN_VERSION, KNOWN_CLIENT_ID,
 };
 
+mod migration;
+
 const CLIENT_ID_PLAIN_FILENAME: &str = "client_id.txt";
 static GLEAN: OnceCell<Mutex<Glean>> = OnceCell::new();
 
@@ -142,6 +145,8 @@ where
 ///     ping_lifetime_max_time: 2000,
 ///     max_pending_pings_count: None,
 ///     max_pending_pings_directory_size: None,
+///     database_size_budget: None,
+///     event_store_limits: None,
 ///     session_mode: glean_core::SessionMode::Auto,
 ///     session_sample_rate: 1.0,
 ///     session_inactivity_timeout_ms: 1_800_000,
@@ -210,7 +215,10 @@ impl Glean {
         }
 
         let data_path = Path::new(&cfg.data_path);
-        let event_data_store = EventDatabase::new(data_path)?;
+        let mut event_data_store = EventDatabase::new(data_path)?;
+        if let Some(limits) = &cfg.event_store_limits {
+            event_data_store.set_limits(limits.clone());
+        }
 
         // Create an upload manager with rate limiting of 15 pings every 60 seconds.
         let mut upload_manager = PingUploadManager::new(&cfg.data_path, &cfg.language_binding_name);
@@ -306,6 +314,9 @@ impl Glean {
             ping_lifetime_threshold,
             ping_lifetime_max_time,
         )?);
+        if let Some(data_store) = glean.data_store.as_mut() {
+            data_store.set_size_budget(cfg.database_size_budget);
+        }
 
         glean.restore_session_state_from_storage();
 
@@ -570,6 +581,8 @@ impl Glean {
             ping_lifetime_max_time: 0,
             max_pending_pings_count: None,
             max_pending_pings_directory_size: None,
+            database_size_budget: None,
+            event_store_limits: None,
             session_mode: SessionMode::Auto,
             session_sample_rate: 1.0,
             session_inactivity_timeout_ms: 1_800_000,
@@ -966,6 +979,18 @@ impl Glean {
         self.with_timestamps
     }
 
+    /// Gets the filters applied to the metrics of a ping when it is collected.
+    ///
+    /// Filters set through Server Knobs replace the ones declared by the ping.
+    pub fn ping_snapshot_filter(&self, ping: &PingType) -> Option<PingSnapshotFilter> {
+        let remote_settings_config = self.remote_settings_config.lock().unwrap();
+        remote_settings_config
+            .ping_snapshot_filters
+            .get(ping.name())
+            .or(ping.snapshot_filter())
+            .cloned()
+    }
+
     /// Gets the maximum number of events to store before sending a ping.
     pub fn get_max_events(&self) -> usize {
         let remote_settings_config = self.remote_settings_config.lock().unwrap();
@@ -1066,6 +1091,27 @@ impl Glean {
         }
     }
 
+    /// Collects the payload of a ping (by its name), as if it was submitted now.
+    ///
+    /// Unlike [`submit_ping_by_name`](Self::submit_ping_by_name) this doesn't clear any stores,
+    /// doesn't increment the ping's sequence number and doesn't queue the ping for upload.
+    ///
+    /// # Arguments
+    ///
+    /// * `ping_name` - The name of the ping to preview.
+    ///
+    /// # Returns
+    ///
+    /// The ping payload, or `None` if the ping is unknown or would not be sent because it is empty.
+    pub fn preview_ping(&self, ping_name: &str) -> Option<JsonValue> {
+        let Some(ping) = self.get_ping_by_name(ping_name) else {
+            log::error!("Attempted to preview unknown ping '{}'", ping_name);
+            return None;
+        };
+
+        PingMaker::new().preview(self, ping, None)
+    }
+
     /// Gets a [`PingType`] by name.
     ///
     /// # Returns
@@ -1084,6 +1130,10 @@ impl Glean {
 
         self.ping_registry
             .insert(ping.name().to_string(), ping.clone());
+
+        if ping.is_scheduled() {
+            ping_scheduler::reschedule();
+        }
     }
 
     /// Gets a list of currently registered ping names.
@@ -1173,6 +1223,11 @@ impl Glean {
                 .pings_enabled
                 .extend(cfg.pings_enabled);
 
+            // Merge the exising ping snapshot filters with the supplied ones
+            remote_settings_config
+                .ping_snapshot_filters
+                .extend(cfg.ping_snapshot_filters);
+
             remote_settings_config.event_threshold = cfg.event_threshold;
 
             // Clamp to [0.0, 1.0] so callers can't accidentally set an invalid rate.
@@ -1830,6 +1885,16 @@ impl Glean {
         }
     }
 
+    /// Instructs the scheduler of custom pings' schedules to exit cleanly.
+    pub fn cancel_ping_scheduler(&self) {
+        ping_scheduler::cancel();
+    }
+
+    /// Instructs the scheduler of custom pings' schedules to begin submitting scheduled pings.
+    pub fn start_ping_scheduler(&self) {
+        ping_scheduler::schedule(self);
+    }
+
     /// Clears the core attribution data.
     /// Does not clear glean.attribution.ext.
     pub fn clear_attribution(&self) {
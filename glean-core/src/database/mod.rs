@@ -8,12 +8,13 @@ use std::collections::BTreeMap;
 use std::fs;
 use std::io;
 use std::num::NonZeroU64;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::RwLock;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
+use crate::metrics::dual_labeled_counter::RECORD_SEPARATOR;
 use crate::ErrorKind;
 
 use malloc_size_of::MallocSizeOf;
@@ -97,10 +98,16 @@ use crate::Glean;
 use crate::Lifetime;
 use crate::Result;
 
+/// Name of the store tracking when user-lifetime labeled entries were last written.
+const WRITE_TIMES_STORE: &str = "user_write_times";
+
 pub struct Database {
     /// Handle to the database environment.
     rkv: Rkv,
 
+    /// Path to the database directory.
+    path: PathBuf,
+
     /// Handles to the "lifetime" stores.
     ///
     /// A "store" is a handle to the underlying database.
@@ -109,6 +116,12 @@ pub struct Database {
     ping_store: SingleStore,
     application_store: SingleStore,
 
+    /// Last write time (in milliseconds since the UNIX epoch) of user-lifetime labeled entries,
+    /// keyed by their storage key.
+    ///
+    /// Only maintained if a size budget is set.
+    write_times: SingleStore,
+
     /// If the `delay_ping_lifetime_io` Glean config option is `true`,
     /// we will save metrics with 'ping' lifetime data in a map temporarily
     /// so as to persist them to disk using rkv in bulk on demand.
@@ -144,6 +157,21 @@ pub struct Database {
     /// Times an Rkv write-commit took.
     /// Re-applied as samples in a timing distribution later.
     pub(crate) write_timings: RefCell<Vec<i64>>,
+
+    /// Maximum size of the database on disk.
+    ///
+    /// If exceeded after a write, the least recently written user-lifetime labeled entries are evicted.
+    size_budget: Option<NonZeroU64>,
+
+    /// Whether the last size budget check found no more entries to evict.
+    ///
+    /// Checks are skipped until the next user-lifetime labeled entry is written,
+    /// as there is nothing to evict before then.
+    size_budget_exhausted: Cell<bool>,
+
+    /// Number of entries evicted to stay within the size budget.
+    /// Re-applied to a counter metric later.
+    pub(crate) evicted_entries: Cell<i32>,
 }
 
 impl MallocSizeOf for Database {
@@ -156,6 +184,7 @@ impl MallocSizeOf for Database {
         n += self.user_store.size_of(ops);
         n += self.ping_store.size_of(ops);
         n += self.application_store.size_of(ops);
+        n += self.write_times.size_of(ops);
 
         n += self
             .ping_lifetime_data
@@ -213,6 +242,12 @@ fn database_size(dir: &Path) -> Option<NonZeroU64> {
     NonZeroU64::new(total_size)
 }
 
+/// Whether a storage key belongs to a labeled or dual-labeled metric.
+fn is_labeled_key(key: &str) -> bool {
+    let metric_id = key.split_once('#').map_or(key, |(_, id)| id);
+    metric_id.contains('/') || metric_id.contains(RECORD_SEPARATOR)
+}
+
 impl Database {
     /// Initializes the data store.
     ///
@@ -236,6 +271,7 @@ impl Database {
         let ping_store = rkv.open_single(Lifetime::Ping.as_str(), StoreOptions::create())?;
         let application_store =
             rkv.open_single(Lifetime::Application.as_str(), StoreOptions::create())?;
+        let write_times = rkv.open_single(WRITE_TIMES_STORE, StoreOptions::create())?;
         let ping_lifetime_data = if delay_ping_lifetime_io {
             Some(RwLock::new(BTreeMap::new()))
         } else {
@@ -250,9 +286,11 @@ impl Database {
 
         let db = Self {
             rkv,
+            path,
             user_store,
             ping_store,
             application_store,
+            write_times,
             ping_lifetime_data,
             ping_lifetime_count: AtomicUsize::new(0),
             ping_lifetime_threshold,
@@ -261,6 +299,9 @@ impl Database {
             file_size,
             rkv_load_state,
             write_timings,
+            size_budget: None,
+            size_budget_exhausted: Cell::new(false),
+            evicted_entries: Cell::new(0),
         };
 
         db.load_ping_lifetime_data();
@@ -273,6 +314,14 @@ impl Database {
         self.file_size
     }
 
+    /// Sets the maximum size of the database on disk.
+    ///
+    /// `None` or `0` disables the budget.
+    pub fn set_size_budget(&mut self, budget: Option<u64>) {
+        self.size_budget = budget.and_then(NonZeroU64::new);
+        self.size_budget_exhausted.set(false);
+    }
+
     /// Get the rkv load state.
     pub fn rkv_load_state(&self) -> Option<String> {
         if let RkvLoadState::Err(e) = &self.rkv_load_state {
@@ -419,6 +468,60 @@ impl Database {
         }
     }
 
+    /// Iterates with the provided transaction function
+    /// over all data stored for the given lifetime, across all storages.
+    ///
+    /// * If the storage is unavailable, the transaction function is never invoked.
+    /// * If the read data cannot be deserialized it will be silently skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `lifetime` - The metric lifetime to iterate over.
+    /// * `transaction_fn` - Called for each entry being iterated over. It is
+    ///   passed three arguments: `(storage_name: &str, metric_id: &str, metric: &Metric)`.
+    ///
+    /// # Panics
+    ///
+    /// This function will **not** panic on database errors.
+    pub fn iter_lifetime<F>(&self, lifetime: Lifetime, mut transaction_fn: F)
+    where
+        F: FnMut(&str, &str, &Metric),
+    {
+        // Lifetime::Ping data is not immediately persisted to disk if
+        // Glean has `delay_ping_lifetime_io` set to true
+        if lifetime == Lifetime::Ping {
+            if let Some(ping_lifetime_data) = &self.ping_lifetime_data {
+                let data = ping_lifetime_data
+                    .read()
+                    .expect("Can't read ping lifetime data");
+                for (key, value) in data.iter() {
+                    if let Some((storage_name, metric_id)) = key.split_once('#') {
+                        transaction_fn(storage_name, metric_id, value);
+                    }
+                }
+                return;
+            }
+        }
+
+        let reader = unwrap_or!(self.rkv.read(), return);
+        let mut iter = unwrap_or!(self.get_store(lifetime).iter_start(&reader), return);
+
+        while let Some(Ok((key, value))) = iter.next() {
+            let key = match str::from_utf8(key) {
+                Ok(key) => key,
+                _ => continue,
+            };
+            let Some((storage_name, metric_id)) = key.split_once('#') else {
+                continue;
+            };
+            let metric: Metric = match value {
+                rkv::Value::Blob(blob) => unwrap_or!(bincode::deserialize(blob), continue),
+                _ => continue,
+            };
+            transaction_fn(storage_name, metric_id, &metric);
+        }
+    }
+
     /// Determines if the storage has the given metric.
     ///
     /// If data cannot be read it is assumed that the storage does not have the metric.
@@ -504,7 +607,7 @@ impl Database {
     /// # Panics
     ///
     /// This function will **not** panic on database errors.
-    fn record_per_lifetime(
+    pub(crate) fn record_per_lifetime(
         &self,
         lifetime: Lifetime,
         storage_name: &str,
@@ -533,8 +636,10 @@ impl Database {
 
         let mut writer = self.rkv.write()?;
         self.get_store(lifetime)
-            .put(&mut writer, final_key, &value)?;
+            .put(&mut writer, &final_key, &value)?;
+        self.record_write_time(lifetime, &mut writer, &final_key)?;
         measure_commit!(self, writer.commit())?;
+        self.enforce_size_budget_after_write(lifetime);
         Ok(())
     }
 
@@ -629,11 +734,125 @@ impl Database {
         let encoded =
             bincode::serialize(&new_value).expect("IMPOSSIBLE: Serializing metric failed");
         let value = rkv::Value::Blob(&encoded);
-        store.put(&mut writer, final_key, &value)?;
+        store.put(&mut writer, &final_key, &value)?;
+        self.record_write_time(lifetime, &mut writer, &final_key)?;
         measure_commit!(self, writer.commit())?;
+        self.enforce_size_budget_after_write(lifetime);
         Ok(())
     }
 
+    /// Remembers when a user-lifetime labeled entry was last written.
+    ///
+    /// Does nothing if no size budget is set.
+    fn record_write_time(&self, lifetime: Lifetime, writer: &mut Writer, key: &str) -> Result<()> {
+        if self.size_budget.is_none() || lifetime != Lifetime::User || !is_labeled_key(key) {
+            return Ok(());
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        self.write_times.put(writer, key, &rkv::Value::U64(now))?;
+        self.size_budget_exhausted.set(false);
+        Ok(())
+    }
+
+    /// Checks the size budget after a write to the given lifetime.
+    ///
+    /// Only user-lifetime writes can grow the database indefinitely,
+    /// so other writes don't trigger a check.
+    fn enforce_size_budget_after_write(&self, lifetime: Lifetime) {
+        if lifetime != Lifetime::User {
+            return;
+        }
+
+        if let Err(e) = self.enforce_size_budget() {
+            log::warn!("Failed to enforce the database size budget: {:?}", e);
+        }
+    }
+
+    /// Evicts user-lifetime labeled entries until the database fits its size budget.
+    ///
+    /// The least recently written entries are evicted first.
+    /// Entries without a known write time are considered the oldest.
+    /// The space an entry takes up on disk is estimated from its key and value size.
+    ///
+    /// If a check evicts all entries there are and the database still exceeds its budget,
+    /// further checks are skipped until a new entry could be evicted.
+    ///
+    /// # Returns
+    ///
+    /// The number of evicted entries.
+    fn enforce_size_budget(&self) -> Result<usize> {
+        let budget = match self.size_budget {
+            Some(budget) => budget.get(),
+            None => return Ok(0),
+        };
+        if self.size_budget_exhausted.get() {
+            return Ok(0);
+        }
+        let size = database_size(&self.path).map_or(0, NonZeroU64::get);
+        if size <= budget {
+            return Ok(0);
+        }
+        let mut excess = size - budget;
+
+        let mut writer = self.rkv.write()?;
+        let mut candidates = Vec::new();
+        {
+            let mut iter = self.user_store.iter_start(&writer)?;
+            while let Some(Ok((key, value))) = iter.next() {
+                let key = match str::from_utf8(key) {
+                    Ok(key) if is_labeled_key(key) => key,
+                    _ => continue,
+                };
+                let value_size = match value {
+                    rkv::Value::Blob(blob) => blob.len(),
+                    _ => 0,
+                };
+                let written = match self.write_times.get(&writer, key)? {
+                    Some(rkv::Value::U64(ts)) => ts,
+                    _ => 0,
+                };
+                candidates.push((written, key.to_owned(), (key.len() + value_size) as u64));
+            }
+        }
+        candidates.sort_unstable();
+
+        let candidate_count = candidates.len();
+        let mut evicted = 0;
+        for (_, key, entry_size) in candidates {
+            if excess == 0 {
+                break;
+            }
+
+            self.user_store.delete(&mut writer, &key)?;
+            // Not every entry has a write time.
+            let _ = self.write_times.delete(&mut writer, &key);
+            excess = excess.saturating_sub(entry_size);
+            evicted += 1;
+        }
+
+        if evicted == candidate_count && excess > 0 {
+            log::info!("No more entries to evict to stay within the database size budget");
+            self.size_budget_exhausted.set(true);
+        }
+        if evicted == 0 {
+            return Ok(0);
+        }
+
+        measure_commit!(self, writer.commit())?;
+        log::info!(
+            "Evicted {} labeled entries to stay within the database size budget of {} bytes",
+            evicted,
+            budget
+        );
+        self.evicted_entries
+            .set(self.evicted_entries.get().saturating_add(evicted as i32));
+        Ok(evicted)
+    }
+
     /// Clears a storage (only Ping Lifetime).
     ///
     /// # Returns
@@ -701,6 +920,10 @@ impl Database {
 
             let mut res = Ok(());
             for to_delete in metrics {
+                if lifetime == Lifetime::User {
+                    // Not every entry has a write time.
+                    let _ = self.write_times.delete(&mut writer, &to_delete);
+                }
                 if let Err(e) = store.delete(&mut writer, to_delete) {
                     log::warn!("Can't delete from store: {:?}", e);
                     res = Err(e);
@@ -773,6 +996,9 @@ impl Database {
     pub fn clear_lifetime(&self, lifetime: Lifetime) {
         let res = self.write_with_store(lifetime, |mut writer, store| {
             store.clear(&mut writer)?;
+            if lifetime == Lifetime::User {
+                self.write_times.clear(&mut writer)?;
+            }
             measure_commit!(self, writer.commit())?;
             Ok(())
         });
@@ -1102,6 +1328,115 @@ mod test {
         assert_eq!(1, found_metrics, "We only expect 1 Lifetime.User metric.");
     }
 
+    #[test]
+    fn size_budget_evicts_labeled_entries_without_write_time_first() {
+        let dir = tempdir().unwrap();
+        let mut db = Database::new(dir.path(), false, 0, Duration::ZERO).unwrap();
+        let test_storage = "test-storage";
+        let value = Metric::String("x".repeat(100));
+
+        // Written before a budget exists, so no write times are tracked.
+        for i in 0..10 {
+            let key = format!("telemetry_test.labeled/old_{i}");
+            db.record_per_lifetime(Lifetime::User, test_storage, &key, &value)
+                .unwrap();
+        }
+        db.record_per_lifetime(Lifetime::User, test_storage, "telemetry_test.plain", &value)
+            .unwrap();
+
+        let budget = database_size(&db.path).unwrap().get();
+        db.set_size_budget(Some(budget));
+        db.record_per_lifetime(
+            Lifetime::User,
+            test_storage,
+            "telemetry_test.labeled/new",
+            &value,
+        )
+        .unwrap();
+
+        assert!(database_size(&db.path).unwrap().get() <= budget);
+        assert!(db.has_metric(Lifetime::User, test_storage, "telemetry_test.labeled/new"));
+        assert!(db.has_metric(Lifetime::User, test_storage, "telemetry_test.plain"));
+
+        let remaining = (0..10)
+            .filter(|i| {
+                let key = format!("telemetry_test.labeled/old_{i}");
+                db.has_metric(Lifetime::User, test_storage, &key)
+            })
+            .count();
+        let evicted = db.evicted_entries.get();
+        assert!(evicted > 0);
+        assert!(remaining > 0);
+        assert_eq!(10, remaining + evicted as usize);
+    }
+
+    #[test]
+    fn size_budget_evicts_least_recently_written_entries() {
+        let dir = tempdir().unwrap();
+        let mut db = Database::new(dir.path(), false, 0, Duration::ZERO).unwrap();
+        db.set_size_budget(Some(u64::MAX));
+        let test_storage = "test-storage";
+        let big = Metric::String("x".repeat(500));
+
+        db.record_per_lifetime(
+            Lifetime::User,
+            test_storage,
+            "telemetry_test.labeled/b",
+            &big,
+        )
+        .unwrap();
+        std::thread::sleep(Duration::from_millis(5));
+        db.record_per_lifetime(
+            Lifetime::User,
+            test_storage,
+            "telemetry_test.labeled/a",
+            &big,
+        )
+        .unwrap();
+
+        let budget = database_size(&db.path).unwrap().get();
+        db.set_size_budget(Some(budget));
+        std::thread::sleep(Duration::from_millis(5));
+        db.record_per_lifetime(
+            Lifetime::User,
+            test_storage,
+            "telemetry_test.labeled/c",
+            &Metric::Counter(1),
+        )
+        .unwrap();
+
+        assert_eq!(1, db.evicted_entries.get());
+        assert!(!db.has_metric(Lifetime::User, test_storage, "telemetry_test.labeled/b"));
+        assert!(db.has_metric(Lifetime::User, test_storage, "telemetry_test.labeled/a"));
+        assert!(db.has_metric(Lifetime::User, test_storage, "telemetry_test.labeled/c"));
+    }
+
+    #[test]
+    fn size_budget_checks_are_skipped_without_entries_to_evict() {
+        let dir = tempdir().unwrap();
+        let mut db = Database::new(dir.path(), false, 0, Duration::ZERO).unwrap();
+        db.set_size_budget(Some(1));
+        let test_storage = "test-storage";
+        let value = Metric::String("x".repeat(100));
+
+        db.record_per_lifetime(Lifetime::User, test_storage, "telemetry_test.plain", &value)
+            .unwrap();
+        assert!(db.size_budget_exhausted.get());
+        assert_eq!(0, db.evicted_entries.get());
+
+        // Writing a labeled entry gives the check something to evict again.
+        db.record_per_lifetime(
+            Lifetime::User,
+            test_storage,
+            "telemetry_test.labeled/a",
+            &value,
+        )
+        .unwrap();
+        assert_eq!(1, db.evicted_entries.get());
+        assert!(db.size_budget_exhausted.get());
+        assert!(db.has_metric(Lifetime::User, test_storage, "telemetry_test.plain"));
+    }
+
     #[test]
     fn test_clear_ping_storage() {
         // Init the database in a temporary directory.
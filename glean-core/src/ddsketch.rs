@@ -0,0 +1,283 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! A relative-error quantile sketch, following [DDSketch](https://arxiv.org/abs/1908.10693).
+//!
+//! Samples are counted in logarithmically sized bins, such that any quantile can be
+//! estimated with a relative error of at most the configured accuracy.
+//! Sketches with the same accuracy are merged by adding up their bins.
+
+use std::collections::{BTreeMap, HashMap};
+
+use malloc_size_of_derive::MallocSizeOf;
+use serde::{Deserialize, Serialize};
+
+/// The smallest supported relative accuracy.
+///
+/// This bounds the number of bins a sketch of 64-bit samples can have to about 22,000.
+pub const MIN_RELATIVE_ACCURACY: f64 = 0.001;
+
+/// The largest supported relative accuracy.
+pub const MAX_RELATIVE_ACCURACY: f64 = 0.25;
+
+/// A quantile sketch of non-negative integer samples.
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// let mut sketch = DDSketch::new(0.01);
+///
+/// for i in 1..=100 {
+///     sketch.accumulate(i);
+/// }
+///
+/// let p95 = sketch.quantile(0.95).unwrap();
+/// assert!((p95 - 95.0).abs() <= 95.0 * 0.01);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, MallocSizeOf)]
+pub struct DDSketch {
+    /// The maximum relative error of the estimated quantiles.
+    relative_accuracy: f64,
+    /// The count of samples per bin index.
+    ///
+    /// Bin `i` holds the samples in `(gamma^(i-1), gamma^i]`.
+    bins: HashMap<i32, u64>,
+    /// The count of samples that were `0`.
+    zero_count: u64,
+    /// The total count of samples.
+    count: u64,
+    /// The sum of all samples.
+    sum: u64,
+}
+
+/// The serialized form of a sketch in a ping payload.
+#[derive(Debug, Serialize)]
+pub(crate) struct Snapshot {
+    relative_accuracy: f64,
+    count: u64,
+    sum: u64,
+    zero_count: u64,
+    bins: BTreeMap<i32, u64>,
+}
+
+impl DDSketch {
+    /// Creates an empty sketch.
+    ///
+    /// `relative_accuracy` is clamped to the range
+    /// [`MIN_RELATIVE_ACCURACY`]`..=`[`MAX_RELATIVE_ACCURACY`].
+    pub fn new(relative_accuracy: f64) -> Self {
+        let relative_accuracy = if relative_accuracy.is_nan() {
+            MAX_RELATIVE_ACCURACY
+        } else {
+            relative_accuracy.clamp(MIN_RELATIVE_ACCURACY, MAX_RELATIVE_ACCURACY)
+        };
+
+        Self {
+            relative_accuracy,
+            bins: HashMap::new(),
+            zero_count: 0,
+            count: 0,
+            sum: 0,
+        }
+    }
+
+    /// The maximum relative error of the estimated quantiles.
+    pub fn relative_accuracy(&self) -> f64 {
+        self.relative_accuracy
+    }
+
+    /// The total count of samples.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// The sum of all samples.
+    pub fn sum(&self) -> u64 {
+        self.sum
+    }
+
+    /// Whether no sample was added to the sketch.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    fn ln_gamma(&self) -> f64 {
+        ((1.0 + self.relative_accuracy) / (1.0 - self.relative_accuracy)).ln()
+    }
+
+    fn index(&self, sample: u64) -> i32 {
+        ((sample as f64).ln() / self.ln_gamma()).ceil() as i32
+    }
+
+    /// The value representing bin `index`, within the relative accuracy of all its samples.
+    fn value(&self, index: i32) -> f64 {
+        let gamma = self.ln_gamma().exp();
+        2.0 * gamma.powi(index) / (1.0 + gamma)
+    }
+
+    /// Adds a sample to the sketch.
+    pub fn accumulate(&mut self, sample: u64) {
+        if sample == 0 {
+            self.zero_count += 1;
+        } else {
+            *self.bins.entry(self.index(sample)).or_insert(0) += 1;
+        }
+        self.count += 1;
+        self.sum = self.sum.saturating_add(sample);
+    }
+
+    /// Merges another sketch with the same accuracy into this one.
+    ///
+    /// Sketches with a different accuracy are ignored.
+    pub fn merge(&mut self, other: &DDSketch) {
+        if self.relative_accuracy != other.relative_accuracy {
+            return;
+        }
+
+        for (&index, &count) in &other.bins {
+            *self.bins.entry(index).or_insert(0) += count;
+        }
+        self.zero_count += other.zero_count;
+        self.count += other.count;
+        self.sum = self.sum.saturating_add(other.sum);
+    }
+
+    /// Estimates the value at `quantile`, in the range `0.0..=1.0`.
+    ///
+    /// # Returns
+    ///
+    /// The estimate, or `None` if the sketch is empty or `quantile` is out of range.
+    pub fn quantile(&self, quantile: f64) -> Option<f64> {
+        if self.is_empty() || !(0.0..=1.0).contains(&quantile) {
+            return None;
+        }
+
+        let rank = quantile * (self.count - 1) as f64;
+        let mut seen = self.zero_count;
+        if rank < seen as f64 {
+            return Some(0.0);
+        }
+
+        let mut indices: Vec<_> = self.bins.keys().copied().collect();
+        indices.sort_unstable();
+        for index in indices {
+            seen += self.bins[&index];
+            if rank < seen as f64 {
+                return Some(self.value(index));
+            }
+        }
+
+        // Only reachable through floating point rounding of `rank`.
+        self.bins.keys().max().map(|&index| self.value(index))
+    }
+
+    /// Gets the samples per representative value of their bin, rounded to an integer.
+    pub fn snapshot_values(&self) -> HashMap<i64, i64> {
+        let mut values = HashMap::new();
+        if self.zero_count > 0 {
+            values.insert(0, self.zero_count as i64);
+        }
+        for (&index, &count) in &self.bins {
+            *values.entry(self.value(index).round() as i64).or_insert(0) += count as i64;
+        }
+        values
+    }
+
+    /// Gets the snapshot of this sketch, as reported in a ping payload.
+    pub(crate) fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            relative_accuracy: self.relative_accuracy,
+            count: self.count,
+            sum: self.sum,
+            zero_count: self.zero_count,
+            bins: self.bins.iter().map(|(&k, &v)| (k, v)).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_within(expected: f64, actual: f64, relative_accuracy: f64) {
+        // Allow for floating point rounding right at the bound.
+        assert!(
+            (actual - expected).abs() <= expected * relative_accuracy * (1.0 + 1e-9),
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn empty_sketch_has_no_quantiles() {
+        let sketch = DDSketch::new(0.01);
+        assert_eq!(None, sketch.quantile(0.5));
+    }
+
+    #[test]
+    fn quantiles_are_within_relative_accuracy() {
+        for accuracy in [0.001, 0.01, 0.05] {
+            let mut sketch = DDSketch::new(accuracy);
+            for i in 1..=10_000 {
+                sketch.accumulate(i);
+            }
+
+            for quantile in [0.0, 0.5, 0.95, 0.99, 1.0] {
+                // The exact value at this quantile's rank.
+                let expected = (quantile * 9999.0f64).floor() + 1.0;
+                assert_within(expected, sketch.quantile(quantile).unwrap(), accuracy);
+            }
+        }
+    }
+
+    #[test]
+    fn zeros_are_counted_separately() {
+        let mut sketch = DDSketch::new(0.01);
+        sketch.accumulate(0);
+        sketch.accumulate(0);
+        sketch.accumulate(100);
+
+        assert_eq!(Some(0.0), sketch.quantile(0.5));
+        assert_within(100.0, sketch.quantile(1.0).unwrap(), 0.01);
+        assert_eq!(Some(&2), sketch.snapshot_values().get(&0));
+    }
+
+    #[test]
+    fn merging_equals_accumulating_into_one() {
+        let mut a = DDSketch::new(0.01);
+        let mut b = DDSketch::new(0.01);
+        let mut both = DDSketch::new(0.01);
+        for i in 0..1000 {
+            if i % 3 == 0 {
+                a.accumulate(i * 7);
+            } else {
+                b.accumulate(i * 7);
+            }
+            both.accumulate(i * 7);
+        }
+
+        a.merge(&b);
+        assert_eq!(both, a);
+
+        // Different accuracies can't be merged.
+        let mut c = DDSketch::new(0.02);
+        c.merge(&a);
+        assert!(c.is_empty());
+    }
+
+    #[test]
+    fn accuracy_is_clamped() {
+        assert_eq!(
+            MIN_RELATIVE_ACCURACY,
+            DDSketch::new(0.0).relative_accuracy()
+        );
+        assert_eq!(
+            MAX_RELATIVE_ACCURACY,
+            DDSketch::new(1.0).relative_accuracy()
+        );
+        assert_eq!(
+            MAX_RELATIVE_ACCURACY,
+            DDSketch::new(f64::NAN).relative_accuracy()
+        );
+    }
+}
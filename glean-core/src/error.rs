@@ -65,6 +65,9 @@ pub enum ErrorKind {
 
     /// Parsing a UUID from a string failed
     UuidError(uuid::Error),
+
+    /// A user data bundle could not be exported or imported
+    InvalidUserDataBundle(String),
 }
 
 /// A specialized [`Error`] type for this crate's operations.
@@ -121,6 +124,7 @@ impl Display for Error {
                 s / 1024
             ),
             UuidError(e) => write!(f, "Failed to parse UUID: {}", e),
+            InvalidUserDataBundle(s) => write!(f, "Invalid user data bundle: {}", s),
         }
     }
 }
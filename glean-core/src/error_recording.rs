@@ -39,6 +39,8 @@ pub enum ErrorType {
     InvalidState,
     /// For when the value to be recorded overflows the metric-specific upper range
     InvalidOverflow,
+    /// For when the value to be recorded is not of the declared type
+    InvalidType,
 }
 
 impl ErrorType {
@@ -49,6 +51,7 @@ impl ErrorType {
             ErrorType::InvalidLabel => "invalid_label",
             ErrorType::InvalidState => "invalid_state",
             ErrorType::InvalidOverflow => "invalid_overflow",
+            ErrorType::InvalidType => "invalid_type",
         }
     }
 
@@ -58,7 +61,7 @@ impl ErrorType {
     /// # use glean_core::ErrorType;
     /// let errors = ErrorType::iter();
     /// let all_errors = errors.collect::<Vec<_>>();
-    /// assert_eq!(4, all_errors.len());
+    /// assert_eq!(5, all_errors.len());
     /// ```
     pub fn iter() -> impl Iterator<Item = Self> {
         // N.B.: This has no compile-time guarantees that it is complete.
@@ -68,6 +71,7 @@ impl ErrorType {
             ErrorType::InvalidLabel,
             ErrorType::InvalidState,
             ErrorType::InvalidOverflow,
+            ErrorType::InvalidType,
         ]
         .iter()
         .copied()
@@ -83,6 +87,7 @@ impl TryFrom<i32> for ErrorType {
             1 => Ok(ErrorType::InvalidLabel),
             2 => Ok(ErrorType::InvalidState),
             3 => Ok(ErrorType::InvalidOverflow),
+            4 => Ok(ErrorType::InvalidType),
             e => Err(ErrorKind::Lifetime(e).into()),
         }
     }
@@ -186,6 +191,8 @@ mod test {
         assert_eq!(error, ErrorType::InvalidState);
         let error: ErrorType = std::convert::TryFrom::try_from(3).unwrap();
         assert_eq!(error, ErrorType::InvalidOverflow);
+        let error: ErrorType = std::convert::TryFrom::try_from(4).unwrap();
+        assert_eq!(error, ErrorType::InvalidType);
     }
 
     #[test]
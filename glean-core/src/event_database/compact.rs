@@ -0,0 +1,534 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Compact binary encoding of the on-disk event stores.
+//!
+//! A store file starts with [`MAGIC`], followed by a sequence of records.
+//! Each record is its payload length as a little-endian `u32`,
+//! followed by the bincode-encoded [`Record`].
+//!
+//! Strings repeated across events (categories, names and extra keys)
+//! and the metadata shared by all events of a session are written once as definitions
+//! and referenced by their index afterwards.
+//! These interning tables are scoped to a single file and rebuilt when the file is opened.
+//!
+//! Files without the magic header are in the previous format,
+//! with one JSON-encoded event per line.
+//! They are converted when opened.
+
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use malloc_size_of::MallocSizeOf;
+use malloc_size_of_derive::MallocSizeOf;
+use serde::{Deserialize, Serialize};
+
+use super::{RecordedEvent, StoredEvent};
+use crate::session::SessionMetadata;
+
+/// Identifies a store file in the compact format, including the format version.
+const MAGIC: &[u8] = b"GLEANEV\x01";
+
+/// Size of the length prefix of each record.
+const LENGTH_SIZE: usize = 4;
+
+/// Extension of the temporary file a store is written to before replacing the original.
+pub(super) const TMP_EXTENSION: &str = "tmp";
+
+/// A single record in a store file.
+#[derive(Debug, Serialize, Deserialize)]
+enum Record {
+    /// Defines the next entry of the string table.
+    String(String),
+    /// Defines the next entry of the session table.
+    Session(SessionInfo),
+    /// An event, referencing earlier definitions.
+    Event(CompactEvent),
+}
+
+/// The part of [`SessionMetadata`] shared by all events of a session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, MallocSizeOf)]
+struct SessionInfo {
+    session_id: String,
+    session_seq: u64,
+    session_sample_rate: f64,
+    session_start_time: Option<String>,
+}
+
+impl SessionInfo {
+    fn new(session: &SessionMetadata) -> Self {
+        Self {
+            session_id: session.session_id.clone(),
+            session_seq: session.session_seq,
+            session_sample_rate: session.session_sample_rate,
+            session_start_time: session.session_start_time.clone(),
+        }
+    }
+
+    fn with_event_seq(&self, event_seq: u64) -> SessionMetadata {
+        SessionMetadata {
+            session_id: self.session_id.clone(),
+            session_seq: self.session_seq,
+            event_seq,
+            session_sample_rate: self.session_sample_rate,
+            session_start_time: self.session_start_time.clone(),
+        }
+    }
+}
+
+/// An event with its strings and session replaced by table indices.
+#[derive(Debug, Serialize, Deserialize)]
+struct CompactEvent {
+    timestamp: u64,
+    category: u32,
+    name: u32,
+    /// Pairs of interned extra key and value.
+    extra: Option<Vec<(u32, String)>>,
+    /// Index into the session table and the event's sequence number within the session.
+    session: Option<(u32, u64)>,
+    execution_counter: Option<i32>,
+}
+
+/// The definitions read from a store file so far.
+#[derive(Debug, Default)]
+struct Tables {
+    strings: Vec<String>,
+    sessions: Vec<SessionInfo>,
+}
+
+impl Tables {
+    /// Resolves the indices of a compact event.
+    ///
+    /// Returns `None` if the event references an unknown definition.
+    fn resolve(&self, event: CompactEvent) -> Option<StoredEvent> {
+        let string = |idx: u32| self.strings.get(idx as usize).cloned();
+
+        let extra = match event.extra {
+            Some(extra) => Some(
+                extra
+                    .into_iter()
+                    .map(|(key, value)| Some((string(key)?, value)))
+                    .collect::<Option<HashMap<_, _>>>()?,
+            ),
+            None => None,
+        };
+        let session = match event.session {
+            Some((idx, event_seq)) => {
+                Some(self.sessions.get(idx as usize)?.with_event_seq(event_seq))
+            }
+            None => None,
+        };
+
+        Some(StoredEvent {
+            event: RecordedEvent {
+                timestamp: event.timestamp,
+                category: string(event.category)?,
+                name: string(event.name)?,
+                extra,
+                session,
+            },
+            execution_counter: event.execution_counter,
+        })
+    }
+}
+
+/// Decodes a store file in the compact format.
+///
+/// Decoding stops at the first truncated or undecodable record.
+/// Events referencing unknown definitions are skipped.
+///
+/// # Returns
+///
+/// The decoded events, the definitions and the length of the valid prefix of `data`.
+fn decode(data: &[u8]) -> (Vec<StoredEvent>, Tables, usize) {
+    let mut tables = Tables::default();
+    let mut events = Vec::new();
+    let mut offset = MAGIC.len();
+
+    while let Some(len) = data.get(offset..offset + LENGTH_SIZE) {
+        let len = u32::from_le_bytes(len.try_into().unwrap()) as usize; // safe unwrap, slice has the right length
+        let start = offset + LENGTH_SIZE;
+        let Some(payload) = data.get(start..start + len) else {
+            break;
+        };
+        let Ok(record) = bincode::deserialize::<Record>(payload) else {
+            break;
+        };
+
+        match record {
+            Record::String(s) => tables.strings.push(s),
+            Record::Session(info) => tables.sessions.push(info),
+            Record::Event(event) => match tables.resolve(event) {
+                Some(event) => events.push(event),
+                None => log::warn!("Skipping event with unknown references"),
+            },
+        }
+        offset = start + len;
+    }
+
+    (events, tables, offset)
+}
+
+/// Decodes a store file in the previous format, one JSON-encoded event per line.
+///
+/// Lines that fail to parse, e.g. a truncated last line, are skipped.
+fn decode_json_lines(data: &[u8]) -> Vec<StoredEvent> {
+    data.split(|&b| b == b'\n')
+        .filter_map(|line| serde_json::from_slice::<StoredEvent>(line).ok())
+        .collect()
+}
+
+fn push_record(buf: &mut Vec<u8>, record: &Record) {
+    let payload = bincode::serialize(record).expect("IMPOSSIBLE: Serializing event failed");
+    buf.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    buf.extend_from_slice(&payload);
+}
+
+/// The size in bytes a store file holding `events` has.
+///
+/// Used for events that only exist in memory because writing them failed.
+pub(super) fn encoded_size(events: &[StoredEvent]) -> u64 {
+    let mut encoder = Encoder::default();
+    let mut buf = MAGIC.to_vec();
+    for event in events {
+        encoder.encode(event, &mut buf);
+    }
+    buf.len() as u64
+}
+
+/// Reads the events of the store file at `path`, in either format, without modifying it.
+pub(super) fn read(path: &Path) -> io::Result<Vec<StoredEvent>> {
+    let data = fs::read(path)?;
+    if data.starts_with(MAGIC) {
+        Ok(decode(&data).0)
+    } else {
+        Ok(decode_json_lines(&data))
+    }
+}
+
+/// An event store file opened for appending, along with its interning tables.
+#[derive(Debug)]
+pub(super) struct StoreFile {
+    file: File,
+    /// Size of the file in bytes.
+    size: u64,
+    encoder: Encoder,
+}
+
+impl MallocSizeOf for StoreFile {
+    fn size_of(&self, ops: &mut malloc_size_of::MallocSizeOfOps) -> usize {
+        // `File` doesn't allocate.
+        self.encoder.size_of(ops)
+    }
+}
+
+/// The interning tables of a store file, used to encode events.
+#[derive(Debug, Default, MallocSizeOf)]
+struct Encoder {
+    strings: HashMap<String, u32>,
+    sessions: Vec<SessionInfo>,
+}
+
+impl StoreFile {
+    /// Opens the store file at `path` for appending, creating it if necessary.
+    ///
+    /// Files in the previous JSON format are converted.
+    /// A truncated or corrupted tail is cut off, so that new events are appended
+    /// right after the last complete record.
+    ///
+    /// # Returns
+    ///
+    /// The opened file and the events already stored in it.
+    pub(super) fn open(path: &Path) -> io::Result<(Self, Vec<StoredEvent>)> {
+        let data = match fs::read(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+
+        if !data.starts_with(MAGIC) {
+            let events = decode_json_lines(&data);
+            if !data.is_empty() {
+                log::info!(
+                    "Converting {} events in {} to the compact format",
+                    events.len(),
+                    path.display()
+                );
+            }
+            let store = Self::create(path, &events)?;
+            return Ok((store, events));
+        }
+
+        let (events, tables, valid_len) = decode(&data);
+        let mut file = OpenOptions::new().write(true).open(path)?;
+        if valid_len < data.len() {
+            log::warn!(
+                "Dropping {} bytes of truncated events from {}",
+                data.len() - valid_len,
+                path.display()
+            );
+            file.set_len(valid_len as u64)?;
+        }
+        let size = file.seek(SeekFrom::End(0))?;
+
+        let strings = tables
+            .strings
+            .into_iter()
+            .enumerate()
+            .map(|(idx, s)| (s, idx as u32))
+            .collect();
+        let store = Self {
+            file,
+            size,
+            encoder: Encoder {
+                strings,
+                sessions: tables.sessions,
+            },
+        };
+        Ok((store, events))
+    }
+
+    /// Writes `events` to a new store file at `path`, replacing any existing file.
+    ///
+    /// The data is written to a temporary file first, so a failure leaves the original intact.
+    pub(super) fn create(path: &Path, events: &[StoredEvent]) -> io::Result<Self> {
+        let mut store = Self {
+            // Replaced below, once the data is in place.
+            file: File::create(tmp_path(path))?,
+            size: 0,
+            encoder: Encoder::default(),
+        };
+
+        let mut buf = MAGIC.to_vec();
+        for event in events {
+            store.encoder.encode(event, &mut buf);
+        }
+        store.file.write_all(&buf)?;
+        store.file.flush()?;
+        store.size = buf.len() as u64;
+
+        fs::rename(tmp_path(path), path)?;
+        store.file = OpenOptions::new().append(true).open(path)?;
+        Ok(store)
+    }
+
+    /// Appends a single event to the file.
+    ///
+    /// On error the interning tables may no longer match the file,
+    /// so the store needs to be reopened.
+    pub(super) fn append(&mut self, event: &StoredEvent) -> io::Result<()> {
+        let mut buf = Vec::new();
+        self.encoder.encode(event, &mut buf);
+        self.file.write_all(&buf)?;
+        self.file.flush()?;
+        self.size += buf.len() as u64;
+        Ok(())
+    }
+
+    /// The size of the file in bytes.
+    pub(super) fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+impl Encoder {
+    /// Encodes `event` into `buf`, preceded by the definitions it needs.
+    fn encode(&mut self, event: &StoredEvent, buf: &mut Vec<u8>) {
+        let StoredEvent {
+            event,
+            execution_counter,
+        } = event;
+
+        let category = self.intern(&event.category, buf);
+        let name = self.intern(&event.name, buf);
+        let extra = event.extra.as_ref().map(|extra| {
+            extra
+                .iter()
+                .map(|(key, value)| (self.intern(key, buf), value.clone()))
+                .collect()
+        });
+        let session = event.session.as_ref().map(|session| {
+            let info = SessionInfo::new(session);
+            // Events of the same session are usually written together, so search from the end.
+            let idx = match self.sessions.iter().rposition(|known| *known == info) {
+                Some(idx) => idx,
+                None => {
+                    push_record(buf, &Record::Session(info.clone()));
+                    self.sessions.push(info);
+                    self.sessions.len() - 1
+                }
+            };
+            (idx as u32, session.event_seq)
+        });
+
+        let event = CompactEvent {
+            timestamp: event.timestamp,
+            category,
+            name,
+            extra,
+            session,
+            execution_counter: *execution_counter,
+        };
+        push_record(buf, &Record::Event(event));
+    }
+
+    /// Returns the index of `s` in the string table, defining it in `buf` if needed.
+    fn intern(&mut self, s: &str, buf: &mut Vec<u8>) -> u32 {
+        if let Some(&idx) = self.strings.get(s) {
+            return idx;
+        }
+
+        let idx = self.strings.len() as u32;
+        push_record(buf, &Record::String(s.to_owned()));
+        self.strings.insert(s.to_owned(), idx);
+        idx
+    }
+}
+
+/// The path of the temporary file used when replacing the store file at `path`.
+pub(super) fn tmp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_owned();
+    name.push(".");
+    name.push(TMP_EXTENSION);
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn event(timestamp: u64, name: &str, session: Option<(&str, u64)>) -> StoredEvent {
+        StoredEvent {
+            event: RecordedEvent {
+                timestamp,
+                category: "category".into(),
+                name: name.into(),
+                extra: Some([("key".to_string(), format!("value {timestamp}"))].into()),
+                session: session.map(|(session_id, event_seq)| SessionMetadata {
+                    session_id: session_id.into(),
+                    session_seq: 1,
+                    event_seq,
+                    session_sample_rate: 1.0,
+                    session_start_time: Some("2026-01-01T00:00:00+00:00".into()),
+                }),
+            },
+            execution_counter: Some(2),
+        }
+    }
+
+    #[test]
+    fn roundtrips_events_across_reopening() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("store1");
+        let events = vec![
+            event(1, "one", Some(("session-a", 0))),
+            event(2, "two", Some(("session-a", 1))),
+            event(3, "one", None),
+            event(4, "two", Some(("session-b", 0))),
+        ];
+
+        {
+            let (mut store, existing) = StoreFile::open(&path).unwrap();
+            assert!(existing.is_empty());
+            for event in &events[..2] {
+                store.append(event).unwrap();
+            }
+        }
+
+        // Appending after reopening needs to reuse the tables rebuilt from the file.
+        {
+            let (mut store, existing) = StoreFile::open(&path).unwrap();
+            assert_eq!(events[..2], existing[..]);
+            for event in &events[2..] {
+                store.append(event).unwrap();
+            }
+        }
+
+        let (_, existing) = StoreFile::open(&path).unwrap();
+        assert_eq!(events, existing);
+    }
+
+    #[test]
+    fn interns_repeated_strings_and_sessions() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("store1");
+        let events: Vec<_> = (0..100)
+            .map(|i| event(i, "name", Some(("session-a", i))))
+            .collect();
+
+        let (mut store, _) = StoreFile::open(&path).unwrap();
+        for event in &events {
+            store.append(event).unwrap();
+        }
+
+        let data = fs::read(&path).unwrap();
+        let (decoded, tables, valid_len) = decode(&data);
+        assert_eq!(events, decoded);
+        assert_eq!(data.len(), valid_len);
+        assert_eq!(vec!["category", "name", "key"], tables.strings);
+        assert_eq!(1, tables.sessions.len());
+
+        let json_len: usize = events
+            .iter()
+            .map(|event| serde_json::to_string(event).unwrap().len() + 1)
+            .sum();
+        assert!(data.len() < json_len / 2);
+    }
+
+    #[test]
+    fn converts_json_lines_files() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("store1");
+        let events = vec![
+            event(1, "one", Some(("session-a", 0))),
+            event(2, "two", None),
+        ];
+        let lines: String = events
+            .iter()
+            .map(|event| serde_json::to_string(event).unwrap() + "\n")
+            .collect();
+        fs::write(&path, lines).unwrap();
+
+        let (_, existing) = StoreFile::open(&path).unwrap();
+        assert_eq!(events, existing);
+        assert!(fs::read(&path).unwrap().starts_with(MAGIC));
+        assert!(!tmp_path(&path).exists());
+
+        let (_, existing) = StoreFile::open(&path).unwrap();
+        assert_eq!(events, existing);
+    }
+
+    #[test]
+    fn drops_truncated_tail() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("store1");
+        let events = vec![event(1, "one", None), event(2, "two", None)];
+
+        {
+            let (mut store, _) = StoreFile::open(&path).unwrap();
+            for event in &events {
+                store.append(event).unwrap();
+            }
+        }
+
+        // Simulate a partially written record.
+        let mut data = fs::read(&path).unwrap();
+        data.extend_from_slice(&[200, 0, 0, 0, 1, 2, 3]);
+        fs::write(&path, &data).unwrap();
+
+        let extra_event = event(3, "three", None);
+        {
+            let (mut store, existing) = StoreFile::open(&path).unwrap();
+            assert_eq!(events, existing);
+            store.append(&extra_event).unwrap();
+        }
+
+        let (_, existing) = StoreFile::open(&path).unwrap();
+        assert_eq!(3, existing.len());
+        assert_eq!(extra_event, existing[2]);
+    }
+}
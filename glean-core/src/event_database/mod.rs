@@ -5,13 +5,10 @@
 use std::cmp::Ordering;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
-use std::fs::{create_dir_all, File, OpenOptions};
-use std::io::BufReader;
-use std::io::Write;
-use std::io::{self, BufRead};
+use std::fs::{self, create_dir_all};
+use std::io;
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex, RwLock};
-use std::{fs, mem};
+use std::sync::{Mutex, RwLock};
 
 use chrono::{DateTime, FixedOffset, Utc};
 
@@ -28,7 +25,11 @@ use crate::storage::INTERNAL_STORAGE;
 use crate::util::get_iso_time_string;
 use crate::Glean;
 use crate::Result;
-use crate::{CommonMetricData, CounterMetric, Lifetime};
+use crate::{CommonMetricData, CounterMetric, EventStoreLimits, Lifetime};
+
+mod compact;
+
+use compact::StoreFile;
 
 /// Represents the recorded data for a single event.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, MallocSizeOf)]
@@ -84,9 +85,9 @@ struct StoredEvent {
 /// This struct handles the in-memory and on-disk storage logic for events.
 ///
 /// So that the data survives shutting down of the application, events are stored
-/// in an append-only file on disk, in addition to the store in memory. Each line
-/// of this file records a single event in JSON, exactly as it will be sent in the
-/// ping. There is one file per store.
+/// in an append-only file on disk, in addition to the store in memory. The file
+/// uses a compact binary encoding (see the `compact` module), which is converted
+/// to the JSON sent in the ping only when snapshotting. There is one file per store.
 ///
 /// When restarting the application, these on-disk files are checked, and if any are
 /// found, they are loaded, and a `glean.restarted` event is added before any
@@ -108,9 +109,11 @@ pub struct EventDatabase {
     pub path: PathBuf,
     /// The in-memory list of events
     event_stores: RwLock<HashMap<String, Vec<StoredEvent>>>,
-    event_store_files: RwLock<HashMap<String, Arc<File>>>,
+    event_store_files: RwLock<HashMap<String, StoreFile>>,
     /// A lock to be held when doing operations on the filesystem
     file_lock: Mutex<()>,
+    /// Caps applied to every store, if any.
+    limits: Option<EventStoreLimits>,
 }
 
 impl MallocSizeOf for EventDatabase {
@@ -118,12 +121,7 @@ impl MallocSizeOf for EventDatabase {
         let mut n = 0;
         n += self.event_stores.read().unwrap().size_of(ops);
 
-        let map = self.event_store_files.read().unwrap();
-        for store_name in map.keys() {
-            n += store_name.size_of(ops);
-            // `File` doesn't allocate, but `Arc` puts it on the heap.
-            n += mem::size_of::<File>();
-        }
+        n += self.event_store_files.read().unwrap().size_of(ops);
         n
     }
 }
@@ -144,9 +142,15 @@ impl EventDatabase {
             event_stores: RwLock::new(HashMap::new()),
             event_store_files: RwLock::new(HashMap::new()),
             file_lock: Mutex::new(()),
+            limits: None,
         })
     }
 
+    /// Sets the caps applied to every store.
+    pub fn set_limits(&mut self, limits: EventStoreLimits) {
+        self.limits = Some(limits);
+    }
+
     /// Initializes events storage after Glean is fully initialized and ready to send pings.
     ///
     /// This must be called once on application startup, e.g. from
@@ -155,6 +159,8 @@ impl EventDatabase {
     ///
     /// If there are any events queued on disk, it loads them into memory so
     /// that the memory and disk representations are in sync.
+    /// Stores written in the previous JSON-lines format are converted to the
+    /// compact format along the way.
     ///
     /// If event records for the "events" ping are present, they are assembled into
     /// an "events" ping which is submitted immediately with reason "startup".
@@ -252,11 +258,17 @@ impl EventDatabase {
         // This is a potential lock-order-inversion.
         let mut db = self.event_stores.write().unwrap(); // safe unwrap, only error case is poisoning
         let _lock = self.file_lock.lock().unwrap(); // safe unwrap, only error case is poisoning
+        let mut files = self.event_store_files.write().unwrap(); // safe unwrap, only error case is poisoning
 
         for entry in fs::read_dir(&self.path)? {
             let entry = entry?;
             if entry.file_type()?.is_file() {
                 let store_name = entry.file_name().into_string()?;
+                if Path::new(&store_name).extension() == Some(compact::TMP_EXTENSION.as_ref()) {
+                    // Left over from an interrupted conversion. The original is still in place.
+                    let _ = fs::remove_file(entry.path());
+                    continue;
+                }
                 log::info!("Loading events for {}", store_name);
                 if trim_data_to_registered_pings && glean.get_ping_by_name(&store_name).is_none() {
                     log::warn!("Trimming {}'s events", store_name);
@@ -270,14 +282,9 @@ impl EventDatabase {
                     }
                     continue;
                 }
-                let file = BufReader::new(File::open(entry.path())?);
-                db.insert(
-                    store_name,
-                    file.lines()
-                        .map_while(Result::ok)
-                        .filter_map(|line| serde_json::from_str::<StoredEvent>(&line).ok())
-                        .collect(),
-                );
+                let (file, events) = StoreFile::open(&entry.path())?;
+                files.insert(store_name.clone(), file);
+                db.insert(store_name, events);
             }
         }
         Ok(())
@@ -297,6 +304,11 @@ impl EventDatabase {
     /// * `ctx` - The event's session context, conveying both whether session
     ///   metadata should be attached and what that metadata is.
     ///
+    /// If a store exceeds the configured [`EventStoreLimits`], either the
+    /// store's ping is submitted with reason `max_capacity`, or its oldest
+    /// events are dropped and an `invalid_overflow` error is recorded.
+    /// The oldest events are also dropped if the ping can't be submitted.
+    ///
     /// ## Returns
     ///
     /// `true` if a ping was submitted and should be uploaded.
@@ -320,7 +332,9 @@ impl EventDatabase {
             EventSessionContext::InSession(session_meta) => Some(session_meta),
         };
 
-        let mut submit_max_capacity_event_ping = false;
+        let mut pings_to_submit = Vec::new();
+        let mut full_stores = Vec::new();
+        let mut overflows = Vec::new();
         {
             let mut db = self.event_stores.write().unwrap(); // safe unwrap, only error case is poisoning
             for store_name in meta.inner.send_in_pings.iter() {
@@ -337,7 +351,6 @@ impl EventDatabase {
                     ..Default::default()
                 })
                 .get_value(glean, INTERNAL_STORAGE);
-                // Create StoredEvent object, and its JSON form for serialization on disk.
                 let event = StoredEvent {
                     event: RecordedEvent {
                         timestamp,
@@ -348,39 +361,140 @@ impl EventDatabase {
                     },
                     execution_counter,
                 };
-                let event_json = serde_json::to_string(&event).unwrap(); // safe unwrap, event can always be serialized
+                let written_size = self.write_event_to_disk(store_name, &event);
                 store.push(event);
-                self.write_event_to_disk(store_name, &event_json);
+                // Events that couldn't be written still count towards the limits.
+                let size = written_size.unwrap_or_else(|| compact::encoded_size(store));
                 if store_name == "events" && store.len() == glean.get_max_events() {
-                    submit_max_capacity_event_ping = true;
+                    pings_to_submit.push(store_name);
+                    continue;
+                }
+
+                let Some(limits) = &self.limits else {
+                    continue;
+                };
+                if !limits.drop_oldest {
+                    let full = limits
+                        .max_events
+                        .is_some_and(|max| store.len() >= max as usize)
+                        || limits.max_bytes.is_some_and(|max| size >= max);
+                    if full {
+                        pings_to_submit.push(store_name);
+                        full_stores.push(store_name);
+                    }
+                } else {
+                    let dropped = self.drop_oldest_events(store_name, store, size, limits);
+                    if dropped > 0 {
+                        overflows.push((store_name, dropped));
+                    }
                 }
             }
         }
-        if submit_max_capacity_event_ping {
-            glean.submit_ping_by_name("events", Some("max_capacity"));
-            true
-        } else {
-            false
+
+        // Recording errors writes to the database, which must not happen while holding the lock.
+        for (store_name, dropped) in overflows.drain(..) {
+            record_overflow(glean, meta, store_name, dropped);
+        }
+
+        // We do not want to be holding the event stores lock when submitting a ping.
+        for ping_name in &pings_to_submit {
+            glean.submit_ping_by_name(ping_name, Some("max_capacity"));
         }
+
+        // A ping that isn't registered or is disabled can't be submitted,
+        // leaving its store full. Drop the oldest events then to keep the store bounded.
+        if let Some(limits) = &self.limits {
+            let mut db = self.event_stores.write().unwrap(); // safe unwrap, only error case is poisoning
+            for store_name in full_stores {
+                let Some(store) = db.get_mut(store_name) else {
+                    continue;
+                };
+                let size = {
+                    let map = self.event_store_files.read().unwrap(); // safe unwrap, only error case is poisoning
+                    map.get(store_name).map(|file| file.size())
+                };
+                let size = size.unwrap_or_else(|| compact::encoded_size(store));
+                let dropped = self.drop_oldest_events(store_name, store, size, limits);
+                if dropped > 0 {
+                    overflows.push((store_name, dropped));
+                }
+            }
+        }
+        for (store_name, dropped) in overflows {
+            record_overflow(glean, meta, store_name, dropped);
+        }
+        !pings_to_submit.is_empty()
     }
 
-    fn get_event_store(&self, store_name: &str) -> Result<Arc<File>, io::Error> {
-        // safe unwrap, only error case is poisoning
-        let mut map = self.event_store_files.write().unwrap();
-        let entry = map.entry(store_name.to_string());
-
-        match entry {
-            Entry::Occupied(entry) => Ok(Arc::clone(entry.get())),
-            Entry::Vacant(entry) => {
-                let file = OpenOptions::new()
-                    .create(true)
-                    .append(true)
-                    .open(self.path.join(store_name))?;
-                let file = Arc::new(file);
-                let entry = entry.insert(file);
-                Ok(Arc::clone(entry))
+    /// Drops the oldest events of a store exceeding its limits.
+    ///
+    /// To avoid rewriting the store on every following event,
+    /// the store is trimmed to 90% of the exceeded limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `store_name` - The name of the store.
+    /// * `store` - The in-memory events of the store.
+    /// * `size` - The current size of the store, as written to disk.
+    /// * `limits` - The limits to enforce.
+    ///
+    /// # Returns
+    ///
+    /// The number of dropped events.
+    fn drop_oldest_events(
+        &self,
+        store_name: &str,
+        store: &mut Vec<StoredEvent>,
+        size: u64,
+        limits: &EventStoreLimits,
+    ) -> usize {
+        let over_count = limits
+            .max_events
+            .is_some_and(|max| store.len() > max as usize);
+        let over_size = limits.max_bytes.is_some_and(|max| size > max);
+        if !over_count && !over_size {
+            return 0;
+        }
+
+        let mut dropped = 0;
+        if let Some(max) = limits.max_events.filter(|_| over_count) {
+            let target = max as usize - max as usize / 10;
+            dropped = store.len() - target;
+            store.drain(..dropped);
+        }
+
+        let _lock = self.file_lock.lock().unwrap(); // safe unwrap, only error case is poisoning
+        let mut map = self.event_store_files.write().unwrap(); // safe unwrap, only error case is poisoning
+        let path = self.path.join(store_name);
+        let target_size = limits.max_bytes.map(|max| max - max / 10);
+        loop {
+            let size = match StoreFile::create(&path, store) {
+                Ok(file) => {
+                    let size = file.size();
+                    map.insert(store_name.to_string(), file);
+                    size
+                }
+                Err(err) => {
+                    log::warn!("IO error rewriting event store '{}': {}", store_name, err);
+                    map.remove(store_name);
+                    // The events are still kept in memory, and count towards the limit.
+                    compact::encoded_size(store)
+                }
+            };
+
+            match target_size {
+                Some(target) if size > target && !store.is_empty() => {
+                    // Events are roughly the same size, so drop them proportionally.
+                    let excess = (size - target) as usize;
+                    let n = (store.len() * excess).div_ceil(size as usize).max(1);
+                    store.drain(..n);
+                    dropped += n;
+                }
+                _ => break,
             }
         }
+
+        dropped
     }
 
     /// Writes an event to a single store on disk.
@@ -388,20 +502,35 @@ impl EventDatabase {
     /// # Arguments
     ///
     /// * `store_name` - The name of the store.
-    /// * `event_json` - The event content, as a single-line JSON-encoded string.
-    fn write_event_to_disk(&self, store_name: &str, event_json: &str) {
+    /// * `event` - The event to append to the store.
+    ///
+    /// # Returns
+    ///
+    /// The size of the store on disk, `None` if writing failed.
+    fn write_event_to_disk(&self, store_name: &str, event: &StoredEvent) -> Option<u64> {
         let _lock = self.file_lock.lock().unwrap(); // safe unwrap, only error case is poisoning
+        let mut map = self.event_store_files.write().unwrap(); // safe unwrap, only error case is poisoning
 
         let write_res = (|| {
-            let mut file = self.get_event_store(store_name)?;
-            file.write_all(event_json.as_bytes())?;
-            file.write_all(b"\n")?;
-            file.flush()?;
-            Ok::<(), std::io::Error>(())
+            let file = match map.entry(store_name.to_string()) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    entry.insert(StoreFile::open(&self.path.join(store_name))?.0)
+                }
+            };
+            file.append(event)?;
+            Ok::<_, io::Error>(file.size())
         })();
 
-        if let Err(err) = write_res {
-            log::warn!("IO error writing event to store '{}': {}", store_name, err);
+        match write_res {
+            Ok(size) => Some(size),
+            Err(err) => {
+                // The interning tables might be out of sync with the file now.
+                // Reopening the store rebuilds them from what made it to disk.
+                map.remove(store_name);
+                log::warn!("IO error writing event to store '{}': {}", store_name, err);
+                None
+            }
         }
     }
 
@@ -692,6 +821,12 @@ impl EventDatabase {
         Ok(())
     }
 
+    /// Reads the events of a store file on disk, without loading or modifying it.
+    pub(crate) fn read_store_file(path: &Path) -> io::Result<Vec<RecordedEvent>> {
+        let events = compact::read(path)?;
+        Ok(events.into_iter().map(|stored| stored.event).collect())
+    }
+
     /// **Test-only API (exported for FFI purposes).**
     ///
     /// Gets the vector of currently stored events for the given event metric in
@@ -721,6 +856,22 @@ impl EventDatabase {
     }
 }
 
+/// Records an `invalid_overflow` error for events dropped from a full store.
+fn record_overflow(
+    glean: &Glean,
+    meta: &CommonMetricDataInternal,
+    store_name: &str,
+    dropped: usize,
+) {
+    record_error(
+        glean,
+        meta,
+        ErrorType::InvalidOverflow,
+        format!("Dropped {dropped} events from full store '{store_name}'"),
+        dropped as i32,
+    );
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -733,13 +884,14 @@ mod test {
         let (glean, t) = new_glean(None);
 
         {
+            // Written in the previous JSON-lines format.
             let db = EventDatabase::new(t.path()).unwrap();
-            db.write_event_to_disk("events", "{\"timestamp\": 500");
-            db.write_event_to_disk("events", "{\"timestamp\"");
-            db.write_event_to_disk(
-                "events",
-                "{\"timestamp\": 501, \"category\": \"ui\", \"name\": \"click\"}",
+            let lines = concat!(
+                "{\"timestamp\": 500\n",
+                "{\"timestamp\"\n",
+                "{\"timestamp\": 501, \"category\": \"ui\", \"name\": \"click\"}\n",
             );
+            fs::write(db.path.join("events"), lines).unwrap();
         }
 
         {
@@ -26,7 +26,7 @@ pub struct Functional {
 
 impl Functional {
     /// Instantiate a new functional bucketing.
-    fn new(log_base: f64, buckets_per_magnitude: f64) -> Functional {
+    pub(crate) fn new(log_base: f64, buckets_per_magnitude: f64) -> Functional {
         // Set the FPU control flag to the required state within this function
         let _fpc = FloatingPointContext::new();
 
@@ -68,6 +68,25 @@ impl Bucketing for Functional {
     fn ranges(&self) -> &[u64] {
         unimplemented!("Bucket ranges for functional bucketing are not precomputed")
     }
+
+    fn bucket_upper_bound(&self, bucket_minimum: u64) -> Option<u64> {
+        if bucket_minimum == 0 {
+            return Some(1);
+        }
+
+        // Small indices can round down to the same minimum, so skip ahead to the next bucket.
+        let mut index = self.sample_to_bucket_index(bucket_minimum);
+        loop {
+            index += 1;
+            let next = self.bucket_index_to_bucket_minimum(index);
+            if next > bucket_minimum {
+                return Some(next);
+            }
+            if next == u64::MAX {
+                return None;
+            }
+        }
+    }
 }
 
 impl Histogram<Functional> {
@@ -89,6 +108,40 @@ impl Histogram<Functional> {
     pub fn snapshot(&self) -> &HashMap<u64, u64> {
         &self.values
     }
+
+    /// Moves the samples of this histogram into buckets with a different number of buckets
+    /// per order of magnitude of `log_base`.
+    ///
+    /// The samples of a bucket go into the new bucket its minimum falls into,
+    /// so the result is only as precise as the coarser of the two bucketings.
+    pub fn rebucket(&self, log_base: f64, buckets_per_magnitude: f64) -> Histogram<Functional> {
+        let bucketing = Functional::new(log_base, buckets_per_magnitude);
+        let mut values = HashMap::new();
+        for (&min, &count) in &self.values {
+            let entry = values
+                .entry(bucketing.sample_to_bucket_minimum(min))
+                .or_insert(0u64);
+            *entry = entry.saturating_add(count);
+        }
+
+        Histogram {
+            values,
+            count: self.count,
+            sum: self.sum,
+            bucketing,
+        }
+    }
+
+    /// Gets the number of buckets per order of magnitude of `log_base`.
+    ///
+    /// This reverses the computation done in [`Histogram::functional`],
+    /// rounded to the nearest integer.
+    pub fn buckets_per_magnitude(&self, log_base: f64) -> u32 {
+        // Set the FPU control flag to the required state within this function
+        let _fpc = FloatingPointContext::new();
+
+        (log_base.ln() / self.bucketing.exponent.ln()).round() as u32
+    }
 }
 
 #[cfg(test)]
@@ -137,6 +190,40 @@ mod test {
         }
     }
 
+    #[test]
+    fn bucket_upper_bound_is_the_next_bucket_minimum() {
+        let bucketing = Functional::new(2.0, 8.0);
+
+        for value in 0..10_000 {
+            let min = bucketing.sample_to_bucket_minimum(value);
+            let upper = bucketing.bucket_upper_bound(min).unwrap();
+            assert!(min <= value && value < upper, "{min} <= {value} < {upper}");
+            assert_eq!(min, bucketing.sample_to_bucket_minimum(upper - 1));
+            assert_eq!(upper, bucketing.sample_to_bucket_minimum(upper));
+        }
+    }
+
+    #[test]
+    fn rebucketing_keeps_count_and_sum() {
+        let mut hist = Histogram::functional(2.0, 16.0);
+        for i in 1..=1000 {
+            hist.accumulate(i);
+        }
+
+        let rebucketed = hist.rebucket(2.0, 4.0);
+        assert_eq!(4, rebucketed.buckets_per_magnitude(2.0));
+        assert_eq!(hist.count(), rebucketed.count());
+        assert_eq!(hist.sum(), rebucketed.sum());
+        assert_eq!(hist.count(), rebucketed.values().values().sum::<u64>());
+        assert!(rebucketed.values().len() < hist.values().len());
+
+        // The rebucketed histogram can be merged with new samples.
+        let mut other = Histogram::functional(2.0, 4.0);
+        other.accumulate(10);
+        other.merge(&rebucketed);
+        assert_eq!(1001, other.count());
+    }
+
     #[test]
     fn histogram_merge() {
         let mut hist = Histogram::functional(2.0, 8.0);
@@ -124,6 +124,19 @@ mod test {
         assert_eq!(1, hist.values[&DEFAULT_RANGE_MAX]);
     }
 
+    #[test]
+    fn bucket_upper_bounds_leave_out_the_overflow_bucket() {
+        let mut hist = Histogram::linear(1, 10, 10);
+        hist.accumulate(3);
+        hist.accumulate(9);
+        hist.accumulate(100);
+
+        let bounds = hist.bucket_upper_bounds();
+        assert_eq!(Some(&4), bounds.get(&3));
+        assert_eq!(Some(&10), bounds.get(&8));
+        assert_eq!(None, bounds.get(&10));
+    }
+
     #[test]
     fn short_linear_buckets_are_correct() {
         let test_buckets = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 10];
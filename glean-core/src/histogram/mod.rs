@@ -84,6 +84,16 @@ pub trait Bucketing {
 
     /// The computed bucket ranges for this bucketing algorithm.
     fn ranges(&self) -> &[u64];
+
+    /// Gets the exclusive upper bound of the bucket with the given minimum,
+    /// which is the minimum of the next bucket.
+    ///
+    /// Returns `None` for the last bucket, which is unbounded.
+    fn bucket_upper_bound(&self, bucket_minimum: u64) -> Option<u64> {
+        let ranges = self.ranges();
+        let index = ranges.binary_search(&bucket_minimum).ok()?;
+        ranges.get(index + 1).copied()
+    }
 }
 
 impl<B: Bucketing> Histogram<B> {
@@ -121,6 +131,16 @@ impl<B: Bucketing> Histogram<B> {
         self.count() == 0
     }
 
+    /// Gets the exclusive upper bound of each filled bucket, by the bucket's minimum.
+    ///
+    /// The last bucket of a histogram with precomputed buckets is unbounded and left out.
+    pub fn bucket_upper_bounds(&self) -> HashMap<u64, u64> {
+        self.values
+            .keys()
+            .filter_map(|&min| Some((min, self.bucketing.bucket_upper_bound(min)?)))
+            .collect()
+    }
+
     /// Gets a snapshot of all values from the first bucket until one past the last filled bucket,
     /// filling in empty buckets with 0.
     pub fn snapshot_values(&self) -> HashMap<u64, u64> {
@@ -0,0 +1,209 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! A HyperLogLog sketch to estimate the number of distinct values.
+//!
+//! Values are hashed with a hash function that is stable across platforms and releases,
+//! so that sketches from different clients and pings can be merged by taking the maximum
+//! of each register.
+
+use std::collections::BTreeMap;
+
+use malloc_size_of_derive::MallocSizeOf;
+use serde::{Deserialize, Serialize};
+
+/// The default number of index bits.
+///
+/// 2^10 registers give a standard error of about 3.25%.
+pub const DEFAULT_PRECISION: u8 = 10;
+
+/// A HyperLogLog sketch.
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// let mut hll = HyperLogLog::new(DEFAULT_PRECISION);
+///
+/// for value in ["a", "b", "a"] {
+///     hll.insert(value);
+/// }
+///
+/// assert_eq!(2, hll.estimate());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, MallocSizeOf)]
+pub struct HyperLogLog {
+    /// The number of bits of the hash used to select a register.
+    precision: u8,
+    /// One register per index, holding the maximum rank observed.
+    registers: Vec<u8>,
+}
+
+/// The serialized form of a sketch in a ping payload.
+///
+/// Only non-empty registers are reported, mapped from their index to their rank.
+#[derive(Debug, Serialize)]
+pub(crate) struct Snapshot {
+    estimate: i64,
+    precision: u8,
+    registers: BTreeMap<u32, u8>,
+}
+
+impl HyperLogLog {
+    /// Creates an empty sketch with `2^precision` registers.
+    ///
+    /// `precision` is clamped to the range `4..=16`.
+    pub fn new(precision: u8) -> Self {
+        let precision = precision.clamp(4, 16);
+        Self {
+            precision,
+            registers: vec![0; 1 << precision],
+        }
+    }
+
+    /// The number of bits of the hash used to select a register.
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    /// Adds a value to the sketch.
+    pub fn insert(&mut self, value: &str) {
+        let hash = hash64(value.as_bytes());
+        let index = (hash >> (64 - self.precision)) as usize;
+        // The remaining bits, with a sentinel bit to bound the rank.
+        let rest = (hash << self.precision) | (1 << (self.precision - 1));
+        let rank = rest.leading_zeros() as u8 + 1;
+        if let Some(register) = self.registers.get_mut(index) {
+            *register = (*register).max(rank);
+        }
+    }
+
+    /// Merges another sketch with the same precision into this one.
+    ///
+    /// Sketches with a different precision are ignored.
+    pub fn merge(&mut self, other: &HyperLogLog) {
+        if self.precision != other.precision {
+            return;
+        }
+        for (register, other) in self.registers.iter_mut().zip(&other.registers) {
+            *register = (*register).max(*other);
+        }
+    }
+
+    /// Estimates the number of distinct values added to the sketch.
+    pub fn estimate(&self) -> i64 {
+        let m = self.registers.len() as f64;
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / m),
+        };
+
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|&rank| 2f64.powi(-i32::from(rank)))
+            .sum();
+        let raw = alpha * m * m / sum;
+
+        let zeros = self.registers.iter().filter(|&&rank| rank == 0).count();
+        let estimate = if raw <= 2.5 * m && zeros > 0 {
+            // Linear counting is more accurate for small cardinalities.
+            m * (m / zeros as f64).ln()
+        } else {
+            raw
+        };
+        estimate.round() as i64
+    }
+
+    /// Gets the snapshot of this sketch, as reported in a ping payload.
+    pub(crate) fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            estimate: self.estimate(),
+            precision: self.precision,
+            registers: self
+                .registers
+                .iter()
+                .enumerate()
+                .filter(|(_, &rank)| rank > 0)
+                .map(|(index, &rank)| (index as u32, rank))
+                .collect(),
+        }
+    }
+}
+
+/// A 64-bit FNV-1a hash, followed by the MurmurHash3 finalizer to spread the bits.
+///
+/// This must never change, or sketches recorded by different versions can't be merged.
+fn hash64(data: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for &byte in data {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51afd7ed558ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ceb9fe1a85ec53);
+    hash ^= hash >> 33;
+    hash
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn empty_sketch_estimates_zero() {
+        let hll = HyperLogLog::new(DEFAULT_PRECISION);
+        assert_eq!(0, hll.estimate());
+        assert!(hll.snapshot().registers.is_empty());
+    }
+
+    #[test]
+    fn duplicates_are_not_counted() {
+        let mut hll = HyperLogLog::new(DEFAULT_PRECISION);
+        for _ in 0..10 {
+            hll.insert("a");
+            hll.insert("b");
+        }
+        assert_eq!(2, hll.estimate());
+    }
+
+    #[test]
+    fn estimate_is_within_error_bounds() {
+        let mut hll = HyperLogLog::new(DEFAULT_PRECISION);
+        for i in 0..100_000 {
+            hll.insert(&format!("value-{i}"));
+        }
+        let estimate = hll.estimate() as f64;
+        // Allow for 3 standard errors.
+        assert!((estimate - 100_000.0).abs() < 100_000.0 * 0.1, "{estimate}");
+    }
+
+    #[test]
+    fn merging_equals_inserting_into_one() {
+        let mut a = HyperLogLog::new(DEFAULT_PRECISION);
+        let mut b = HyperLogLog::new(DEFAULT_PRECISION);
+        let mut both = HyperLogLog::new(DEFAULT_PRECISION);
+        for i in 0..1000 {
+            let value = i.to_string();
+            if i % 2 == 0 {
+                a.insert(&value);
+            } else {
+                b.insert(&value);
+            }
+            both.insert(&value);
+        }
+
+        a.merge(&b);
+        assert_eq!(both, a);
+    }
+
+    #[test]
+    fn hash_is_stable() {
+        assert_eq!(0x5d962dd48dfcd780, hash64(b"glean"));
+    }
+}
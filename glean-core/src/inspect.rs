@@ -0,0 +1,321 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Read-only inspection of a Glean data directory.
+//!
+//! This allows tools to enumerate the metrics, event stores and pending pings
+//! that a Glean instance left on disk, without initializing Glean.
+//! Nothing is ever written, moved or deleted: corrupted files are skipped
+//! instead of being repaired, and the database is opened without creating any store.
+//!
+//! Note that the data is a snapshot of what was persisted to disk.
+//! If a running Glean instance delays ping lifetime I/O,
+//! its not yet persisted ping lifetime data is not visible.
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::str;
+
+use rkv::StoreOptions;
+use serde::Serialize;
+
+use crate::database::Rkv;
+use crate::event_database::EventDatabase;
+use crate::metrics::Metric;
+use crate::upload::{parse_ping_file, PingPayload};
+use crate::{ErrorKind, Lifetime, RecordedEvent, Result};
+use crate::{DELETION_REQUEST_PINGS_DIRECTORY, PENDING_PINGS_DIRECTORY};
+
+/// A metric value stored in the database.
+#[derive(Debug, Clone, Serialize)]
+pub struct StoredMetric {
+    /// The lifetime of the store the metric is in.
+    pub lifetime: Lifetime,
+    /// The name of the storage (usually a ping name) the metric is recorded for.
+    pub storage_name: String,
+    /// The metric identifier, including its label (if any).
+    pub metric_id: String,
+    /// The decoded metric value.
+    pub value: Metric,
+}
+
+/// The events recorded in an event store.
+#[derive(Debug, Clone, Serialize)]
+pub struct EventStore {
+    /// The name of the store (usually a ping name).
+    pub name: String,
+    /// The events in the order they were recorded.
+    pub events: Vec<RecordedEvent>,
+}
+
+/// A ping waiting on disk to be uploaded.
+#[derive(Debug, Clone)]
+pub struct PendingPing {
+    /// The name of the directory the ping is stored in.
+    pub directory: String,
+    /// The ping's payload, as it would be handed to the uploader.
+    pub payload: PingPayload,
+}
+
+/// A Glean data directory, opened for reading.
+#[derive(Debug)]
+pub struct DataDir {
+    path: PathBuf,
+}
+
+/// Opens the Glean data directory at `path` for read-only inspection.
+///
+/// # Errors
+///
+/// Returns an error if `path` is not a directory.
+pub fn open_readonly<P: AsRef<Path>>(path: P) -> Result<DataDir> {
+    let path = path.as_ref();
+    if !path.is_dir() {
+        return Err(ErrorKind::IoError(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("{} is not a directory", path.display()),
+        ))
+        .into());
+    }
+
+    Ok(DataDir {
+        path: path.to_path_buf(),
+    })
+}
+
+impl DataDir {
+    /// The path of the data directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Enumerates every metric in every lifetime store of the database.
+    ///
+    /// Metrics are returned per lifetime, in key order.
+    /// Entries that can't be decoded are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database exists but can't be opened.
+    pub fn metrics(&self) -> Result<Vec<StoredMetric>> {
+        let db_path = self.path.join("db");
+        if !db_path.join("data.safe.bin").exists() {
+            return Ok(vec![]);
+        }
+
+        let rkv = Rkv::new::<rkv::backend::SafeMode>(&db_path)?;
+        // Stores can't be opened while a read transaction is active.
+        // A store that was never written to doesn't exist.
+        let stores: Vec<_> = [Lifetime::Ping, Lifetime::Application, Lifetime::User]
+            .into_iter()
+            .filter_map(|lifetime| {
+                let store = rkv
+                    .open_single(lifetime.as_str(), StoreOptions::default())
+                    .ok()?;
+                Some((lifetime, store))
+            })
+            .collect();
+
+        let reader = rkv.read()?;
+        let mut metrics = vec![];
+        for (lifetime, store) in stores {
+            let Ok(mut iter) = store.iter_start(&reader) else {
+                continue;
+            };
+
+            while let Some(Ok((key, value))) = iter.next() {
+                let Ok(key) = str::from_utf8(key) else {
+                    continue;
+                };
+                let Some((storage_name, metric_id)) = key.split_once('#') else {
+                    continue;
+                };
+                let rkv::Value::Blob(blob) = value else {
+                    continue;
+                };
+                let Ok(value) = bincode::deserialize(blob) else {
+                    continue;
+                };
+                metrics.push(StoredMetric {
+                    lifetime,
+                    storage_name: storage_name.into(),
+                    metric_id: metric_id.into(),
+                    value,
+                });
+            }
+        }
+
+        Ok(metrics)
+    }
+
+    /// Enumerates every event store on disk, sorted by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an event store file can't be read.
+    pub fn event_stores(&self) -> Result<Vec<EventStore>> {
+        let events_path = self.path.join("events");
+        if !events_path.is_dir() {
+            return Ok(vec![]);
+        }
+
+        let mut stores = vec![];
+        for entry in fs::read_dir(&events_path)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_file() {
+                continue;
+            }
+            // Leftovers of an interrupted rewrite are ignored on load as well.
+            let Some(name) = entry.file_name().to_str().map(String::from) else {
+                continue;
+            };
+            if Path::new(&name).extension().is_some() {
+                continue;
+            }
+            let events = EventDatabase::read_store_file(&entry.path())?;
+            stores.push(EventStore { name, events });
+        }
+        stores.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(stores)
+    }
+
+    /// Enumerates every ping waiting to be uploaded, oldest first per directory.
+    ///
+    /// Deletion-request pings are listed after the regular pending pings.
+    /// Files that are not valid ping files are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a pings directory exists but can't be read.
+    pub fn pending_pings(&self) -> Result<Vec<PendingPing>> {
+        let mut pings = vec![];
+        for directory in [PENDING_PINGS_DIRECTORY, DELETION_REQUEST_PINGS_DIRECTORY] {
+            let dir = self.path.join(directory);
+            if !dir.is_dir() {
+                continue;
+            }
+
+            let mut files = vec![];
+            for entry in fs::read_dir(&dir)? {
+                let entry = entry?;
+                let metadata = entry.metadata()?;
+                if !metadata.is_file() {
+                    continue;
+                }
+                let Some(document_id) = entry.file_name().to_str().map(String::from) else {
+                    continue;
+                };
+                files.push((metadata.modified().ok(), document_id, entry.path()));
+            }
+            files.sort();
+
+            for (_, document_id, path) in files {
+                let Ok(file) = File::open(&path) else {
+                    continue;
+                };
+                if let Some(payload) = parse_ping_file(&document_id, file) {
+                    pings.push(PendingPing {
+                        directory: directory.into(),
+                        payload,
+                    });
+                }
+            }
+        }
+
+        Ok(pings)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::metrics::{CounterMetric, EventMetric, StringMetric};
+    use crate::tests::new_glean;
+    use crate::CommonMetricData;
+    use std::collections::HashMap;
+
+    #[test]
+    fn inspects_metrics_events_and_pings() {
+        let (glean, dir) = new_glean(None);
+
+        let counter = CounterMetric::new(CommonMetricData {
+            name: "counter".into(),
+            category: "inspect".into(),
+            send_in_pings: vec!["store1".into()],
+            ..Default::default()
+        });
+        counter.add_sync(&glean, 3);
+        let string = StringMetric::new(CommonMetricData {
+            name: "string".into(),
+            category: "inspect".into(),
+            send_in_pings: vec!["store1".into()],
+            lifetime: Lifetime::User,
+            ..Default::default()
+        });
+        string.set_sync(&glean, "value");
+        let event = EventMetric::new(
+            CommonMetricData {
+                name: "event".into(),
+                category: "inspect".into(),
+                send_in_pings: vec!["store1".into()],
+                ..Default::default()
+            },
+            vec![],
+        );
+        event.record_sync(&glean, 1, HashMap::new(), 0);
+
+        let data = open_readonly(dir.path()).unwrap();
+
+        let metrics = data.metrics().unwrap();
+        let counter = metrics
+            .iter()
+            .find(|m| m.metric_id == "inspect.counter")
+            .unwrap();
+        assert_eq!(Lifetime::Ping, counter.lifetime);
+        assert_eq!("store1", counter.storage_name);
+        assert_eq!(Metric::Counter(3), counter.value);
+        let string = metrics
+            .iter()
+            .find(|m| m.metric_id == "inspect.string")
+            .unwrap();
+        assert_eq!(Lifetime::User, string.lifetime);
+        assert_eq!(Metric::String("value".into()), string.value);
+
+        let stores = data.event_stores().unwrap();
+        let store1 = stores.iter().find(|s| s.name == "store1").unwrap();
+        assert_eq!(1, store1.events.len());
+        assert_eq!("event", store1.events[0].name);
+
+        assert!(glean.submit_ping_by_name("store1", None));
+
+        let pings = data.pending_pings().unwrap();
+        assert_eq!(1, pings.len());
+        assert_eq!(PENDING_PINGS_DIRECTORY, pings[0].directory);
+        assert_eq!("store1", pings[0].payload.ping_name);
+
+        // Inspecting leaves everything in place for the uploader.
+        let pings = data.pending_pings().unwrap();
+        assert_eq!(1, pings.len());
+    }
+
+    #[test]
+    fn ignores_invalid_files_without_removing_them() {
+        let (_glean, dir) = new_glean(None);
+        let pending = dir.path().join(PENDING_PINGS_DIRECTORY);
+        fs::create_dir_all(&pending).unwrap();
+        let invalid = pending.join("not-a-ping");
+        fs::write(&invalid, "garbage").unwrap();
+
+        let data = open_readonly(dir.path()).unwrap();
+        assert!(data.pending_pings().unwrap().is_empty());
+        assert!(invalid.exists());
+    }
+
+    #[test]
+    fn missing_directory_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(open_readonly(dir.path().join("missing")).is_err());
+    }
+}
@@ -365,6 +365,9 @@ pub struct DatabaseMetrics {
 
     /// The time it takes for a write-commit for the Glean database.
     pub write_time: TimingDistributionMetric,
+
+    /// The number of labeled entries evicted to stay within the database size budget.
+    pub inactive_metrics_evicted: CounterMetric,
 }
 
 impl DatabaseMetrics {
@@ -400,6 +403,14 @@ impl DatabaseMetrics {
                 },
                 TimeUnit::Microsecond,
             ),
+
+            inactive_metrics_evicted: CounterMetric::new(CommonMetricData {
+                name: "inactive_metrics_evicted".into(),
+                category: "glean.database".into(),
+                send_in_pings: vec!["metrics".into(), "health".into()],
+                lifetime: Lifetime::Ping,
+                ..Default::default()
+            }),
         }
     }
 }
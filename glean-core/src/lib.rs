@@ -36,6 +36,7 @@ mod common_metric_data;
 mod core;
 mod core_metrics;
 mod database;
+mod ddsketch;
 mod debug;
 #[cfg(feature = "benchmark")]
 #[doc(hidden)]
@@ -47,10 +48,15 @@ mod error_recording;
 mod event_database;
 mod glean_metrics;
 mod histogram;
+mod hyperloglog;
+pub mod inspect;
 mod internal_metrics;
 mod internal_pings;
 pub mod metrics;
 pub mod ping;
+mod ping_scheduler;
+mod privacy;
+mod pseudonymous_id;
 mod scheduler;
 pub(crate) mod session;
 pub mod storage;
@@ -73,19 +79,25 @@ pub use crate::error_recording::{test_get_num_recorded_errors, ErrorType};
 pub use crate::histogram::HistogramType;
 use crate::internal_metrics::DataDirectoryInfoObject;
 pub use crate::metrics::labeled::{
-    AllowLabeled, LabeledBoolean, LabeledCounter, LabeledCustomDistribution,
-    LabeledMemoryDistribution, LabeledMetric, LabeledMetricData, LabeledQuantity, LabeledString,
+    AllowLabeled, LabeledBoolean, LabeledCounter, LabeledCounter64, LabeledCustomDistribution,
+    LabeledDatetime, LabeledGauge, LabeledMemoryDistribution, LabeledMetric, LabeledMetricData,
+    LabeledQuantity, LabeledRate, LabeledString, LabeledText, LabeledTimespan,
     LabeledTimingDistribution,
 };
 pub use crate::metrics::{
-    BooleanMetric, CounterMetric, CustomDistributionMetric, Datetime, DatetimeMetric,
-    DenominatorMetric, DistributionData, DualLabeledCounterMetric, EventMetric,
-    LocalCustomDistribution, LocalMemoryDistribution, LocalTimingDistribution,
-    MemoryDistributionMetric, MemoryUnit, NumeratorMetric, ObjectMetric, PingType, QuantityMetric,
-    Rate, RateMetric, RecordedEvent, RecordedExperiment, StringListMetric, StringMetric,
-    TestGetValue, TextMetric, TimeUnit, TimerId, TimespanMetric, TimingDistributionMetric,
-    UrlMetric, UuidMetric,
+    BooleanMetric, Counter64Metric, CounterMetric, CustomDistributionMetric, Datetime,
+    DatetimeMetric, DenominatorMetric, DistinctCountMetric, DistributionData,
+    DualLabeledBooleanMetric, DualLabeledCounter64Metric, DualLabeledCounterMetric,
+    DualLabeledCustomDistributionMetric, DualLabeledQuantityMetric,
+    DualLabeledTimingDistributionMetric, EnumStringMetric, EventMetric, ExtraKeyType, GaugeData,
+    GaugeMetric, LocalCustomDistribution, LocalMemoryDistribution, LocalTimingDistribution,
+    MemoryDistributionMetric, MemoryUnit, NumeratorMetric, ObjectMetric, PingOptions,
+    PingTimeSchedule, PingType, PseudonymousIdRotation, QuantileDistributionMetric, QuantityMetric,
+    Rate, RateMetric, RecordedEvent, RecordedExperiment, SpanId, SpanMetric, StringListMetric,
+    StringMetric, TestGetValue, TextMetric, TimeUnit, TimerId, TimespanMetric,
+    TimingDistributionMetric, UrlMetric, UrlReduction, UrlSanitization, UuidMetric,
 };
+pub use crate::ping::PingSnapshotFilter;
 pub use crate::session::{SessionManager, SessionMetadata, SessionMode};
 pub use crate::upload::{PingRequest, PingUploadTask, UploadResult, UploadTaskAction};
 
@@ -174,6 +186,12 @@ pub struct InternalConfiguration {
     pub max_pending_pings_count: Option<u64>,
     /// Maximum size in bytes of the pending pings directory. Overrides the default when set.
     pub max_pending_pings_directory_size: Option<u64>,
+    /// Maximum size in bytes of the database on disk.
+    /// If exceeded, the least recently written user-lifetime labeled entries are evicted.
+    /// Default: no limit.
+    pub database_size_budget: Option<u64>,
+    /// Caps applied to every event store. Default: no caps besides `max_events`.
+    pub event_store_limits: Option<EventStoreLimits>,
     /// Session management mode. Default: `Auto`.
     pub session_mode: session::SessionMode,
     /// The fraction of sessions to sample (0.0–1.0). Default: `1.0` (all sessions).
@@ -192,6 +210,21 @@ pub struct PingRateLimit {
     pub pings_per_interval: u32,
 }
 
+/// Caps on the number and size of events queued per event store.
+///
+/// Unlike `max_events`, which only applies to the "events" ping,
+/// these apply to the store of every ping.
+#[derive(Debug, Clone, Default, MallocSizeOf)]
+pub struct EventStoreLimits {
+    /// Maximum number of events in a single store.
+    pub max_events: Option<u32>,
+    /// Maximum size in bytes of a single store on disk.
+    pub max_bytes: Option<u64>,
+    /// Whether to drop the oldest events when a store exceeds a cap,
+    /// instead of submitting the store's ping with reason `max_capacity`.
+    pub drop_oldest: bool,
+}
+
 /// Launches a new task on the global dispatch queue with a reference to the Glean singleton.
 fn launch_with_glean(callback: impl FnOnce(&Glean) + Send + 'static) {
     dispatcher::launch(|| core::with_glean(callback));
@@ -547,6 +580,7 @@ fn initialize_inner(
         core::with_glean(|glean| {
             // Start the MPS if its handled within Rust.
             glean.start_metrics_ping_scheduler();
+            glean.start_ping_scheduler();
         });
 
         // The metrics ping scheduler might _synchronously_ submit a ping
@@ -747,6 +781,7 @@ pub fn shutdown() {
     // Case 3: "After init completed"
     crate::launch_with_glean_mut(|glean| {
         glean.cancel_metrics_ping_scheduler();
+        glean.cancel_ping_scheduler();
         glean.set_dirty_flag(false);
     });
 
@@ -927,6 +962,7 @@ pub fn glean_set_upload_enabled(enabled: bool) {
         if !enabled {
             // Stop the MPS if its handled within Rust.
             glean.cancel_metrics_ping_scheduler();
+            glean.cancel_ping_scheduler();
             // Stop wrapper-controlled uploader.
             if let Err(e) = state.callbacks.cancel_uploads() {
                 log::error!("Canceling upload failed. Error: {}", e);
@@ -937,6 +973,7 @@ pub fn glean_set_upload_enabled(enabled: bool) {
 
         if !original_enabled && enabled {
             initialize_core_metrics(glean, &state.client_info);
+            glean.start_ping_scheduler();
         }
 
         if original_enabled && !enabled {
@@ -1007,6 +1044,45 @@ pub fn glean_get_registered_ping_names() -> Vec<String> {
     })
 }
 
+/// Exports all user-lifetime metrics into a versioned bundle for device migration.
+///
+/// See [`core::Glean::export_user_data`].
+///
+/// # Returns
+///
+/// The JSON-encoded bundle or `None` if exporting failed.
+pub fn glean_export_user_data(include_client_id: bool) -> Option<String> {
+    block_on_dispatcher();
+    core::with_glean(|glean| match glean.export_user_data(include_client_id) {
+        Ok(bundle) => Some(bundle),
+        Err(e) => {
+            log::warn!("Failed to export user data. Error: {}", e);
+            None
+        }
+    })
+}
+
+/// Imports a user data bundle created by [`glean_export_user_data`] on another device.
+///
+/// See [`core::Glean::import_user_data`].
+///
+/// # Returns
+///
+/// Whether the bundle was imported.
+pub fn glean_import_user_data(bundle: String) -> bool {
+    block_on_dispatcher();
+    core::with_glean(|glean| match glean.import_user_data(&bundle) {
+        Ok(imported) => {
+            log::info!("Imported {} metrics from user data bundle.", imported);
+            true
+        }
+        Err(e) => {
+            log::warn!("Failed to import user data. Error: {}", e);
+            false
+        }
+    })
+}
+
 /// Indicate that an experiment is running.  Glean will then add an
 /// experiment annotation to the environment which is sent with pings. This
 /// infomration is not persisted between runs.
@@ -1280,6 +1356,21 @@ pub fn glean_submit_ping_by_name_sync(ping_name: String, reason: Option<String>)
         .unwrap_or(false)
 }
 
+/// Collects the payload of a ping (by its name) without submitting it.
+///
+/// No stores are cleared and no upload is queued.
+///
+/// Returns the pretty-printed JSON payload,
+/// or `None` if the ping is unknown or would not be sent because it is empty.
+pub fn glean_preview_ping(ping_name: String) -> Option<String> {
+    block_on_dispatcher();
+    core::with_glean(|glean| {
+        glean
+            .preview_ping(&ping_name)
+            .map(|payload| serde_json::to_string_pretty(&payload).unwrap())
+    })
+}
+
 /// EXPERIMENTAL: Register a listener object to recieve notifications of event recordings.
 ///
 /// # Arguments
@@ -1437,6 +1528,27 @@ pub fn glean_test_get_distribution() -> DistributionMetrics {
     core::with_glean(|glean| glean.test_get_distribution())
 }
 
+/// Gets the mean of all samples in a distribution.
+///
+/// See [`DistributionData::mean`].
+pub fn glean_distribution_data_mean(data: DistributionData) -> Option<f64> {
+    data.mean()
+}
+
+/// Estimates a percentile of a distribution.
+///
+/// See [`DistributionData::percentile`].
+pub fn glean_distribution_data_percentile(data: DistributionData, percentile: f64) -> Option<i64> {
+    data.percentile(percentile)
+}
+
+/// Renders a distribution as an ASCII histogram.
+///
+/// See [`DistributionData::ascii_histogram`].
+pub fn glean_distribution_data_ascii_histogram(data: DistributionData, width: u32) -> String {
+    data.ascii_histogram(width)
+}
+
 #[cfg(all(not(target_os = "android"), not(target_os = "ios")))]
 static FD_LOGGER: OnceCell<fd_logger::FdLogger> = OnceCell::new();
 
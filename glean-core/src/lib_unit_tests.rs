@@ -234,6 +234,8 @@ fn experimentation_id_is_set_correctly() {
         ping_lifetime_max_time: 0,
         max_pending_pings_count: None,
         max_pending_pings_directory_size: None,
+        database_size_budget: None,
+        event_store_limits: None,
         session_mode: crate::session::SessionMode::Auto,
         session_sample_rate: 1.0,
         session_inactivity_timeout_ms: 1_800_000,
@@ -526,6 +528,15 @@ fn correct_order() {
         Rate(0, 0),
         Text(long_string),
         Object("{}".into()),
+        DistinctCount(hyperloglog::HyperLogLog::new(hyperloglog::DEFAULT_PRECISION)),
+        QuantileDistribution(ddsketch::DDSketch::new(0.01)),
+        PrivatizedBoolean(true, 1.0),
+        PrivatizedCounter(1, 1.0),
+        Gauge(metrics::GaugeData::new(1)),
+        Counter64(0),
+        EnumString(0),
+        BoundedTimingDistribution(Histogram::functional(2.0, 8.0), 1),
+        BoundedMemoryDistribution(Histogram::functional(2.0, 8.0), 1),
     ];
 
     for metric in all_metrics {
@@ -555,6 +566,15 @@ fn correct_order() {
             Url(..)                           => assert_eq!(15, disc),
             Text(..)                          => assert_eq!(16, disc),
             Object(..)                        => assert_eq!(17, disc),
+            DistinctCount(..)                 => assert_eq!(18, disc),
+            QuantileDistribution(..)          => assert_eq!(19, disc),
+            PrivatizedBoolean(..)             => assert_eq!(20, disc),
+            PrivatizedCounter(..)             => assert_eq!(21, disc),
+            Gauge(..)                         => assert_eq!(22, disc),
+            Counter64(..)                     => assert_eq!(23, disc),
+            EnumString(..)                    => assert_eq!(24, disc),
+            BoundedTimingDistribution(..)     => assert_eq!(25, disc),
+            BoundedMemoryDistribution(..)     => assert_eq!(26, disc),
         }
     }
 }
@@ -5,9 +5,10 @@
 use std::sync::Arc;
 
 use crate::common_metric_data::{CommonMetricDataInternal, DynamicLabelType};
-use crate::error_recording::{test_get_num_recorded_errors, ErrorType};
+use crate::error_recording::{record_error, test_get_num_recorded_errors, ErrorType};
 use crate::metrics::MetricType;
 use crate::metrics::{Metric, TestGetValue};
+use crate::privacy;
 use crate::storage::StorageManager;
 use crate::CommonMetricData;
 use crate::Glean;
@@ -15,6 +16,9 @@ use crate::Glean;
 /// A boolean metric.
 ///
 /// Records a simple flag.
+///
+/// If the metric has an `ldp_epsilon`, the flag is recorded with randomized response:
+/// it is flipped with probability `1 / (1 + e^ε)` before it is stored.
 #[derive(Clone, Debug)]
 pub struct BooleanMetric {
     meta: Arc<CommonMetricDataInternal>,
@@ -66,7 +70,23 @@ impl BooleanMetric {
             return;
         }
 
-        let value = Metric::Boolean(value);
+        let value = match self.meta.inner.ldp_epsilon {
+            None => Metric::Boolean(value),
+            Some(epsilon) if privacy::is_valid_epsilon(epsilon) => {
+                Metric::PrivatizedBoolean(privacy::randomized_response(value, epsilon), epsilon)
+            }
+            Some(epsilon) => {
+                // Never store the true value of a metric meant to be privatized.
+                record_error(
+                    glean,
+                    &self.meta,
+                    ErrorType::InvalidValue,
+                    format!("Invalid local differential privacy epsilon {}", epsilon),
+                    None,
+                );
+                return;
+            }
+        };
         glean.storage().record(glean, &self.meta, &value)
     }
 
@@ -95,7 +115,7 @@ impl BooleanMetric {
             &self.meta.identifier(glean),
             self.meta.inner.lifetime,
         ) {
-            Some(Metric::Boolean(b)) => Some(b),
+            Some(Metric::Boolean(b)) | Some(Metric::PrivatizedBoolean(b, _)) => Some(b),
             _ => None,
         }
     }
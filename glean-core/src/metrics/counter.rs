@@ -9,6 +9,7 @@ use crate::common_metric_data::{CommonMetricDataInternal, DynamicLabelType};
 use crate::error_recording::{record_error, test_get_num_recorded_errors, ErrorType};
 use crate::metrics::Metric;
 use crate::metrics::MetricType;
+use crate::privacy;
 use crate::storage::StorageManager;
 use crate::Glean;
 use crate::{CommonMetricData, TestGetValue};
@@ -17,6 +18,10 @@ use crate::{CommonMetricData, TestGetValue};
 ///
 /// Used to count things.
 /// The value can only be incremented, not decremented.
+///
+/// If the metric has an `ldp_epsilon`, two-sided geometric noise is added to every increment
+/// before it is stored. The noise has a mean of 0, but the stored value can be negative.
+/// Increments are clamped to the metric's `ldp_max_increment`, which the noise is scaled to.
 #[derive(Clone, Debug)]
 pub struct CounterMetric {
     meta: Arc<CommonMetricDataInternal>,
@@ -57,6 +62,11 @@ impl CounterMetric {
     }
 
     /// Increases the counter by `amount` synchronously.
+    ///
+    /// With local differential privacy, `amount` is clamped to `ldp_max_increment`
+    /// and noise calibrated to that bound is added to every increment.
+    /// Each increment is `ε`-differentially private on its own, but the privacy cost composes:
+    /// a counter incremented `n` times is `nε`-differentially private, which is what it reports.
     #[doc(hidden)]
     pub fn add_sync(&self, glean: &Glean, amount: i32) {
         if !self.should_record(glean) {
@@ -81,17 +91,48 @@ impl CounterMetric {
             Ordering::Greater => (),
         };
 
+        let max_increment = self.meta.inner.ldp_max_increment.unwrap_or(1).max(1);
+        let epsilon = match self.meta.inner.ldp_epsilon {
+            None => None,
+            Some(epsilon) if privacy::is_valid_epsilon(epsilon) => Some(epsilon),
+            Some(epsilon) => {
+                // Never store the true value of a metric meant to be privatized.
+                record_error(
+                    glean,
+                    &self.meta,
+                    ErrorType::InvalidValue,
+                    format!("Invalid local differential privacy epsilon {}", epsilon),
+                    None,
+                );
+                return;
+            }
+        };
+
         // Let's be defensive here:
         // The uploader tries to store a counter metric,
         // but in tests that storage might be gone already.
         // Let's just ignore those.
         // This should never happen in real app usage.
         if let Some(storage) = glean.storage_opt() {
-            storage.record_with(glean, &self.meta, |old_value| match old_value {
-                Some(Metric::Counter(old_value)) => {
+            storage.record_with(glean, &self.meta, |old_value| match (old_value, epsilon) {
+                (Some(Metric::Counter(old_value)), None) => {
                     Metric::Counter(old_value.saturating_add(amount))
                 }
-                _ => Metric::Counter(amount),
+                (_, None) => Metric::Counter(amount),
+                (old_value, Some(epsilon)) => {
+                    let (old_value, spent) = match old_value {
+                        Some(Metric::PrivatizedCounter(v, spent)) => (v, spent),
+                        Some(Metric::Counter(v)) => (v, 0.0),
+                        _ => (0, 0.0),
+                    };
+                    // Calibrated to the bound rather than to the increment,
+                    // so the noise doesn't depend on the recorded value.
+                    // No error is recorded for clamped increments, as that would reveal them.
+                    let clamped = amount.min(i32::try_from(max_increment).unwrap_or(i32::MAX));
+                    let noisy =
+                        clamped.saturating_add(privacy::geometric_noise(epsilon, max_increment));
+                    Metric::PrivatizedCounter(old_value.saturating_add(noisy), spent + epsilon)
+                }
             })
         } else {
             log::warn!(
@@ -132,7 +173,7 @@ impl CounterMetric {
             &self.meta.identifier(glean),
             self.meta.inner.lifetime,
         ) {
-            Some(Metric::Counter(i)) => Some(i),
+            Some(Metric::Counter(i)) | Some(Metric::PrivatizedCounter(i, _)) => Some(i),
             _ => None,
         }
     }
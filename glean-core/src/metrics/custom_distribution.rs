@@ -35,6 +35,14 @@ pub(crate) fn snapshot<B: Bucketing>(hist: &Histogram<B>) -> DistributionData {
             .collect(),
         sum: hist.sum() as i64,
         count: hist.count() as i64,
+        buckets_per_magnitude: None,
+        upper_bounds: hist
+            .bucket_upper_bounds()
+            .into_iter()
+            .map(|(k, v)| (k as i64, v as i64))
+            .collect(),
+        max_sample_time: None,
+        max_bytes: None,
     }
 }
 
@@ -250,6 +258,24 @@ impl CustomDistributionMetric {
         })
     }
 
+    /// **Exported for test purposes.**
+    ///
+    /// Estimates a percentile of the currently stored distribution.
+    /// See [`DistributionData::percentile`].
+    ///
+    /// # Arguments
+    ///
+    /// * `percentile` - The percentile to estimate, between `0` and `100`.
+    /// * `ping_name` - the optional name of the ping to retrieve the metric
+    ///                 for. Defaults to the first value in `send_in_pings`.
+    ///
+    /// # Returns
+    ///
+    /// The estimate or `None` if nothing is stored.
+    pub fn test_get_percentile(&self, percentile: f64, ping_name: Option<String>) -> Option<i64> {
+        self.test_get_value(ping_name)?.percentile(percentile)
+    }
+
     /// **Experimental:** Start a new histogram buffer associated with this custom distribution metric.
     ///
     /// A histogram buffer accumulates in-memory.
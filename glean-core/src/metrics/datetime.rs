@@ -5,7 +5,7 @@
 use std::fmt;
 use std::sync::Arc;
 
-use crate::common_metric_data::CommonMetricDataInternal;
+use crate::common_metric_data::{CommonMetricDataInternal, DynamicLabelType};
 use crate::error_recording::{record_error, test_get_num_recorded_errors, ErrorType};
 use crate::metrics::time_unit::TimeUnit;
 use crate::metrics::Metric;
@@ -93,6 +93,24 @@ impl MetricType for DatetimeMetric {
     fn meta(&self) -> &CommonMetricDataInternal {
         &self.meta
     }
+
+    fn with_name(&self, name: String) -> Self {
+        let mut meta = (*self.meta).clone();
+        meta.inner.name = name;
+        Self {
+            meta: Arc::new(meta),
+            time_unit: self.time_unit,
+        }
+    }
+
+    fn with_dynamic_label(&self, label: DynamicLabelType) -> Self {
+        let mut meta = (*self.meta).clone();
+        meta.inner.dynamic_label = Some(label);
+        Self {
+            meta: Arc::new(meta),
+            time_unit: self.time_unit,
+        }
+    }
 }
 
 impl From<ChronoDatetime> for Datetime {
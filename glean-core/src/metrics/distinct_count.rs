@@ -0,0 +1,191 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::sync::Arc;
+
+use crate::common_metric_data::{CommonMetricDataInternal, DynamicLabelType};
+use crate::error_recording::{record_error, test_get_num_recorded_errors, ErrorType};
+use crate::hyperloglog::{HyperLogLog, DEFAULT_PRECISION};
+use crate::metrics::Metric;
+use crate::metrics::MetricType;
+use crate::storage::StorageManager;
+use crate::Glean;
+use crate::{CommonMetricData, TestGetValue};
+
+/// A distinct-count metric.
+///
+/// Estimates the number of distinct values it was given, without storing the values.
+/// The values are added to a [HyperLogLog](https://en.wikipedia.org/wiki/HyperLogLog) sketch,
+/// which is reported in the ping so that it can be merged across pings and clients.
+#[derive(Clone, Debug)]
+pub struct DistinctCountMetric {
+    meta: Arc<CommonMetricDataInternal>,
+}
+
+impl MetricType for DistinctCountMetric {
+    fn meta(&self) -> &CommonMetricDataInternal {
+        &self.meta
+    }
+
+    fn with_name(&self, name: String) -> Self {
+        let mut meta = (*self.meta).clone();
+        meta.inner.name = name;
+        Self {
+            meta: Arc::new(meta),
+        }
+    }
+
+    fn with_dynamic_label(&self, label: DynamicLabelType) -> Self {
+        let mut meta = (*self.meta).clone();
+        meta.inner.dynamic_label = Some(label);
+        Self {
+            meta: Arc::new(meta),
+        }
+    }
+}
+
+// IMPORTANT:
+//
+// When changing this implementation, make sure all the operations are
+// also declared in the related trait in `../traits/`.
+impl DistinctCountMetric {
+    /// Creates a new distinct-count metric.
+    pub fn new(meta: CommonMetricData) -> Self {
+        Self {
+            meta: Arc::new(meta.into()),
+        }
+    }
+
+    /// Adds a value to the set of distinct values seen.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value. Must not be empty.
+    ///
+    /// ## Notes
+    ///
+    /// Logs an error if the `value` is empty.
+    pub fn add(&self, value: String) {
+        let metric = self.clone();
+        crate::launch_with_glean(move |glean| metric.add_sync(glean, &value))
+    }
+
+    /// Adds a value to the set of distinct values seen synchronously.
+    #[doc(hidden)]
+    pub fn add_sync(&self, glean: &Glean, value: &str) {
+        if !self.should_record(glean) {
+            return;
+        }
+
+        if value.is_empty() {
+            record_error(
+                glean,
+                &self.meta,
+                ErrorType::InvalidValue,
+                "Added an empty value",
+                None,
+            );
+            return;
+        }
+
+        glean
+            .storage()
+            .record_with(glean, &self.meta, |old_value| match old_value {
+                Some(Metric::DistinctCount(mut hll)) => {
+                    hll.insert(value);
+                    Metric::DistinctCount(hll)
+                }
+                _ => {
+                    let mut hll = HyperLogLog::new(DEFAULT_PRECISION);
+                    hll.insert(value);
+                    Metric::DistinctCount(hll)
+                }
+            });
+    }
+
+    /// Gets the currently stored sketch.
+    #[doc(hidden)]
+    pub fn get_value<'a, S: Into<Option<&'a str>>>(
+        &self,
+        glean: &Glean,
+        ping_name: S,
+    ) -> Option<HyperLogLog> {
+        let queried_ping_name = ping_name
+            .into()
+            .unwrap_or_else(|| &self.meta().inner.send_in_pings[0]);
+
+        match StorageManager.snapshot_metric(
+            glean.storage(),
+            queried_ping_name,
+            &self.meta.identifier(glean),
+            self.meta.inner.lifetime,
+        ) {
+            Some(Metric::DistinctCount(hll)) => Some(hll),
+            _ => None,
+        }
+    }
+
+    /// **Test-only API (exported for FFI purposes).**
+    ///
+    /// Gets the currently estimated number of distinct values.
+    ///
+    /// This doesn't clear the stored value.
+    ///
+    /// # Arguments
+    ///
+    /// * `ping_name` - the optional name of the ping to retrieve the metric
+    ///                 for. Defaults to the first value in `send_in_pings`.
+    ///
+    /// # Returns
+    ///
+    /// The estimate or `None` if nothing stored.
+    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<i64> {
+        crate::block_on_dispatcher();
+        crate::core::with_glean(|glean| {
+            self.get_value(glean, ping_name.as_deref())
+                .map(|hll| hll.estimate())
+        })
+    }
+
+    /// **Exported for test purposes.**
+    ///
+    /// Gets the number of recorded errors for the given metric and error type.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - The type of error
+    ///
+    /// # Returns
+    ///
+    /// The number of errors reported.
+    pub fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32 {
+        crate::block_on_dispatcher();
+
+        crate::core::with_glean(|glean| {
+            test_get_num_recorded_errors(glean, self.meta(), error).unwrap_or(0)
+        })
+    }
+}
+
+impl TestGetValue for DistinctCountMetric {
+    type Output = i64;
+
+    /// **Test-only API (exported for FFI purposes).**
+    ///
+    /// Gets the currently estimated number of distinct values.
+    ///
+    /// This doesn't clear the stored value.
+    ///
+    /// # Arguments
+    ///
+    /// * `ping_name` - the optional name of the ping to retrieve the metric
+    ///                 for. Defaults to the first value in `send_in_pings`.
+    ///
+    /// # Returns
+    ///
+    /// The estimate or `None` if nothing stored.
+    fn test_get_value(&self, ping_name: Option<String>) -> Option<i64> {
+        DistinctCountMetric::test_get_value(self, ping_name)
+    }
+}
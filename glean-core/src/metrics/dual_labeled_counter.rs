@@ -10,7 +10,11 @@ use std::sync::{Arc, Mutex};
 
 use crate::common_metric_data::{CommonMetricData, CommonMetricDataInternal, DynamicLabelType};
 use crate::error_recording::{record_error, test_get_num_recorded_errors, ErrorType};
-use crate::metrics::{CounterMetric, Metric, MetricType};
+use crate::histogram::HistogramType;
+use crate::metrics::{
+    BooleanMetric, Counter64Metric, CounterMetric, CustomDistributionMetric, Metric, MetricType,
+    QuantityMetric, TimeUnit, TimingDistributionMetric,
+};
 use crate::{Glean, TestGetValue};
 
 const MAX_LABELS: usize = 16;
@@ -18,31 +22,49 @@ const OTHER_LABEL: &str = "__other__";
 const MAX_LABEL_LENGTH: usize = 111;
 pub(crate) const RECORD_SEPARATOR: char = '\x1E';
 
+/// A dual labeled counter.
+pub type DualLabeledCounterMetric = DualLabeledMetric<CounterMetric>;
+
+/// A dual labeled 64-bit counter.
+pub type DualLabeledCounter64Metric = DualLabeledMetric<Counter64Metric>;
+
+/// A dual labeled quantity.
+pub type DualLabeledQuantityMetric = DualLabeledMetric<QuantityMetric>;
+
+/// A dual labeled boolean.
+pub type DualLabeledBooleanMetric = DualLabeledMetric<BooleanMetric>;
+
+/// A dual labeled custom distribution.
+pub type DualLabeledCustomDistributionMetric = DualLabeledMetric<CustomDistributionMetric>;
+
+/// A dual labeled timing distribution.
+pub type DualLabeledTimingDistributionMetric = DualLabeledMetric<TimingDistributionMetric>;
+
 /// A dual labled metric
 ///
 /// Dual labled metrics allow recording multiple sub-metrics of the same type, in relation
 /// to two dimensions rather than the single label provided by the standard labeled type.
 #[derive(Debug)]
-pub struct DualLabeledCounterMetric {
+pub struct DualLabeledMetric<T> {
     keys: Option<Vec<Cow<'static, str>>>,
     categories: Option<Vec<Cow<'static, str>>>,
     /// Type of the underlying metric
     /// We hold on to an instance of it, which is cloned to create new modified instances.
-    counter: CounterMetric,
+    counter: T,
 
     /// A map from a unique ID for the dual labeled submetric to a handle of an instantiated
     /// metric type.
-    dual_label_map: Mutex<HashMap<(String, String), Arc<CounterMetric>>>,
+    dual_label_map: Mutex<HashMap<(String, String), Arc<T>>>,
 }
 
-impl ::malloc_size_of::MallocSizeOf for DualLabeledCounterMetric {
+impl<T: ::malloc_size_of::MallocSizeOf> ::malloc_size_of::MallocSizeOf for DualLabeledMetric<T> {
     fn size_of(&self, ops: &mut malloc_size_of::MallocSizeOfOps) -> usize {
         let mut n = 0;
         n += self.keys.size_of(ops);
         n += self.categories.size_of(ops);
         n += self.counter.size_of(ops);
 
-        // `MallocSizeOf` is not implemented for `Arc<CounterMetric>`,
+        // `MallocSizeOf` is not implemented for `Arc<T>`,
         // so we reimplement counting the size of the hashmap ourselves.
         let map = self.dual_label_map.lock().unwrap();
 
@@ -56,9 +78,8 @@ impl ::malloc_size_of::MallocSizeOf for DualLabeledCounterMetric {
         } else {
             map.capacity()
                 * (mem::size_of::<String>() // key
-                    + mem::size_of::<Arc<CounterMetric>>() // allocation for the `Arc` value
-                    + mem::size_of::<CounterMetric>() // allocation for the `CounterMetric` value
-                                                      // within the `Arc`
+                    + mem::size_of::<Arc<T>>() // allocation for the `Arc` value
+                    + mem::size_of::<T>() // allocation for the `T` value within the `Arc`
                     + mem::size_of::<usize>())
         };
 
@@ -73,13 +94,13 @@ impl ::malloc_size_of::MallocSizeOf for DualLabeledCounterMetric {
     }
 }
 
-impl MetricType for DualLabeledCounterMetric {
+impl<T: MetricType> MetricType for DualLabeledMetric<T> {
     fn meta(&self) -> &CommonMetricDataInternal {
         self.counter.meta()
     }
 }
 
-impl DualLabeledCounterMetric {
+impl DualLabeledMetric<CounterMetric> {
     /// Creates a new dual labeled counter from the given metric instance and optional list of labels.
     pub fn new(
         meta: CommonMetricData,
@@ -87,16 +108,90 @@ impl DualLabeledCounterMetric {
         catgories: Option<Vec<Cow<'static, str>>>,
     ) -> DualLabeledCounterMetric {
         let submetric = CounterMetric::new(meta);
-        DualLabeledCounterMetric::new_inner(submetric, keys, catgories)
+        DualLabeledMetric::new_inner(submetric, keys, catgories)
+    }
+}
+
+impl DualLabeledMetric<Counter64Metric> {
+    /// Creates a new dual labeled 64-bit counter from the given metric instance and optional list of labels.
+    pub fn new(
+        meta: CommonMetricData,
+        keys: Option<Vec<Cow<'static, str>>>,
+        catgories: Option<Vec<Cow<'static, str>>>,
+    ) -> DualLabeledCounter64Metric {
+        let submetric = Counter64Metric::new(meta);
+        DualLabeledMetric::new_inner(submetric, keys, catgories)
+    }
+}
+
+impl DualLabeledMetric<QuantityMetric> {
+    /// Creates a new dual labeled quantity from the given metric instance and optional list of labels.
+    pub fn new(
+        meta: CommonMetricData,
+        keys: Option<Vec<Cow<'static, str>>>,
+        categories: Option<Vec<Cow<'static, str>>>,
+    ) -> DualLabeledQuantityMetric {
+        let submetric = QuantityMetric::new(meta);
+        DualLabeledMetric::new_inner(submetric, keys, categories)
+    }
+}
+
+impl DualLabeledMetric<BooleanMetric> {
+    /// Creates a new dual labeled boolean from the given metric instance and optional list of labels.
+    pub fn new(
+        meta: CommonMetricData,
+        keys: Option<Vec<Cow<'static, str>>>,
+        categories: Option<Vec<Cow<'static, str>>>,
+    ) -> DualLabeledBooleanMetric {
+        let submetric = BooleanMetric::new(meta);
+        DualLabeledMetric::new_inner(submetric, keys, categories)
+    }
+}
+
+impl DualLabeledMetric<CustomDistributionMetric> {
+    /// Creates a new dual labeled custom distribution from the given metric instance and optional list of labels.
+    ///
+    /// All submetrics share the bucketing given by `range_min`, `range_max`, `bucket_count`
+    /// and `histogram_type`.
+    pub fn new(
+        meta: CommonMetricData,
+        range_min: i64,
+        range_max: i64,
+        bucket_count: i64,
+        histogram_type: HistogramType,
+        keys: Option<Vec<Cow<'static, str>>>,
+        categories: Option<Vec<Cow<'static, str>>>,
+    ) -> DualLabeledCustomDistributionMetric {
+        let submetric =
+            CustomDistributionMetric::new(meta, range_min, range_max, bucket_count, histogram_type);
+        DualLabeledMetric::new_inner(submetric, keys, categories)
     }
+}
 
+impl DualLabeledMetric<TimingDistributionMetric> {
+    /// Creates a new dual labeled timing distribution from the given metric instance and optional list of labels.
+    pub fn new(
+        meta: CommonMetricData,
+        time_unit: TimeUnit,
+        keys: Option<Vec<Cow<'static, str>>>,
+        categories: Option<Vec<Cow<'static, str>>>,
+    ) -> DualLabeledTimingDistributionMetric {
+        let submetric = TimingDistributionMetric::new(meta, time_unit);
+        DualLabeledMetric::new_inner(submetric, keys, categories)
+    }
+}
+
+impl<T> DualLabeledMetric<T>
+where
+    T: MetricType + Clone,
+{
     fn new_inner(
-        counter: CounterMetric,
+        counter: T,
         keys: Option<Vec<Cow<'static, str>>>,
         categories: Option<Vec<Cow<'static, str>>>,
-    ) -> DualLabeledCounterMetric {
+    ) -> DualLabeledMetric<T> {
         let dual_label_map = Default::default();
-        DualLabeledCounterMetric {
+        DualLabeledMetric {
             keys,
             categories,
             counter,
@@ -106,7 +201,7 @@ impl DualLabeledCounterMetric {
 
     /// Creates a new metric with a specific key and category, validating against
     /// the static or dynamic labels where needed.
-    fn new_counter_metric(&self, key: &str, category: &str) -> CounterMetric {
+    fn new_counter_metric(&self, key: &str, category: &str) -> T {
         match (&self.keys, &self.categories) {
             (None, None) => self
                 .counter
@@ -199,7 +294,7 @@ impl DualLabeledCounterMetric {
     ///
     /// Labels must have a maximum of 111 characters, and may comprise any printable ASCII characters.
     /// If an invalid label is used, the metric will be recorded in the special `OTHER_LABEL` label.
-    pub fn get<S: AsRef<str>>(&self, key: S, category: S) -> Arc<CounterMetric> {
+    pub fn get<S: AsRef<str>>(&self, key: S, category: S) -> Arc<T> {
         let key = key.as_ref();
         let category = category.as_ref();
 
@@ -231,14 +326,17 @@ impl DualLabeledCounterMetric {
     }
 }
 
-impl TestGetValue for DualLabeledCounterMetric {
-    type Output = HashMap<String, HashMap<String, i32>>;
+impl<T> TestGetValue for DualLabeledMetric<T>
+where
+    T: TestGetValue,
+{
+    type Output = HashMap<String, HashMap<String, T::Output>>;
 
     fn test_get_value(
         &self,
         ping_name: Option<String>,
-    ) -> Option<HashMap<String, HashMap<String, i32>>> {
-        let mut out: HashMap<String, HashMap<String, i32>> = HashMap::new();
+    ) -> Option<HashMap<String, HashMap<String, T::Output>>> {
+        let mut out: HashMap<String, HashMap<String, T::Output>> = HashMap::new();
         let map = self.dual_label_map.lock().unwrap();
         for ((key, category), metric) in map.iter() {
             if let Some(value) = metric.test_get_value(ping_name.clone()) {
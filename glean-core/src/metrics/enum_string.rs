@@ -0,0 +1,234 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use once_cell::sync::Lazy;
+
+use crate::common_metric_data::{CommonMetricDataInternal, DynamicLabelType};
+use crate::error_recording::{record_error, test_get_num_recorded_errors, ErrorType};
+use crate::metrics::Metric;
+use crate::metrics::MetricType;
+use crate::storage::StorageManager;
+use crate::Glean;
+use crate::{CommonMetricData, TestGetValue};
+
+const OTHER_VALUE: &str = "__other__";
+
+/// The stored index of values not in the list of allowed values.
+const OTHER_INDEX: u32 = 0;
+
+/// The values an enumerated string metric accepts, in the order they were declared.
+type AllowedValues = Arc<[Cow<'static, str>]>;
+
+/// The allowed values of the enumerated string metrics created in this process,
+/// by their base identifier.
+static ALLOWED_VALUES: Lazy<RwLock<HashMap<String, AllowedValues>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Gets the value of an enumerated string metric for a stored `index`.
+///
+/// Values of metrics that weren't created in this process can't be mapped back,
+/// and are reported as [`OTHER_VALUE`].
+pub(crate) fn value_for(base_identifier: &str, index: u32) -> String {
+    let values = ALLOWED_VALUES.read().unwrap(); // safe unwrap, only error case is poisoning
+    match values.get(base_identifier) {
+        Some(allowed_values) => value_of(allowed_values, index).to_string(),
+        None => {
+            log::warn!("Unknown enum string metric '{}'", base_identifier);
+            OTHER_VALUE.to_string()
+        }
+    }
+}
+
+/// Gets the value for a stored `index` from a list of allowed values.
+fn value_of<'a>(allowed_values: &'a [Cow<'static, str>], index: u32) -> &'a str {
+    match index.checked_sub(1) {
+        Some(i) => allowed_values
+            .get(i as usize)
+            .map(|v| v.as_ref())
+            .unwrap_or(OTHER_VALUE),
+        None => OTHER_VALUE,
+    }
+}
+
+/// An enumerated string metric.
+///
+/// Records one of a fixed set of string values, as declared in the `metrics.yaml` file.
+/// Values not in that set are recorded as [`OTHER_VALUE`].
+/// Only the index of the value is stored:
+/// `0` for [`OTHER_VALUE`] and `i + 1` for the `i`-th allowed value.
+/// It is mapped back to the value when the metric is snapshotted.
+#[derive(Clone, Debug)]
+pub struct EnumStringMetric {
+    meta: Arc<CommonMetricDataInternal>,
+    allowed_values: AllowedValues,
+}
+
+impl MetricType for EnumStringMetric {
+    fn meta(&self) -> &CommonMetricDataInternal {
+        &self.meta
+    }
+
+    fn with_name(&self, name: String) -> Self {
+        let mut meta = (*self.meta).clone();
+        meta.inner.name = name;
+        Self::register(meta, Arc::clone(&self.allowed_values))
+    }
+
+    fn with_dynamic_label(&self, label: DynamicLabelType) -> Self {
+        let mut meta = (*self.meta).clone();
+        meta.inner.dynamic_label = Some(label);
+        Self {
+            meta: Arc::new(meta),
+            allowed_values: Arc::clone(&self.allowed_values),
+        }
+    }
+}
+
+// IMPORTANT:
+//
+// When changing this implementation, make sure all the operations are
+// also declared in the related trait in `../traits/`.
+impl EnumStringMetric {
+    /// Creates a new enumerated string metric.
+    ///
+    /// # Arguments
+    ///
+    /// * `meta` - The metadata for the metric.
+    /// * `allowed_values` - The values this metric accepts, in the order they were declared.
+    pub fn new(meta: CommonMetricData, allowed_values: Vec<Cow<'static, str>>) -> Self {
+        Self::register(meta.into(), allowed_values.into())
+    }
+
+    /// Creates the metric and registers its allowed values, so that its stored indices
+    /// can be mapped back to values when snapshotting.
+    fn register(meta: CommonMetricDataInternal, allowed_values: AllowedValues) -> Self {
+        ALLOWED_VALUES
+            .write()
+            .unwrap() // safe unwrap, only error case is poisoning
+            .insert(meta.base_identifier(), Arc::clone(&allowed_values));
+        Self {
+            meta: Arc::new(meta),
+            allowed_values,
+        }
+    }
+
+    /// Gets the stored index of `value`.
+    fn index_of(&self, value: &str) -> Option<u32> {
+        self.allowed_values
+            .iter()
+            .position(|v| v == value)
+            .map(|i| i as u32 + 1)
+    }
+
+    /// Sets to the specified value.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - One of the allowed values.
+    ///
+    /// ## Notes
+    ///
+    /// Values not in the list of allowed values are recorded as `__other__`,
+    /// and an [`ErrorType::InvalidValue`] is recorded.
+    pub fn set(&self, value: String) {
+        let metric = self.clone();
+        crate::launch_with_glean(move |glean| metric.set_sync(glean, &value))
+    }
+
+    /// Sets to the specified value synchronously.
+    #[doc(hidden)]
+    pub fn set_sync(&self, glean: &Glean, value: &str) {
+        if !self.should_record(glean) {
+            return;
+        }
+
+        let index = match self.index_of(value) {
+            Some(index) => index,
+            None => {
+                record_error(
+                    glean,
+                    &self.meta,
+                    ErrorType::InvalidValue,
+                    format!("Value '{}' is not one of the allowed values", value),
+                    None,
+                );
+                OTHER_INDEX
+            }
+        };
+
+        glean
+            .storage()
+            .record(glean, &self.meta, &Metric::EnumString(index))
+    }
+
+    /// Gets the current-stored value as a string, or None if there is no value.
+    #[doc(hidden)]
+    pub fn get_value<'a, S: Into<Option<&'a str>>>(
+        &self,
+        glean: &Glean,
+        ping_name: S,
+    ) -> Option<String> {
+        let queried_ping_name = ping_name
+            .into()
+            .unwrap_or_else(|| &self.meta().inner.send_in_pings[0]);
+
+        match StorageManager.snapshot_metric(
+            glean.storage(),
+            queried_ping_name,
+            &self.meta.identifier(glean),
+            self.meta.inner.lifetime,
+        ) {
+            Some(Metric::EnumString(index)) => {
+                Some(value_of(&self.allowed_values, index).to_string())
+            }
+            _ => None,
+        }
+    }
+
+    /// **Exported for test purposes.**
+    ///
+    /// Gets the number of recorded errors for the given metric and error type.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - The type of error
+    ///
+    /// # Returns
+    ///
+    /// The number of errors reported.
+    pub fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32 {
+        crate::block_on_dispatcher();
+
+        crate::core::with_glean(|glean| {
+            test_get_num_recorded_errors(glean, self.meta(), error).unwrap_or(0)
+        })
+    }
+}
+
+impl TestGetValue for EnumStringMetric {
+    type Output = String;
+
+    /// **Test-only API (exported for FFI purposes).**
+    ///
+    /// Gets the currently stored value as a string.
+    ///
+    /// This doesn't clear the stored value.
+    ///
+    /// # Arguments
+    ///
+    /// * `ping_name` - the optional name of the ping to retrieve the metric
+    ///                 for. Defaults to the first value in `send_in_pings`.
+    ///
+    /// # Returns
+    ///
+    /// The stored value or `None` if nothing stored.
+    fn test_get_value(&self, ping_name: Option<String>) -> Option<String> {
+        crate::block_on_dispatcher();
+        crate::core::with_glean(|glean| self.get_value(glean, ping_name.as_deref()))
+    }
+}
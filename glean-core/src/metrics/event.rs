@@ -17,6 +17,39 @@ use chrono::Utc;
 
 const MAX_LENGTH_EXTRA_KEY_VALUE: usize = 500;
 
+/// The declared type of an event extra key.
+///
+/// Extra values are always transported as strings.
+/// The type restricts which strings are accepted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtraKeyType {
+    /// Any string.
+    String,
+    /// A non-negative integer, e.g. `"42"`.
+    Quantity,
+    /// Either `"true"` or `"false"`.
+    Boolean,
+}
+
+impl ExtraKeyType {
+    /// Checks whether `value` is a valid representation of this type.
+    fn accepts(self, value: &str) -> bool {
+        match self {
+            ExtraKeyType::String => true,
+            ExtraKeyType::Quantity => value.parse::<i64>().is_ok_and(|n| n >= 0),
+            ExtraKeyType::Boolean => value == "true" || value == "false",
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            ExtraKeyType::String => "string",
+            ExtraKeyType::Quantity => "quantity",
+            ExtraKeyType::Boolean => "boolean",
+        }
+    }
+}
+
 /// An event metric.
 ///
 /// Events allow recording of e.g. individual occurences of user actions, say
@@ -25,7 +58,7 @@ const MAX_LENGTH_EXTRA_KEY_VALUE: usize = 500;
 #[derive(Clone, Debug)]
 pub struct EventMetric {
     meta: CommonMetricDataInternal,
-    allowed_extra_keys: Vec<String>,
+    allowed_extra_keys: HashMap<String, ExtraKeyType>,
 }
 
 impl MetricType for EventMetric {
@@ -40,7 +73,26 @@ impl MetricType for EventMetric {
 // also declared in the related trait in `../traits/`.
 impl EventMetric {
     /// Creates a new event metric.
+    ///
+    /// All extra keys accept any string value.
     pub fn new(meta: CommonMetricData, allowed_extra_keys: Vec<String>) -> Self {
+        let allowed_extra_keys = allowed_extra_keys
+            .into_iter()
+            .map(|key| (key, ExtraKeyType::String))
+            .collect();
+        Self::new_with_extra_types(meta, allowed_extra_keys)
+    }
+
+    /// Creates a new event metric with typed extra keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `meta` - The metadata for the metric.
+    /// * `allowed_extra_keys` - The allowed extra keys, mapped to the type of their values.
+    pub fn new_with_extra_types(
+        meta: CommonMetricData,
+        allowed_extra_keys: HashMap<String, ExtraKeyType>,
+    ) -> Self {
         Self {
             meta: meta.into(),
             allowed_extra_keys,
@@ -52,8 +104,9 @@ impl EventMetric {
     /// # Arguments
     ///
     /// * `extra` - A [`HashMap`] of `(key, value)` pairs.
-    ///             Keys must be one of the allowed extra keys.
-    ///             If any key is not allowed, an error is reported and no event is recorded.
+    ///             Keys must be one of the allowed extra keys
+    ///             and values must match the declared type of their key.
+    ///             If any key or value is not allowed, an error is reported and no event is recorded.
     pub fn record(&self, extra: HashMap<String, String>) {
         let timestamp = crate::get_timestamp_ms();
         self.record_with_time(timestamp, extra);
@@ -67,8 +120,9 @@ impl EventMetric {
     ///
     /// * `timestamp` - The event timestamp, in milliseconds.
     /// * `extra` - A [`HashMap`] of `(key, value)` pairs.
-    ///             Keys must be one of the allowed extra keys.
-    ///             If any key is not allowed, an error is reported and no event is recorded.
+    ///             Keys must be one of the allowed extra keys
+    ///             and values must match the declared type of their key.
+    ///             If any key or value is not allowed, an error is reported and no event is recorded.
     pub fn record_with_time(&self, timestamp: u64, extra: HashMap<String, String>) {
         let metric = self.clone();
 
@@ -95,9 +149,11 @@ impl EventMetric {
         });
     }
 
-    /// Validate that extras are empty or all extra keys are allowed.
+    /// Validate that extras are empty or all extra keys are allowed
+    /// and their values match the declared types.
     ///
     /// If at least one key is not allowed, record an error and fail.
+    /// If at least one value does not match its type, record an [`ErrorType::InvalidType`] and fail.
     fn validate_extra(
         &self,
         glean: &Glean,
@@ -109,10 +165,16 @@ impl EventMetric {
 
         let mut extra_strings = HashMap::new();
         for (k, v) in extra.into_iter() {
-            if !self.allowed_extra_keys.contains(&k) {
+            let Some(&key_type) = self.allowed_extra_keys.get(&k) else {
                 let msg = format!("Invalid key index {}", k);
                 record_error(glean, &self.meta, ErrorType::InvalidValue, msg, None);
                 return Err(());
+            };
+
+            if !key_type.accepts(&v) {
+                let msg = format!("Extra key {} requires a {} value", k, key_type.as_str());
+                record_error(glean, &self.meta, ErrorType::InvalidType, msg, None);
+                return Err(());
             }
 
             let value = truncate_string_at_boundary_with_error(
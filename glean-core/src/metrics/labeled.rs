@@ -15,8 +15,9 @@ use crate::common_metric_data::{CommonMetricData, CommonMetricDataInternal, Dyna
 use crate::error_recording::{record_error, test_get_num_recorded_errors, ErrorType};
 use crate::histogram::HistogramType;
 use crate::metrics::{
-    BooleanMetric, CounterMetric, CustomDistributionMetric, MemoryDistributionMetric, MemoryUnit,
-    Metric, MetricType, QuantityMetric, StringMetric, TestGetValue, TimeUnit,
+    BooleanMetric, Counter64Metric, CounterMetric, CustomDistributionMetric, DatetimeMetric,
+    GaugeMetric, MemoryDistributionMetric, MemoryUnit, Metric, MetricType, QuantityMetric,
+    RateMetric, StringMetric, TestGetValue, TextMetric, TimeUnit, TimespanMetric,
     TimingDistributionMetric,
 };
 use crate::storage::StorageManager;
@@ -29,6 +30,9 @@ const MAX_LABEL_LENGTH: usize = 111;
 /// A labeled counter.
 pub type LabeledCounter = LabeledMetric<CounterMetric>;
 
+/// A labeled 64-bit counter.
+pub type LabeledCounter64 = LabeledMetric<Counter64Metric>;
+
 /// A labeled boolean.
 pub type LabeledBoolean = LabeledMetric<BooleanMetric>;
 
@@ -47,6 +51,21 @@ pub type LabeledTimingDistribution = LabeledMetric<TimingDistributionMetric>;
 /// A labeled quantity
 pub type LabeledQuantity = LabeledMetric<QuantityMetric>;
 
+/// A labeled gauge.
+pub type LabeledGauge = LabeledMetric<GaugeMetric>;
+
+/// A labeled rate.
+pub type LabeledRate = LabeledMetric<RateMetric>;
+
+/// A labeled timespan.
+pub type LabeledTimespan = LabeledMetric<TimespanMetric>;
+
+/// A labeled datetime.
+pub type LabeledDatetime = LabeledMetric<DatetimeMetric>;
+
+/// A labeled text.
+pub type LabeledText = LabeledMetric<TextMetric>;
+
 /// The metric data needed to construct inner submetrics.
 ///
 /// Different Labeled metrics require different amounts and kinds of information to
@@ -69,12 +88,28 @@ pub enum LabeledMetricData {
     MemoryDistribution {
         cmd: CommonMetricData,
         unit: MemoryUnit,
+        buckets_per_magnitude: Option<u32>,
+        max_bytes: Option<u64>,
     },
     /// The timing_distribution-specific case.
     #[allow(missing_docs)]
     TimingDistribution {
         cmd: CommonMetricData,
         unit: TimeUnit,
+        buckets_per_magnitude: Option<u32>,
+        max_sample_time: Option<u64>,
+    },
+    /// The timespan-specific case.
+    #[allow(missing_docs)]
+    Timespan {
+        cmd: CommonMetricData,
+        unit: TimeUnit,
+    },
+    /// The datetime-specific case.
+    #[allow(missing_docs)]
+    Datetime {
+        cmd: CommonMetricData,
+        unit: TimeUnit,
     },
 }
 
@@ -125,8 +160,9 @@ impl<T: MallocSizeOf> ::malloc_size_of::MallocSizeOf for LabeledMetric<T> {
 mod private {
     use super::LabeledMetricData;
     use crate::metrics::{
-        BooleanMetric, CounterMetric, CustomDistributionMetric, MemoryDistributionMetric,
-        QuantityMetric, StringMetric, TimingDistributionMetric,
+        BooleanMetric, Counter64Metric, CounterMetric, CustomDistributionMetric, DatetimeMetric,
+        GaugeMetric, MemoryDistributionMetric, QuantityMetric, RateMetric, StringMetric,
+        TextMetric, TimespanMetric, TimingDistributionMetric,
     };
 
     /// The sealed labeled trait.
@@ -148,6 +184,15 @@ mod private {
         }
     }
 
+    impl Sealed for Counter64Metric {
+        fn new_inner(meta: LabeledMetricData) -> Self {
+            match meta {
+                LabeledMetricData::Common { cmd } => Self::new(cmd),
+                _ => panic!("Incorrect construction of Labeled<Counter64Metric>"),
+            }
+        }
+    }
+
     impl Sealed for BooleanMetric {
         fn new_inner(meta: LabeledMetricData) -> Self {
             match meta {
@@ -184,7 +229,12 @@ mod private {
     impl Sealed for MemoryDistributionMetric {
         fn new_inner(meta: LabeledMetricData) -> Self {
             match meta {
-                LabeledMetricData::MemoryDistribution { cmd, unit } => Self::new(cmd, unit),
+                LabeledMetricData::MemoryDistribution {
+                    cmd,
+                    unit,
+                    buckets_per_magnitude,
+                    max_bytes,
+                } => Self::new_with_bucketing(cmd, unit, buckets_per_magnitude, max_bytes),
                 _ => panic!("Incorrect construction of Labeled<MemoryDistributionMetric>"),
             }
         }
@@ -193,7 +243,12 @@ mod private {
     impl Sealed for TimingDistributionMetric {
         fn new_inner(meta: LabeledMetricData) -> Self {
             match meta {
-                LabeledMetricData::TimingDistribution { cmd, unit } => Self::new(cmd, unit),
+                LabeledMetricData::TimingDistribution {
+                    cmd,
+                    unit,
+                    buckets_per_magnitude,
+                    max_sample_time,
+                } => Self::new_with_bucketing(cmd, unit, buckets_per_magnitude, max_sample_time),
                 _ => panic!("Incorrect construction of Labeled<TimingDistributionMetric>"),
             }
         }
@@ -207,6 +262,51 @@ mod private {
             }
         }
     }
+
+    impl Sealed for GaugeMetric {
+        fn new_inner(meta: LabeledMetricData) -> Self {
+            match meta {
+                LabeledMetricData::Common { cmd } => Self::new(cmd),
+                _ => panic!("Incorrect construction of Labeled<GaugeMetric>"),
+            }
+        }
+    }
+
+    impl Sealed for RateMetric {
+        fn new_inner(meta: LabeledMetricData) -> Self {
+            match meta {
+                LabeledMetricData::Common { cmd } => Self::new(cmd),
+                _ => panic!("Incorrect construction of Labeled<RateMetric>"),
+            }
+        }
+    }
+
+    impl Sealed for TimespanMetric {
+        fn new_inner(meta: LabeledMetricData) -> Self {
+            match meta {
+                LabeledMetricData::Timespan { cmd, unit } => Self::new(cmd, unit),
+                _ => panic!("Incorrect construction of Labeled<TimespanMetric>"),
+            }
+        }
+    }
+
+    impl Sealed for DatetimeMetric {
+        fn new_inner(meta: LabeledMetricData) -> Self {
+            match meta {
+                LabeledMetricData::Datetime { cmd, unit } => Self::new(cmd, unit),
+                _ => panic!("Incorrect construction of Labeled<DatetimeMetric>"),
+            }
+        }
+    }
+
+    impl Sealed for TextMetric {
+        fn new_inner(meta: LabeledMetricData) -> Self {
+            match meta {
+                LabeledMetricData::Common { cmd } => Self::new(cmd),
+                _ => panic!("Incorrect construction of Labeled<TextMetric>"),
+            }
+        }
+    }
 }
 
 /// Trait for metrics that can be nested inside a labeled metric.
@@ -15,12 +15,16 @@ use crate::Glean;
 use crate::{CommonMetricData, TestGetValue};
 
 // The base of the logarithm used to determine bucketing
-const LOG_BASE: f64 = 2.0;
+pub(crate) const LOG_BASE: f64 = 2.0;
 
-// The buckets per each order of magnitude of the logarithm.
-const BUCKETS_PER_MAGNITUDE: f64 = 16.0;
+// The default buckets per each order of magnitude of the logarithm.
+pub(crate) const BUCKETS_PER_MAGNITUDE: u32 = 16;
 
-// Set a maximum recordable value of 1 terabyte so the buckets aren't
+// The range of buckets per order of magnitude a metric can be configured with.
+const MIN_BUCKETS_PER_MAGNITUDE: u32 = 1;
+const MAX_BUCKETS_PER_MAGNITUDE: u32 = 64;
+
+// Set a default maximum recordable value of 1 terabyte so the buckets aren't
 // completely unbounded.
 const MAX_BYTES: u64 = 1 << 40;
 
@@ -31,12 +35,14 @@ const MAX_BYTES: u64 = 1 << 40;
 pub struct MemoryDistributionMetric {
     meta: Arc<CommonMetricDataInternal>,
     memory_unit: MemoryUnit,
+    buckets_per_magnitude: u32,
+    max_bytes: u64,
 }
 
-/// Create a snapshot of the histogram.
+/// Create a snapshot of the histogram, with the custom maximum it was recorded with, if any.
 ///
 /// The snapshot can be serialized into the payload format.
-pub(crate) fn snapshot(hist: &Histogram<Functional>) -> DistributionData {
+pub(crate) fn snapshot(hist: &Histogram<Functional>, max_bytes: Option<u64>) -> DistributionData {
     DistributionData {
         // **Caution**: This cannot use `Histogram::snapshot_values` and needs to use the more
         // specialized snapshot function.
@@ -47,6 +53,15 @@ pub(crate) fn snapshot(hist: &Histogram<Functional>) -> DistributionData {
             .collect(),
         sum: hist.sum() as i64,
         count: hist.count() as i64,
+        buckets_per_magnitude: Some(hist.buckets_per_magnitude(LOG_BASE))
+            .filter(|&n| n != BUCKETS_PER_MAGNITUDE),
+        upper_bounds: hist
+            .bucket_upper_bounds()
+            .into_iter()
+            .map(|(k, v)| (k as i64, v as i64))
+            .collect(),
+        max_sample_time: None,
+        max_bytes,
     }
 }
 
@@ -61,6 +76,8 @@ impl MetricType for MemoryDistributionMetric {
         Self {
             meta: Arc::new(meta),
             memory_unit: self.memory_unit,
+            buckets_per_magnitude: self.buckets_per_magnitude,
+            max_bytes: self.max_bytes,
         }
     }
 
@@ -70,6 +87,8 @@ impl MetricType for MemoryDistributionMetric {
         Self {
             meta: Arc::new(meta),
             memory_unit: self.memory_unit,
+            buckets_per_magnitude: self.buckets_per_magnitude,
+            max_bytes: self.max_bytes,
         }
     }
 }
@@ -81,12 +100,113 @@ impl MetricType for MemoryDistributionMetric {
 impl MemoryDistributionMetric {
     /// Creates a new memory distribution metric.
     pub fn new(meta: CommonMetricData, memory_unit: MemoryUnit) -> Self {
+        Self::new_with_bucketing(meta, memory_unit, None, None)
+    }
+
+    /// Creates a new memory distribution metric with a custom bucketing.
+    ///
+    /// # Arguments
+    ///
+    /// * `meta` - The metadata for the metric.
+    /// * `memory_unit` - The unit samples are recorded in.
+    /// * `buckets_per_magnitude` - The number of buckets per power of 2,
+    ///   between 1 and 64. Higher values give a better precision.
+    ///   Defaults to 16 if `None`.
+    /// * `max_bytes` - The largest sample that can be recorded, in bytes.
+    ///   Larger samples are truncated. Defaults to 1 Terabyte (2<sup>40</sup> bytes) if `None`.
+    ///
+    /// ## Notes
+    ///
+    /// Invalid values fall back to the defaults.
+    pub fn new_with_bucketing(
+        meta: CommonMetricData,
+        memory_unit: MemoryUnit,
+        buckets_per_magnitude: Option<u32>,
+        max_bytes: Option<u64>,
+    ) -> Self {
+        let meta: CommonMetricDataInternal = meta.into();
+        let buckets_per_magnitude = buckets_per_magnitude.unwrap_or(BUCKETS_PER_MAGNITUDE);
+        let buckets_per_magnitude = if (MIN_BUCKETS_PER_MAGNITUDE..=MAX_BUCKETS_PER_MAGNITUDE)
+            .contains(&buckets_per_magnitude)
+        {
+            buckets_per_magnitude
+        } else {
+            log::error!(
+                "Invalid buckets per magnitude {} for {}, using the default",
+                buckets_per_magnitude,
+                meta.base_identifier()
+            );
+            BUCKETS_PER_MAGNITUDE
+        };
+        let max_bytes = max_bytes.unwrap_or(MAX_BYTES);
+        let max_bytes = if max_bytes > 0 {
+            max_bytes
+        } else {
+            log::error!(
+                "Invalid maximum of 0 bytes for {}, using the default",
+                meta.base_identifier()
+            );
+            MAX_BYTES
+        };
+
         Self {
-            meta: Arc::new(meta.into()),
+            meta: Arc::new(meta),
             memory_unit,
+            buckets_per_magnitude,
+            max_bytes,
+        }
+    }
+
+    /// Creates an empty histogram with the bucketing of this metric.
+    fn new_histogram(&self) -> Histogram<Functional> {
+        Histogram::functional(LOG_BASE, self.buckets_per_magnitude as f64)
+    }
+
+    /// Gets the histogram to record into from the stored value.
+    ///
+    /// A histogram stored with a different bucketing, e.g. by an older version of the metric,
+    /// is re-bucketed, as samples can only be merged into matching buckets.
+    ///
+    /// # Returns
+    ///
+    /// The histogram and whether it had to be re-bucketed.
+    fn stored_histogram(&self, old_value: Option<Metric>) -> (Histogram<Functional>, bool) {
+        let hist = match old_value {
+            Some(Metric::MemoryDistribution(hist))
+            | Some(Metric::BoundedMemoryDistribution(hist, _)) => hist,
+            _ => return (self.new_histogram(), false),
+        };
+
+        if hist.buckets_per_magnitude(LOG_BASE) == self.buckets_per_magnitude {
+            (hist, false)
+        } else {
+            (
+                hist.rebucket(LOG_BASE, self.buckets_per_magnitude as f64),
+                true,
+            )
+        }
+    }
+
+    /// Wraps a histogram into the metric to store, along with a custom maximum.
+    fn to_metric(&self, hist: Histogram<Functional>) -> Metric {
+        if self.max_bytes == MAX_BYTES {
+            Metric::MemoryDistribution(hist)
+        } else {
+            Metric::BoundedMemoryDistribution(hist, self.max_bytes)
         }
     }
 
+    /// Records an error for a stored histogram that had to be re-bucketed.
+    fn record_rebucketed_error(&self, glean: &Glean) {
+        record_error(
+            glean,
+            &self.meta,
+            ErrorType::InvalidState,
+            "Re-bucketed samples stored with a different bucketing",
+            None,
+        );
+    }
+
     /// Accumulates the provided sample in the metric.
     ///
     /// # Arguments
@@ -96,7 +216,8 @@ impl MemoryDistributionMetric {
     ///
     /// ## Notes
     ///
-    /// Values bigger than 1 Terabyte (2<sup>40</sup> bytes) are truncated
+    /// Values bigger than the maximum of the metric, 1 Terabyte (2<sup>40</sup> bytes)
+    /// by default, are truncated
     /// and an [`ErrorType::InvalidValue`] error is recorded.
     pub fn accumulate(&self, sample: i64) {
         let metric = self.clone();
@@ -125,10 +246,10 @@ impl MemoryDistributionMetric {
 
         let mut sample = self.memory_unit.as_bytes(sample as u64);
 
-        if sample > MAX_BYTES {
-            let msg = "Sample is bigger than 1 terabyte";
+        if sample > self.max_bytes {
+            let msg = format!("Sample is bigger than {} bytes", self.max_bytes);
             record_error(glean, &self.meta, ErrorType::InvalidValue, msg, None);
-            sample = MAX_BYTES;
+            sample = self.max_bytes;
         }
 
         // Let's be defensive here:
@@ -138,17 +259,16 @@ impl MemoryDistributionMetric {
         // We do the same for counters and timing distributions.
         // This should never happen in real app usage.
         if let Some(storage) = glean.storage_opt() {
-            storage.record_with(glean, &self.meta, |old_value| match old_value {
-                Some(Metric::MemoryDistribution(mut hist)) => {
-                    hist.accumulate(sample);
-                    Metric::MemoryDistribution(hist)
-                }
-                _ => {
-                    let mut hist = Histogram::functional(LOG_BASE, BUCKETS_PER_MAGNITUDE);
-                    hist.accumulate(sample);
-                    Metric::MemoryDistribution(hist)
-                }
+            let mut rebucketed = false;
+            storage.record_with(glean, &self.meta, |old_value| {
+                let (mut hist, was_rebucketed) = self.stored_histogram(old_value);
+                rebucketed = was_rebucketed;
+                hist.accumulate(sample);
+                self.to_metric(hist)
             });
+            if rebucketed {
+                self.record_rebucketed_error(glean);
+            }
         } else {
             log::warn!(
                 "Couldn't get storage. Can't record memory distribution '{}'.",
@@ -178,7 +298,8 @@ impl MemoryDistributionMetric {
     /// Discards any negative value in `samples` and report an [`ErrorType::InvalidValue`]
     /// for each of them.
     ///
-    /// Values bigger than 1 Terabyte (2<sup>40</sup> bytes) are truncated
+    /// Values bigger than the maximum of the metric, 1 Terabyte (2<sup>40</sup> bytes)
+    /// by default, are truncated
     /// and an [`ErrorType::InvalidValue`] error is recorded.
     pub fn accumulate_samples(&self, samples: Vec<i64>) {
         let metric = self.clone();
@@ -196,12 +317,11 @@ impl MemoryDistributionMetric {
 
         let mut num_negative_samples = 0;
         let mut num_too_log_samples = 0;
+        let mut rebucketed = false;
 
         glean.storage().record_with(glean, &self.meta, |old_value| {
-            let mut hist = match old_value {
-                Some(Metric::MemoryDistribution(hist)) => hist,
-                _ => Histogram::functional(LOG_BASE, BUCKETS_PER_MAGNITUDE),
-            };
+            let (mut hist, was_rebucketed) = self.stored_histogram(old_value);
+            rebucketed = was_rebucketed;
 
             for &sample in samples.iter() {
                 if sample < 0 {
@@ -209,17 +329,21 @@ impl MemoryDistributionMetric {
                 } else {
                     let sample = sample as u64;
                     let mut sample = self.memory_unit.as_bytes(sample);
-                    if sample > MAX_BYTES {
+                    if sample > self.max_bytes {
                         num_too_log_samples += 1;
-                        sample = MAX_BYTES;
+                        sample = self.max_bytes;
                     }
 
                     hist.accumulate(sample);
                 }
             }
-            Metric::MemoryDistribution(hist)
+            self.to_metric(hist)
         });
 
+        if rebucketed {
+            self.record_rebucketed_error(glean);
+        }
+
         if num_negative_samples > 0 {
             let msg = format!("Accumulated {} negative samples", num_negative_samples);
             record_error(
@@ -233,8 +357,8 @@ impl MemoryDistributionMetric {
 
         if num_too_log_samples > 0 {
             let msg = format!(
-                "Accumulated {} samples larger than 1TB",
-                num_too_log_samples
+                "Accumulated {} samples larger than {} bytes",
+                num_too_log_samples, self.max_bytes
             );
             record_error(
                 glean,
@@ -263,7 +387,8 @@ impl MemoryDistributionMetric {
             &self.meta.identifier(glean),
             self.meta.inner.lifetime,
         ) {
-            Some(Metric::MemoryDistribution(hist)) => Some(snapshot(&hist)),
+            Some(Metric::MemoryDistribution(hist)) => Some(snapshot(&hist, None)),
+            Some(Metric::BoundedMemoryDistribution(hist, max)) => Some(snapshot(&hist, Some(max))),
             _ => None,
         }
     }
@@ -287,6 +412,24 @@ impl MemoryDistributionMetric {
         })
     }
 
+    /// **Exported for test purposes.**
+    ///
+    /// Estimates a percentile of the currently stored distribution.
+    /// See [`DistributionData::percentile`].
+    ///
+    /// # Arguments
+    ///
+    /// * `percentile` - The percentile to estimate, between `0` and `100`.
+    /// * `ping_name` - the optional name of the ping to retrieve the metric
+    ///                 for. Defaults to the first value in `send_in_pings`.
+    ///
+    /// # Returns
+    ///
+    /// The estimate or `None` if nothing is stored.
+    pub fn test_get_percentile(&self, percentile: f64, ping_name: Option<String>) -> Option<i64> {
+        self.test_get_value(ping_name)?.percentile(percentile)
+    }
+
     /// **Experimental:** Start a new histogram buffer associated with this memory distribution metric.
     ///
     /// A histogram buffer accumulates in-memory.
@@ -299,7 +442,10 @@ impl MemoryDistributionMetric {
         let metric = self.clone();
         crate::launch_with_glean(move |glean| {
             if errors > 0 {
-                let msg = format!("Accumulated {} samples larger than 1TB", errors);
+                let msg = format!(
+                    "Accumulated {} samples larger than {} bytes",
+                    errors, metric.max_bytes
+                );
                 record_error(
                     glean,
                     &metric.meta,
@@ -309,17 +455,18 @@ impl MemoryDistributionMetric {
                 );
             }
 
+            let mut rebucketed = false;
             glean
                 .storage()
-                .record_with(glean, &metric.meta, move |old_value| {
-                    let mut hist = match old_value {
-                        Some(Metric::MemoryDistribution(hist)) => hist,
-                        _ => Histogram::functional(LOG_BASE, BUCKETS_PER_MAGNITUDE),
-                    };
-
+                .record_with(glean, &metric.meta, |old_value| {
+                    let (mut hist, was_rebucketed) = metric.stored_histogram(old_value);
+                    rebucketed = was_rebucketed;
                     hist.merge(&histogram);
-                    Metric::MemoryDistribution(hist)
+                    metric.to_metric(hist)
                 });
+            if rebucketed {
+                metric.record_rebucketed_error(glean);
+            }
         });
     }
 }
@@ -361,7 +508,7 @@ pub struct LocalMemoryDistribution<'a> {
 impl<'a> LocalMemoryDistribution<'a> {
     /// Create a new histogram buffer referencing the memory distribution it will record into.
     fn new(metric: &'a MemoryDistributionMetric) -> Self {
-        let histogram = Histogram::functional(LOG_BASE, BUCKETS_PER_MAGNITUDE);
+        let histogram = metric.new_histogram();
         Self {
             histogram,
             metric,
@@ -378,9 +525,9 @@ impl<'a> LocalMemoryDistribution<'a> {
     /// Accumulation happens in-memory only.
     pub fn accumulate(&mut self, sample: u64) {
         let mut sample = self.metric.memory_unit.as_bytes(sample);
-        if sample > MAX_BYTES {
+        if sample > self.metric.max_bytes {
             self.errors += 1;
-            sample = MAX_BYTES;
+            sample = self.metric.max_bytes;
         }
         self.histogram.accumulate(sample)
     }
@@ -15,58 +15,77 @@ pub use serde_json::Value as JsonValue;
 
 mod boolean;
 mod counter;
+mod counter64;
 mod custom_distribution;
 mod datetime;
 mod denominator;
+mod distinct_count;
 pub(crate) mod dual_labeled_counter;
+pub(crate) mod enum_string;
 mod event;
 mod experiment;
+mod gauge;
 pub(crate) mod labeled;
-mod memory_distribution;
+pub(crate) mod memory_distribution;
 mod memory_unit;
 mod numerator;
 mod object;
 mod ping;
+mod quantile_distribution;
 mod quantity;
 mod rate;
 mod recorded_experiment;
 mod remote_settings_config;
+mod span;
 mod string;
 mod string_list;
 mod text;
 mod time_unit;
 mod timespan;
-mod timing_distribution;
+pub(crate) mod timing_distribution;
 mod url;
 mod uuid;
 
 use crate::common_metric_data::CommonMetricDataInternal;
 pub use crate::common_metric_data::DynamicLabelType;
+pub use crate::ddsketch::DDSketch;
 pub use crate::event_database::RecordedEvent;
 use crate::histogram::{Functional, Histogram, PrecomputedExponential, PrecomputedLinear};
+pub use crate::hyperloglog::HyperLogLog;
 pub use crate::metrics::datetime::Datetime;
 use crate::util::get_iso_time_string;
 use crate::Glean;
 
 pub use self::boolean::BooleanMetric;
 pub use self::counter::CounterMetric;
+pub use self::counter64::Counter64Metric;
 pub use self::custom_distribution::{CustomDistributionMetric, LocalCustomDistribution};
 pub use self::datetime::DatetimeMetric;
 pub use self::denominator::DenominatorMetric;
-pub use self::dual_labeled_counter::DualLabeledCounterMetric;
-pub use self::event::EventMetric;
+pub use self::distinct_count::DistinctCountMetric;
+pub use self::dual_labeled_counter::{
+    DualLabeledBooleanMetric, DualLabeledCounter64Metric, DualLabeledCounterMetric,
+    DualLabeledCustomDistributionMetric, DualLabeledMetric, DualLabeledQuantityMetric,
+    DualLabeledTimingDistributionMetric,
+};
+pub use self::enum_string::EnumStringMetric;
+pub use self::event::{EventMetric, ExtraKeyType};
 pub(crate) use self::experiment::ExperimentMetric;
+pub use self::gauge::{GaugeData, GaugeMetric};
 pub use self::labeled::{
-    LabeledBoolean, LabeledCounter, LabeledCustomDistribution, LabeledMemoryDistribution,
-    LabeledMetric, LabeledMetricData, LabeledQuantity, LabeledString, LabeledTimingDistribution,
+    LabeledBoolean, LabeledCounter, LabeledCounter64, LabeledCustomDistribution, LabeledDatetime,
+    LabeledGauge, LabeledMemoryDistribution, LabeledMetric, LabeledMetricData, LabeledQuantity,
+    LabeledRate, LabeledString, LabeledText, LabeledTimespan, LabeledTimingDistribution,
 };
 pub use self::memory_distribution::{LocalMemoryDistribution, MemoryDistributionMetric};
 pub use self::memory_unit::MemoryUnit;
 pub use self::numerator::NumeratorMetric;
 pub use self::object::ObjectMetric;
-pub use self::ping::PingType;
+pub use self::ping::{PingOptions, PingTimeSchedule, PingType, PseudonymousIdRotation};
+pub use self::quantile_distribution::QuantileDistributionMetric;
 pub use self::quantity::QuantityMetric;
 pub use self::rate::{Rate, RateMetric};
+pub use self::span::{SpanId, SpanMetric};
 pub use self::string::StringMetric;
 pub use self::string_list::StringListMetric;
 pub use self::text::TextMetric;
@@ -75,7 +94,7 @@ pub use self::timespan::TimespanMetric;
 pub use self::timing_distribution::LocalTimingDistribution;
 pub use self::timing_distribution::TimerId;
 pub use self::timing_distribution::TimingDistributionMetric;
-pub use self::url::UrlMetric;
+pub use self::url::{UrlMetric, UrlReduction, UrlSanitization};
 pub use self::uuid::UuidMetric;
 pub use crate::histogram::HistogramType;
 pub use recorded_experiment::RecordedExperiment;
@@ -100,6 +119,139 @@ pub struct DistributionData {
     /// The total number of entries in the distribution.
     #[serde(skip)]
     pub count: i64,
+
+    /// The number of buckets per power of 2 of a timing or memory distribution.
+    ///
+    /// Only set if it differs from the default for the metric type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buckets_per_magnitude: Option<u32>,
+
+    /// The exclusive upper bound of each bucket, by the bucket's minimum.
+    ///
+    /// Used to interpolate percentiles. Unbounded buckets are left out.
+    #[serde(skip)]
+    pub upper_bounds: HashMap<i64, i64>,
+
+    /// The longest sample a timing distribution can record, in nanoseconds.
+    ///
+    /// Only set if it differs from the default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_sample_time: Option<u64>,
+
+    /// The largest sample a memory distribution can record, in bytes.
+    ///
+    /// Only set if it differs from the default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<u64>,
+}
+
+impl DistributionData {
+    /// Gets the buckets of the distribution, ordered by their minimum.
+    fn sorted_buckets(&self) -> Vec<(i64, i64)> {
+        let mut buckets: Vec<_> = self.values.iter().map(|(&k, &v)| (k, v)).collect();
+        buckets.sort_unstable();
+        buckets
+    }
+
+    /// Gets the arithmetic mean of all samples in the distribution.
+    ///
+    /// Returns `None` if the distribution is empty.
+    pub fn mean(&self) -> Option<f64> {
+        if self.count <= 0 {
+            return None;
+        }
+
+        Some(self.sum as f64 / self.count as f64)
+    }
+
+    /// Estimates the given percentile of the distribution.
+    ///
+    /// The samples themselves are not kept, so they are assumed to be spread evenly
+    /// over the bucket the percentile falls into, between its minimum and its upper bound.
+    /// For unbounded buckets this is the bucket's minimum.
+    ///
+    /// # Arguments
+    ///
+    /// * `percentile` - The percentile to estimate, between `0` and `100`.
+    ///
+    /// # Returns
+    ///
+    /// The estimate or `None` if the distribution is empty or `percentile` is out of range.
+    pub fn percentile(&self, percentile: f64) -> Option<i64> {
+        if !(0.0..=100.0).contains(&percentile) {
+            return None;
+        }
+
+        let buckets = self.sorted_buckets();
+        let total: i64 = buckets.iter().map(|(_, count)| count).sum();
+        if total == 0 {
+            return None;
+        }
+
+        // The rank of the sample that is at the requested percentile, starting at 1.
+        let rank = ((percentile / 100.0) * total as f64).ceil().max(1.0) as i64;
+        let mut seen = 0;
+        for (bucket, count) in buckets {
+            if seen + count < rank {
+                seen += count;
+                continue;
+            }
+
+            let Some(&upper) = self.upper_bounds.get(&bucket) else {
+                return Some(bucket);
+            };
+            // The middle of the slice of the bucket that the sample at `rank` takes up.
+            let fraction = ((rank - seen) as f64 - 0.5) / count as f64;
+            return Some(bucket + ((upper - bucket) as f64 * fraction) as i64);
+        }
+
+        None
+    }
+
+    /// Renders the distribution as an ASCII histogram.
+    ///
+    /// Each non-empty bucket is rendered on its own line,
+    /// with a bar scaled relative to the largest bucket.
+    ///
+    /// ```text
+    ///   1 | ##########           2
+    ///  10 | #################### 4
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The length of the bar of the largest bucket.
+    pub fn ascii_histogram(&self, width: u32) -> String {
+        let buckets: Vec<_> = self
+            .sorted_buckets()
+            .into_iter()
+            .filter(|&(_, count)| count > 0)
+            .collect();
+        let max_count = match buckets.iter().map(|&(_, count)| count).max() {
+            Some(max_count) => max_count,
+            None => return String::new(),
+        };
+        let label_width = buckets
+            .iter()
+            .map(|(bucket, _)| bucket.to_string().len())
+            .max()
+            .unwrap_or(0);
+
+        let width = width as usize;
+        let mut out = String::new();
+        for (bucket, count) in buckets {
+            let bar = ((count as f64 / max_count as f64) * width as f64).round() as usize;
+            // Non-empty buckets always get at least one mark.
+            let bar = bar.max(1).min(width.max(1));
+            out.push_str(&format!(
+                "{:>label_width$} | {:<width$} {}\n",
+                bucket,
+                "#".repeat(bar),
+                count,
+            ));
+        }
+        out
+    }
 }
 
 /// The available metrics.
@@ -154,6 +306,28 @@ pub enum Metric {
     Text(String),
     /// An Object metric. See [`ObjectMetric`] for more information.
     Object(String),
+    /// A distinct-count metric. See [`DistinctCountMetric`] for more information.
+    DistinctCount(HyperLogLog),
+    /// A quantile distribution. See [`QuantileDistributionMetric`] for more information.
+    QuantileDistribution(DDSketch),
+    /// A boolean metric with local differential privacy, and the epsilon it was recorded with.
+    /// See [`BooleanMetric`] for more information.
+    PrivatizedBoolean(bool, f64),
+    /// A counter metric with local differential privacy, and the sum of the epsilons of its increments.
+    /// See [`CounterMetric`] for more information.
+    PrivatizedCounter(i32, f64),
+    /// A gauge metric. See [`GaugeMetric`] for more information.
+    Gauge(GaugeData),
+    /// A 64-bit counter metric. See [`Counter64Metric`] for more information.
+    Counter64(i64),
+    /// An enumerated string metric. See [`EnumStringMetric`] for more information.
+    EnumString(u32),
+    /// A timing distribution metric with a custom maximum sample time, in nanoseconds.
+    /// See [`TimingDistributionMetric`] for more information.
+    BoundedTimingDistribution(Histogram<Functional>, u64),
+    /// A memory distribution metric with a custom maximum sample size, in bytes.
+    /// See [`MemoryDistributionMetric`] for more information.
+    BoundedMemoryDistribution(Histogram<Functional>, u64),
 }
 
 impl MallocSizeOf for Metric {
@@ -178,6 +352,15 @@ impl MallocSizeOf for Metric {
             Metric::Jwe(m) => m.size_of(ops),
             Metric::Text(m) => m.size_of(ops),
             Metric::Object(m) => m.size_of(ops),
+            Metric::DistinctCount(m) => m.size_of(ops),
+            Metric::QuantileDistribution(m) => m.size_of(ops),
+            Metric::PrivatizedBoolean(a, b) => a.size_of(ops) + b.size_of(ops),
+            Metric::PrivatizedCounter(a, b) => a.size_of(ops) + b.size_of(ops),
+            Metric::Gauge(m) => m.size_of(ops),
+            Metric::Counter64(m) => m.size_of(ops),
+            Metric::EnumString(m) => m.size_of(ops),
+            Metric::BoundedTimingDistribution(a, b) => a.size_of(ops) + b.size_of(ops),
+            Metric::BoundedMemoryDistribution(a, b) => a.size_of(ops) + b.size_of(ops),
         }
     }
 }
@@ -351,6 +534,26 @@ impl Metric {
             Metric::Jwe(_) => "jwe",
             Metric::Text(_) => "text",
             Metric::Object(_) => "object",
+            Metric::DistinctCount(_) => "distinct_count",
+            Metric::QuantileDistribution(_) => "quantile_distribution",
+            // Privatized values are reported in the same section, their epsilon separately.
+            Metric::PrivatizedBoolean(..) => "boolean",
+            Metric::PrivatizedCounter(..) => "counter",
+            Metric::Gauge(_) => "gauge",
+            Metric::Counter64(_) => "counter64",
+            Metric::EnumString(_) => "enum_string",
+            Metric::BoundedTimingDistribution(..) => "timing_distribution",
+            Metric::BoundedMemoryDistribution(..) => "memory_distribution",
+        }
+    }
+
+    /// Gets the total local differential privacy epsilon spent on the metric, if any.
+    pub fn ldp_epsilon(&self) -> Option<f64> {
+        match self {
+            Metric::PrivatizedBoolean(_, epsilon) | Metric::PrivatizedCounter(_, epsilon) => {
+                Some(*epsilon)
+            }
+            _ => None,
         }
     }
 
@@ -374,15 +577,33 @@ impl Metric {
             Metric::Timespan(time, time_unit) => {
                 json!({"value": time_unit.duration_convert(*time), "time_unit": time_unit})
             }
-            Metric::TimingDistribution(hist) => json!(timing_distribution::snapshot(hist)),
+            Metric::TimingDistribution(hist) => {
+                json!(timing_distribution::snapshot(hist, None))
+            }
             Metric::Url(s) => json!(s),
             Metric::Uuid(s) => json!(s),
-            Metric::MemoryDistribution(hist) => json!(memory_distribution::snapshot(hist)),
+            Metric::MemoryDistribution(hist) => {
+                json!(memory_distribution::snapshot(hist, None))
+            }
             Metric::Jwe(s) => json!(s),
             Metric::Text(s) => json!(s),
             Metric::Object(s) => {
                 serde_json::from_str(s).expect("object storage should have been json")
             }
+            Metric::DistinctCount(hll) => json!(hll.snapshot()),
+            Metric::QuantileDistribution(sketch) => json!(sketch.snapshot()),
+            Metric::PrivatizedBoolean(b, _) => json!(b),
+            Metric::PrivatizedCounter(c, _) => json!(c),
+            Metric::Gauge(data) => json!(data),
+            Metric::Counter64(c) => json!(c),
+            // Only the index is stored, the snapshot maps it back to the value.
+            Metric::EnumString(index) => json!(index),
+            Metric::BoundedTimingDistribution(hist, max) => {
+                json!(timing_distribution::snapshot(hist, Some(*max)))
+            }
+            Metric::BoundedMemoryDistribution(hist, max) => {
+                json!(memory_distribution::snapshot(hist, Some(*max)))
+            }
         }
     }
 }
@@ -401,16 +622,22 @@ macro_rules! impl_malloc_size_of_for_metric {
 
 impl_malloc_size_of_for_metric!(BooleanMetric);
 impl_malloc_size_of_for_metric!(CounterMetric);
+impl_malloc_size_of_for_metric!(Counter64Metric);
 impl_malloc_size_of_for_metric!(CustomDistributionMetric);
 impl_malloc_size_of_for_metric!(DatetimeMetric);
 impl_malloc_size_of_for_metric!(DenominatorMetric);
+impl_malloc_size_of_for_metric!(DistinctCountMetric);
+impl_malloc_size_of_for_metric!(EnumStringMetric);
 impl_malloc_size_of_for_metric!(EventMetric);
 impl_malloc_size_of_for_metric!(ExperimentMetric);
+impl_malloc_size_of_for_metric!(GaugeMetric);
 impl_malloc_size_of_for_metric!(MemoryDistributionMetric);
 impl_malloc_size_of_for_metric!(NumeratorMetric);
 impl_malloc_size_of_for_metric!(ObjectMetric);
+impl_malloc_size_of_for_metric!(QuantileDistributionMetric);
 impl_malloc_size_of_for_metric!(QuantityMetric);
 impl_malloc_size_of_for_metric!(RateMetric);
+impl_malloc_size_of_for_metric!(SpanMetric);
 impl_malloc_size_of_for_metric!(StringMetric);
 impl_malloc_size_of_for_metric!(StringListMetric);
 impl_malloc_size_of_for_metric!(TextMetric);
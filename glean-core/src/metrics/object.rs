@@ -2,8 +2,11 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use serde::Deserialize;
+
 use crate::common_metric_data::CommonMetricDataInternal;
 use crate::error_recording::{record_error, test_get_num_recorded_errors, ErrorType};
 use crate::metrics::JsonValue;
@@ -13,6 +16,93 @@ use crate::storage::StorageManager;
 use crate::Glean;
 use crate::{CommonMetricData, TestGetValue};
 
+// The maximum nesting depth of an object with a schema.
+const MAX_DEPTH: usize = 10;
+
+// The maximum length of the serialized object with a schema, in bytes.
+const MAX_LENGTH_VALUE: usize = 100 * 1024;
+
+/// The structure of an object, as declared in the `metrics.yaml` file.
+///
+/// This is the subset of JSON schema that `glean_parser` generates:
+/// every node has a `type`, objects list their `properties`
+/// and arrays describe their `items`.
+/// Properties of an object are optional, but no other properties are allowed.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum ObjectSchema {
+    Object {
+        #[serde(default)]
+        properties: HashMap<String, ObjectSchema>,
+    },
+    Array {
+        items: Box<ObjectSchema>,
+    },
+    String,
+    Number,
+    Boolean,
+}
+
+impl ObjectSchema {
+    /// Checks whether `value` matches this schema.
+    ///
+    /// Returns a description of the first mismatch found, if any.
+    fn validate(&self, value: &JsonValue, path: &str) -> Result<(), String> {
+        match (self, value) {
+            (ObjectSchema::Object { properties }, JsonValue::Object(obj)) => {
+                for (key, value) in obj {
+                    let path = format!("{}.{}", path, key);
+                    match properties.get(key) {
+                        // Unset optional properties may be serialized as `null`.
+                        Some(_) if value.is_null() => (),
+                        Some(schema) => schema.validate(value, &path)?,
+                        None => return Err(format!("Unknown property at {}", path)),
+                    }
+                }
+                Ok(())
+            }
+            (ObjectSchema::Array { items }, JsonValue::Array(arr)) => arr
+                .iter()
+                .enumerate()
+                .try_for_each(|(i, value)| items.validate(value, &format!("{}[{}]", path, i))),
+            (ObjectSchema::String, JsonValue::String(_))
+            | (ObjectSchema::Number, JsonValue::Number(_))
+            | (ObjectSchema::Boolean, JsonValue::Bool(_)) => Ok(()),
+            _ => Err(format!("Expected {} at {}", self.type_name(), path)),
+        }
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self {
+            ObjectSchema::Object { .. } => "object",
+            ObjectSchema::Array { .. } => "array",
+            ObjectSchema::String => "string",
+            ObjectSchema::Number => "number",
+            ObjectSchema::Boolean => "boolean",
+        }
+    }
+}
+
+/// How values of an [`ObjectMetric`] are checked before they are recorded.
+#[derive(Debug)]
+enum Structure {
+    /// No schema is known. The shape needs to be externally verified.
+    Unchecked,
+    /// Values need to match the schema.
+    Checked(ObjectSchema),
+    /// The schema could not be parsed. No value can be recorded.
+    Invalid,
+}
+
+/// Gets the nesting depth of `value`. Scalars have a depth of 0.
+fn depth(value: &JsonValue) -> usize {
+    match value {
+        JsonValue::Object(obj) => 1 + obj.values().map(depth).max().unwrap_or(0),
+        JsonValue::Array(arr) => 1 + arr.iter().map(depth).max().unwrap_or(0),
+        _ => 0,
+    }
+}
+
 /// An object metric.
 ///
 /// Record structured data.
@@ -20,6 +110,7 @@ use crate::{CommonMetricData, TestGetValue};
 #[derive(Clone, Debug)]
 pub struct ObjectMetric {
     meta: Arc<CommonMetricDataInternal>,
+    structure: Arc<Structure>,
 }
 
 impl MetricType for ObjectMetric {
@@ -34,9 +125,56 @@ impl MetricType for ObjectMetric {
 // also declared in the related trait in `../traits/`.
 impl ObjectMetric {
     /// Creates a new object metric.
+    ///
+    /// The shape of the values needs to be externally verified.
     pub fn new(meta: CommonMetricData) -> Self {
         Self {
             meta: Arc::new(meta.into()),
+            structure: Arc::new(Structure::Unchecked),
+        }
+    }
+
+    /// Creates a new object metric that checks values against a schema.
+    ///
+    /// # Arguments
+    ///
+    /// * `meta` - The metadata for the metric.
+    /// * `schema` - The JSON schema of the object, as generated by `glean_parser`.
+    ///
+    /// ## Notes
+    ///
+    /// If the schema can't be parsed, no value will ever be recorded.
+    pub fn new_with_schema(meta: CommonMetricData, schema: String) -> Self {
+        let meta: CommonMetricDataInternal = meta.into();
+        let structure = match serde_json::from_str(&schema) {
+            Ok(schema) => Structure::Checked(schema),
+            Err(e) => {
+                log::error!(
+                    "Invalid schema for object metric {}: {}",
+                    meta.base_identifier(),
+                    e
+                );
+                Structure::Invalid
+            }
+        };
+
+        Self {
+            meta: Arc::new(meta),
+            structure: Arc::new(structure),
+        }
+    }
+
+    /// Checks `value` against the schema of this metric and its depth limit.
+    ///
+    /// Returns a description of the problem if the value is not acceptable.
+    fn check(&self, value: &JsonValue) -> Result<(), String> {
+        match &*self.structure {
+            Structure::Unchecked => Ok(()),
+            Structure::Checked(_) if depth(value) > MAX_DEPTH => {
+                Err(format!("Value is nested deeper than {} levels", MAX_DEPTH))
+            }
+            Structure::Checked(schema) => schema.validate(value, "$"),
+            Structure::Invalid => Err("Object schema is invalid".to_string()),
         }
     }
 
@@ -52,14 +190,31 @@ impl ObjectMetric {
             return;
         }
 
-        let value = Metric::Object(serde_json::to_string(&value).unwrap());
+        if let Err(msg) = self.check(&value) {
+            record_error(glean, &self.meta, ErrorType::InvalidValue, msg, None);
+            return;
+        }
+
+        let value = serde_json::to_string(&value).unwrap();
+        let checked = matches!(*self.structure, Structure::Checked(_));
+        if checked && value.len() > MAX_LENGTH_VALUE {
+            let msg = format!(
+                "Value length {} exceeds maximum of {}",
+                value.len(),
+                MAX_LENGTH_VALUE
+            );
+            record_error(glean, &self.meta, ErrorType::InvalidOverflow, msg, None);
+            return;
+        }
+
+        let value = Metric::Object(value);
         glean.storage().record(glean, &self.meta, &value)
     }
 
     /// Sets to the specified structure.
     ///
-    /// No additional verification is done.
-    /// The shape needs to be externally verified.
+    /// If the metric was created with a schema, the value is checked against it.
+    /// Otherwise the shape needs to be externally verified.
     ///
     /// # Arguments
     ///
@@ -74,7 +229,8 @@ impl ObjectMetric {
     /// Parses the passed JSON string.
     /// If it can't be parsed into a valid object it records an invalid value error.
     ///
-    /// Note: This does not check the structure. This needs to be done by the wrapper.
+    /// If the metric was created with a schema, the structure is checked against it.
+    /// Otherwise this needs to be done by the wrapper.
     ///
     /// # Arguments
     ///
@@ -6,13 +6,63 @@ use std::fmt;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
-use crate::ping::PingMaker;
+use crate::ping::{Ping, PingMaker, PingSnapshotFilter};
 use crate::upload::PingPayload;
 use crate::Glean;
 
 use malloc_size_of_derive::MallocSizeOf;
 use uuid::Uuid;
 
+/// The longest interval a ping can be scheduled with, about 10 years.
+const MAX_INTERVAL_HOURS: u32 = 10 * 366 * 24;
+
+/// A time-based schedule for submitting a ping.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, MallocSizeOf)]
+pub enum PingTimeSchedule {
+    /// Submit the ping once a day, at the given hour in local time (0-23).
+    Daily {
+        /// The hour of the day, in local time.
+        hour: u8,
+    },
+    /// Submit the ping every given number of hours.
+    Interval {
+        /// The number of hours between two submissions, at most about 10 years.
+        hours: u32,
+    },
+}
+
+/// When the pseudonymous identifier of a ping rotates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, MallocSizeOf)]
+pub enum PseudonymousIdRotation {
+    /// Rotate every given number of days.
+    /// Windows are counted in UTC days since the Unix epoch.
+    Days {
+        /// The number of days the identifier is kept.
+        days: u32,
+    },
+    /// Rotate with every session.
+    /// Pings collected outside of a session don't carry an identifier.
+    Session,
+}
+
+/// Optional settings for a ping.
+///
+/// The default submits the ping only when asked to.
+#[derive(Clone, Debug, Default, PartialEq, Eq, MallocSizeOf)]
+pub struct PingOptions {
+    /// Submit the ping on a time-based schedule, with reason `scheduled`.
+    pub time_schedule: Option<PingTimeSchedule>,
+    /// Submit the ping when the app build changed since the last run, with reason `upgrade`.
+    pub submit_on_build_change: bool,
+    /// Include a rotating pseudonymous identifier in `client_info.pseudonymous_id`.
+    /// Pings within the same window can be linked, pings across windows can't.
+    /// The identifier replaces the `client_id`, which is not included then.
+    pub pseudonymous_id: Option<PseudonymousIdRotation>,
+    /// Filters applied to the metrics of the ping when it is collected.
+    /// Can be overridden through Server Knobs.
+    pub snapshot_filter: Option<PingSnapshotFilter>,
+}
+
 /// Stores information about a ping.
 ///
 /// This is required so that given metric data queued on disk we can send
@@ -45,6 +95,9 @@ struct InnerPing {
 
     /// Ordered list of uploader capabilities required to upload this ping.
     uploader_capabilities: Vec<String>,
+
+    /// Optional settings, e.g. when to submit this ping on its own.
+    options: PingOptions,
 }
 
 impl fmt::Debug for PingType {
@@ -63,6 +116,7 @@ impl fmt::Debug for PingType {
                 &self.0.follows_collection_enabled.load(Ordering::Relaxed),
             )
             .field("uploader_capabilities", &self.0.uploader_capabilities)
+            .field("options", &self.0.options)
             .finish()
     }
 }
@@ -121,10 +175,22 @@ impl PingType {
         )
     }
 
+    /// Creates a new ping type with additional options.
+    ///
+    /// See [`PingType::new`] for the other arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Optional settings, e.g. a schedule to submit this ping on.
+    ///
+    /// ## Notes
+    ///
+    /// Invalid schedules, like an hour past 23 or an interval of 0 hours, are ignored.
+    /// Intervals longer than about 10 years are shortened.
     #[allow(clippy::too_many_arguments)]
-    pub(crate) fn new_internal<A: Into<String>>(
+    pub fn new_with_options<A: Into<String>>(
         name: A,
-        include_client_id: bool,
+        mut include_client_id: bool,
         send_if_empty: bool,
         precise_timestamps: bool,
         include_info_sections: bool,
@@ -133,9 +199,51 @@ impl PingType {
         reason_codes: Vec<String>,
         follows_collection_enabled: bool,
         uploader_capabilities: Vec<String>,
+        mut options: PingOptions,
     ) -> Self {
+        let name = name.into();
+        let valid_schedule = match options.time_schedule {
+            Some(PingTimeSchedule::Daily { hour }) => hour < 24,
+            Some(PingTimeSchedule::Interval { hours }) => hours > 0,
+            None => true,
+        };
+        if !valid_schedule {
+            log::error!(
+                "Invalid schedule {:?} for ping {}, ignoring it",
+                options.time_schedule,
+                name
+            );
+            options.time_schedule = None;
+        }
+        if let Some(PingTimeSchedule::Interval { hours }) = &mut options.time_schedule {
+            if *hours > MAX_INTERVAL_HOURS {
+                log::error!(
+                    "Interval of {} hours for ping {} is too long, using {} hours",
+                    hours,
+                    name,
+                    MAX_INTERVAL_HOURS
+                );
+                *hours = MAX_INTERVAL_HOURS;
+            }
+        }
+        if options.pseudonymous_id == Some(PseudonymousIdRotation::Days { days: 0 }) {
+            log::error!(
+                "Invalid pseudonymous ID rotation for ping {}, not including an ID",
+                name
+            );
+            options.pseudonymous_id = None;
+        }
+        if include_client_id && options.pseudonymous_id.is_some() {
+            // The stable client ID would link the pseudonymous IDs of all windows.
+            log::error!(
+                "Ping {} can't include both a client ID and a pseudonymous ID, not including the client ID",
+                name
+            );
+            include_client_id = false;
+        }
+
         let this = Self(Arc::new(InnerPing {
-            name: name.into(),
+            name,
             include_client_id,
             send_if_empty,
             precise_timestamps,
@@ -145,6 +253,7 @@ impl PingType {
             reason_codes,
             follows_collection_enabled: AtomicBool::new(follows_collection_enabled),
             uploader_capabilities,
+            options,
         }));
 
         // Register this ping.
@@ -154,6 +263,34 @@ impl PingType {
         this
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new_internal<A: Into<String>>(
+        name: A,
+        include_client_id: bool,
+        send_if_empty: bool,
+        precise_timestamps: bool,
+        include_info_sections: bool,
+        enabled: bool,
+        schedules_pings: Vec<String>,
+        reason_codes: Vec<String>,
+        follows_collection_enabled: bool,
+        uploader_capabilities: Vec<String>,
+    ) -> Self {
+        Self::new_with_options(
+            name,
+            include_client_id,
+            send_if_empty,
+            precise_timestamps,
+            include_info_sections,
+            enabled,
+            schedules_pings,
+            reason_codes,
+            follows_collection_enabled,
+            uploader_capabilities,
+            PingOptions::default(),
+        )
+    }
+
     /// Get the name of this Ping
     pub fn name(&self) -> &str {
         &self.0.name
@@ -246,6 +383,34 @@ impl PingType {
         &self.0.uploader_capabilities
     }
 
+    /// The time-based schedule this ping is submitted on, if any.
+    pub fn time_schedule(&self) -> Option<&PingTimeSchedule> {
+        self.0.options.time_schedule.as_ref()
+    }
+
+    /// Whether this ping is submitted when the app build changes.
+    pub fn submit_on_build_change(&self) -> bool {
+        self.0.options.submit_on_build_change
+    }
+
+    /// When the pseudonymous identifier included in this ping rotates, if it includes one.
+    pub fn pseudonymous_id(&self) -> Option<&PseudonymousIdRotation> {
+        self.0.options.pseudonymous_id.as_ref()
+    }
+
+    /// The filters declared for the metrics of this ping, if any.
+    ///
+    /// Server Knobs might override these,
+    /// see [`Glean::ping_snapshot_filter`](crate::Glean::ping_snapshot_filter).
+    pub fn snapshot_filter(&self) -> Option<&PingSnapshotFilter> {
+        self.0.options.snapshot_filter.as_ref()
+    }
+
+    /// Whether this ping is submitted by the ping scheduler.
+    pub(crate) fn is_scheduled(&self) -> bool {
+        self.time_schedule().is_some() || self.submit_on_build_change()
+    }
+
     /// Submits the ping for eventual uploading.
     ///
     /// The ping content is assembled as soon as possible, but upload is not
@@ -353,38 +518,42 @@ impl PingType {
                         .add_sync(glean, 1);
                 }
 
-                if let Err(e) = ping_maker.store_ping(glean.get_data_path(), &ping) {
-                    log::warn!(
-                        "IO error while writing ping to file: {}. Enqueuing upload of what we have in memory.",
-                        e
-                    );
-                    glean.additional_metrics.io_errors.add_sync(glean, 1);
-                    // `serde_json::to_string` only fails if serialization of the content
-                    // fails or it contains maps with non-string keys.
-                    // However `ping.content` is already a `JsonValue`,
-                    // so both scenarios should be impossible.
-                    let content =
-                        ::serde_json::to_string(&ping.content).expect("ping serialization failed");
-                    // TODO: Shouldn't we consolidate on a single collected Ping representation?
-                    let ping = PingPayload {
-                        document_id: ping.doc_id.to_string(),
-                        upload_path: ping.url_path.to_string(),
-                        json_body: content,
-                        headers: Some(ping.headers),
-                        body_has_info_sections: self.0.include_info_sections,
-                        ping_name: self.0.name.to_string(),
-                        uploader_capabilities: self.0.uploader_capabilities.clone(),
-                    };
-
-                    glean.upload_manager.enqueue_ping(glean, ping);
+                let max_body_size = glean.upload_manager.max_ping_body_size();
+                let stored = match ping_maker.split_oversized(&ping, max_body_size) {
+                    None => self.store_and_enqueue(glean, &ping_maker, ping),
+                    Some(parts) => {
+                        log::info!(
+                            "The ping '{}' is too large and will be sent in {} parts",
+                            ping.name,
+                            parts.len()
+                        );
+
+                        let mut stored = true;
+                        for content in parts {
+                            let doc_id = Uuid::new_v4().to_string();
+                            let url_path = glean.make_path(ping.name, &doc_id);
+                            let part = Ping {
+                                doc_id: &doc_id,
+                                name: ping.name,
+                                url_path: &url_path,
+                                content,
+                                headers: ping.headers.clone(),
+                                includes_info_sections: ping.includes_info_sections,
+                                schedules_pings: ping.schedules_pings.clone(),
+                                uploader_capabilities: ping.uploader_capabilities.clone(),
+                            };
+                            stored &= self.store_and_enqueue(glean, &ping_maker, part);
+                        }
+                        stored
+                    }
+                };
+                if !stored {
                     return true;
                 }
 
-                glean.upload_manager.enqueue_ping_from_file(glean, &doc_id);
-
                 log::info!(
                     "The ping '{}' was submitted and will be sent as soon as possible",
-                    ping.name
+                    self.0.name
                 );
 
                 true
@@ -395,6 +564,47 @@ impl PingType {
         submitted
     }
 
+    /// Stores a collected ping on disk and enqueues it for upload.
+    ///
+    /// If the ping can't be stored, it is enqueued from memory.
+    ///
+    /// # Returns
+    ///
+    /// Whether the ping was stored on disk.
+    fn store_and_enqueue(&self, glean: &Glean, ping_maker: &PingMaker, ping: Ping) -> bool {
+        if let Err(e) = ping_maker.store_ping(glean.get_data_path(), &ping) {
+            log::warn!(
+                "IO error while writing ping to file: {}. Enqueuing upload of what we have in memory.",
+                e
+            );
+            glean.additional_metrics.io_errors.add_sync(glean, 1);
+            // `serde_json::to_string` only fails if serialization of the content
+            // fails or it contains maps with non-string keys.
+            // However `ping.content` is already a `JsonValue`,
+            // so both scenarios should be impossible.
+            let content =
+                ::serde_json::to_string(&ping.content).expect("ping serialization failed");
+            // TODO: Shouldn't we consolidate on a single collected Ping representation?
+            let ping = PingPayload {
+                document_id: ping.doc_id.to_string(),
+                upload_path: ping.url_path.to_string(),
+                json_body: content,
+                headers: Some(ping.headers),
+                body_has_info_sections: self.0.include_info_sections,
+                ping_name: self.0.name.to_string(),
+                uploader_capabilities: self.0.uploader_capabilities.clone(),
+            };
+
+            glean.upload_manager.enqueue_ping(glean, ping);
+            return false;
+        }
+
+        glean
+            .upload_manager
+            .enqueue_ping_from_file(glean, ping.doc_id);
+        true
+    }
+
     fn handle_ping_schedule(&self, glean: &Glean, ping: &InnerPing, reason: Option<&str>) {
         if ping.schedules_pings.is_empty() {
             let ping_schedule = glean
@@ -0,0 +1,274 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::common_metric_data::{CommonMetricDataInternal, DynamicLabelType};
+use crate::ddsketch::DDSketch;
+use crate::error_recording::{record_error, test_get_num_recorded_errors, ErrorType};
+use crate::metrics::{DistributionData, Metric, MetricType};
+use crate::storage::StorageManager;
+use crate::Glean;
+use crate::{CommonMetricData, TestGetValue};
+
+/// A quantile distribution metric.
+///
+/// Records non-negative integer samples into a relative-error quantile sketch,
+/// such that any percentile can be computed with a relative error of at most
+/// the configured accuracy, both on the client and after merging sketches on the server.
+#[derive(Clone, Debug)]
+pub struct QuantileDistributionMetric {
+    meta: Arc<CommonMetricDataInternal>,
+    relative_accuracy: f64,
+}
+
+/// Create a snapshot of the sketch.
+///
+/// Samples are grouped by the representative value of their bin.
+pub(crate) fn snapshot(sketch: &DDSketch) -> DistributionData {
+    DistributionData {
+        values: sketch.snapshot_values(),
+        sum: sketch.sum() as i64,
+        count: sketch.count() as i64,
+        buckets_per_magnitude: None,
+        upper_bounds: HashMap::new(),
+        max_sample_time: None,
+        max_bytes: None,
+    }
+}
+
+impl MetricType for QuantileDistributionMetric {
+    fn meta(&self) -> &CommonMetricDataInternal {
+        &self.meta
+    }
+
+    fn with_name(&self, name: String) -> Self {
+        let mut meta = (*self.meta).clone();
+        meta.inner.name = name;
+        Self {
+            meta: Arc::new(meta),
+            relative_accuracy: self.relative_accuracy,
+        }
+    }
+
+    fn with_dynamic_label(&self, label: DynamicLabelType) -> Self {
+        let mut meta = (*self.meta).clone();
+        meta.inner.dynamic_label = Some(label);
+        Self {
+            meta: Arc::new(meta),
+            relative_accuracy: self.relative_accuracy,
+        }
+    }
+}
+
+// IMPORTANT:
+//
+// When changing this implementation, make sure all the operations are
+// also declared in the related trait in `../traits/`.
+impl QuantileDistributionMetric {
+    /// Creates a new quantile distribution metric.
+    ///
+    /// # Arguments
+    ///
+    /// * `meta` - The metadata for the metric.
+    /// * `relative_accuracy` - The maximum relative error of the computed quantiles,
+    ///   e.g. `0.01` for 1%. Clamped to the range `0.001..=0.25`.
+    pub fn new(meta: CommonMetricData, relative_accuracy: f64) -> Self {
+        // Normalize through the sketch, so that the accuracy always matches the stored data.
+        let relative_accuracy = DDSketch::new(relative_accuracy).relative_accuracy();
+        Self {
+            meta: Arc::new(meta.into()),
+            relative_accuracy,
+        }
+    }
+
+    /// Accumulates the provided signed samples in the metric.
+    ///
+    /// This is required so that the platform-specific code can provide us with
+    /// 64 bit signed integers if no `u64` comparable type is available. This
+    /// will take care of filtering and reporting errors for any provided negative
+    /// sample.
+    ///
+    /// # Arguments
+    ///
+    /// - `samples` - The vector holding the samples to be recorded by the metric.
+    ///
+    /// ## Notes
+    ///
+    /// Discards any negative value in `samples` and report an [`ErrorType::InvalidValue`]
+    /// for each of them.
+    pub fn accumulate_samples(&self, samples: Vec<i64>) {
+        let metric = self.clone();
+        crate::launch_with_glean(move |glean| metric.accumulate_samples_sync(glean, &samples))
+    }
+
+    /// Accumulates precisely one signed sample and appends it to the metric.
+    ///
+    /// Signed is required so that the platform-specific code can provide us with a
+    /// 64 bit signed integer if no `u64` comparable type is available. This
+    /// will take care of filtering and reporting errors.
+    ///
+    /// # Arguments
+    ///
+    /// - `sample` - The singular sample to be recorded by the metric.
+    ///
+    /// ## Notes
+    ///
+    /// Discards any negative value of `sample` and reports an
+    /// [`ErrorType::InvalidValue`].
+    pub fn accumulate_single_sample(&self, sample: i64) {
+        let metric = self.clone();
+        crate::launch_with_glean(move |glean| metric.accumulate_samples_sync(glean, &[sample]))
+    }
+
+    /// Accumulates the provided sample in the metric synchronously.
+    ///
+    /// See [`accumulate_samples`](Self::accumulate_samples) for details.
+    #[doc(hidden)]
+    pub fn accumulate_samples_sync(&self, glean: &Glean, samples: &[i64]) {
+        if !self.should_record(glean) {
+            return;
+        }
+
+        let mut num_negative_samples = 0;
+
+        glean.storage().record_with(glean, &self.meta, |old_value| {
+            let mut sketch = match old_value {
+                Some(Metric::QuantileDistribution(sketch)) => sketch,
+                _ => DDSketch::new(self.relative_accuracy),
+            };
+
+            num_negative_samples = 0;
+            for &sample in samples {
+                if sample < 0 {
+                    num_negative_samples += 1;
+                } else {
+                    sketch.accumulate(sample as u64);
+                }
+            }
+
+            Metric::QuantileDistribution(sketch)
+        });
+
+        if num_negative_samples > 0 {
+            let msg = format!("Accumulated {} negative samples", num_negative_samples);
+            record_error(
+                glean,
+                &self.meta,
+                ErrorType::InvalidValue,
+                msg,
+                num_negative_samples,
+            );
+        }
+    }
+
+    /// Gets the currently stored sketch.
+    #[doc(hidden)]
+    pub fn get_value<'a, S: Into<Option<&'a str>>>(
+        &self,
+        glean: &Glean,
+        ping_name: S,
+    ) -> Option<DDSketch> {
+        let queried_ping_name = ping_name
+            .into()
+            .unwrap_or_else(|| &self.meta().inner.send_in_pings[0]);
+
+        match StorageManager.snapshot_metric(
+            glean.storage(),
+            queried_ping_name,
+            &self.meta.identifier(glean),
+            self.meta.inner.lifetime,
+        ) {
+            Some(Metric::QuantileDistribution(sketch)) => Some(sketch),
+            _ => None,
+        }
+    }
+
+    /// **Test-only API (exported for FFI purposes).**
+    ///
+    /// Gets the currently stored value as a [`DistributionData`],
+    /// with the samples grouped by the representative value of their bin.
+    ///
+    /// This doesn't clear the stored value.
+    ///
+    /// # Arguments
+    ///
+    /// * `ping_name` - the optional name of the ping to retrieve the metric
+    ///                 for. Defaults to the first value in `send_in_pings`.
+    ///
+    /// # Returns
+    ///
+    /// The stored value or `None` if nothing stored.
+    pub fn test_get_value(&self, ping_name: Option<String>) -> Option<DistributionData> {
+        crate::block_on_dispatcher();
+        crate::core::with_glean(|glean| {
+            self.get_value(glean, ping_name.as_deref())
+                .map(|sketch| snapshot(&sketch))
+        })
+    }
+
+    /// **Test-only API (exported for FFI purposes).**
+    ///
+    /// Estimates the value at the given quantile.
+    ///
+    /// # Arguments
+    ///
+    /// * `quantile` - The quantile, in the range `0.0..=1.0`, e.g. `0.95` for the 95th percentile.
+    /// * `ping_name` - the optional name of the ping to retrieve the metric
+    ///                 for. Defaults to the first value in `send_in_pings`.
+    ///
+    /// # Returns
+    ///
+    /// The estimate or `None` if nothing stored or `quantile` is out of range.
+    pub fn test_get_quantile(&self, quantile: f64, ping_name: Option<String>) -> Option<f64> {
+        crate::block_on_dispatcher();
+        crate::core::with_glean(|glean| {
+            self.get_value(glean, ping_name.as_deref())
+                .and_then(|sketch| sketch.quantile(quantile))
+        })
+    }
+
+    /// **Exported for test purposes.**
+    ///
+    /// Gets the number of recorded errors for the given metric and error type.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - The type of error
+    ///
+    /// # Returns
+    ///
+    /// The number of errors reported.
+    pub fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32 {
+        crate::block_on_dispatcher();
+
+        crate::core::with_glean(|glean| {
+            test_get_num_recorded_errors(glean, self.meta(), error).unwrap_or(0)
+        })
+    }
+}
+
+impl TestGetValue for QuantileDistributionMetric {
+    type Output = DistributionData;
+
+    /// **Test-only API (exported for FFI purposes).**
+    ///
+    /// Gets the currently stored value as a [`DistributionData`],
+    /// with the samples grouped by the representative value of their bin.
+    ///
+    /// This doesn't clear the stored value.
+    ///
+    /// # Arguments
+    ///
+    /// * `ping_name` - the optional name of the ping to retrieve the metric
+    ///                 for. Defaults to the first value in `send_in_pings`.
+    ///
+    /// # Returns
+    ///
+    /// The stored value or `None` if nothing stored.
+    fn test_get_value(&self, ping_name: Option<String>) -> Option<DistributionData> {
+        QuantileDistributionMetric::test_get_value(self, ping_name)
+    }
+}
@@ -2,7 +2,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::common_metric_data::CommonMetricDataInternal;
+use crate::common_metric_data::{CommonMetricDataInternal, DynamicLabelType};
 use crate::error_recording::{record_error, test_get_num_recorded_errors, ErrorType};
 use crate::metrics::Metric;
 use crate::metrics::MetricType;
@@ -44,6 +44,18 @@ impl MetricType for RateMetric {
     fn meta(&self) -> &CommonMetricDataInternal {
         &self.meta
     }
+
+    fn with_name(&self, name: String) -> Self {
+        let mut meta = self.meta.clone();
+        meta.inner.name = name;
+        Self { meta }
+    }
+
+    fn with_dynamic_label(&self, label: DynamicLabelType) -> Self {
+        let mut meta = self.meta.clone();
+        meta.inner.dynamic_label = Some(label);
+        Self { meta }
+    }
 }
 
 // IMPORTANT:
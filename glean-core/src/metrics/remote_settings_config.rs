@@ -7,6 +7,8 @@ use std::collections::HashMap;
 use malloc_size_of_derive::MallocSizeOf;
 use serde::{Deserialize, Serialize};
 
+use crate::ping::PingSnapshotFilter;
+
 /// Represents a list of metrics and an associated boolean property
 /// indicating if the metric is enabled from the remote-settings
 /// configuration store.
@@ -46,6 +48,12 @@ pub struct RemoteSettingsConfig {
     /// Changes take effect at the next session start.
     #[serde(default)]
     pub session_sample_rate: Option<f64>,
+
+    /// This is a `HashMap` consisting of ping names as keys and
+    /// filters that replace the snapshot filters declared by the ping
+    /// of the same name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub ping_snapshot_filters: HashMap<String, PingSnapshotFilter>,
 }
 
 impl RemoteSettingsConfig {
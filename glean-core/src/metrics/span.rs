@@ -0,0 +1,336 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::common_metric_data::CommonMetricDataInternal;
+use crate::error_recording::{record_error, test_get_num_recorded_errors, ErrorType};
+use crate::event_database::RecordedEvent;
+use crate::metrics::{EventMetric, ExtraKeyType, MetricType};
+use crate::Glean;
+use crate::{CommonMetricData, TestGetValue};
+
+/// The extra key holding the id of the span.
+const SPAN_ID_KEY: &str = "span_id";
+/// The extra key holding the id of the parent span, if any.
+const PARENT_ID_KEY: &str = "parent_span_id";
+/// The extra key holding whether the record starts or ends the span.
+const PHASE_KEY: &str = "span_phase";
+/// The extra key holding the duration of the span, in nanoseconds.
+const DURATION_KEY: &str = "duration_ns";
+
+const RESERVED_KEYS: [&str; 4] = [SPAN_ID_KEY, PARENT_ID_KEY, PHASE_KEY, DURATION_KEY];
+
+/// Identifier for a span.
+///
+/// Span ids are random, so spans of different metrics can refer to each other as parents.
+/// Its internals are considered private,
+/// but due to UniFFI's behavior we expose its field for now.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SpanId {
+    /// This span's id.
+    pub id: u64,
+}
+
+impl SpanId {
+    fn new() -> Self {
+        SpanId {
+            id: Uuid::new_v4().as_u64_pair().0,
+        }
+    }
+}
+
+/// A span that was started, but not yet ended.
+#[derive(Debug)]
+struct OpenSpan {
+    parent: Option<SpanId>,
+    /// Timestamp in nanoseconds, from the same clock as timing distributions.
+    start_time: u64,
+}
+
+/// A span metric.
+///
+/// Spans record operations with a start and an end.
+/// Each span can have a parent span, which allows to link operations causally,
+/// e.g. the background work a user action triggered.
+///
+/// Spans are stored as pairs of events, sharing the same `span_id` extra.
+/// The start record carries the `parent_span_id`, if any.
+/// The end record additionally carries the `duration_ns` of the span and the user-provided extras.
+#[derive(Clone, Debug)]
+pub struct SpanMetric {
+    event: EventMetric,
+    open_spans: Arc<Mutex<HashMap<SpanId, OpenSpan>>>,
+}
+
+impl MetricType for SpanMetric {
+    fn meta(&self) -> &CommonMetricDataInternal {
+        self.event.meta()
+    }
+}
+
+// IMPORTANT:
+//
+// When changing this implementation, make sure all the operations are
+// also declared in the related trait in `../traits/`.
+impl SpanMetric {
+    /// Creates a new span metric.
+    ///
+    /// # Arguments
+    ///
+    /// * `meta` - The metadata for the metric.
+    /// * `allowed_extra_keys` - The extra keys that can be passed when ending a span.
+    pub fn new(meta: CommonMetricData, allowed_extra_keys: Vec<String>) -> Self {
+        let mut allowed_extra_keys: HashMap<String, ExtraKeyType> = allowed_extra_keys
+            .into_iter()
+            .map(|key| (key, ExtraKeyType::String))
+            .collect();
+        // Span ids use the full `u64` range, which doesn't fit a quantity.
+        allowed_extra_keys.insert(SPAN_ID_KEY.to_string(), ExtraKeyType::String);
+        allowed_extra_keys.insert(PARENT_ID_KEY.to_string(), ExtraKeyType::String);
+        allowed_extra_keys.insert(PHASE_KEY.to_string(), ExtraKeyType::String);
+        allowed_extra_keys.insert(DURATION_KEY.to_string(), ExtraKeyType::Quantity);
+
+        Self {
+            event: EventMetric::new_with_extra_types(meta, allowed_extra_keys),
+            open_spans: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Starts a new span.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent` - The span that caused this one, if any.
+    ///   It does not need to be recorded by the same metric.
+    ///
+    /// # Returns
+    ///
+    /// A unique [`SpanId`] for the new span.
+    pub fn start(&self, parent: Option<SpanId>) -> SpanId {
+        let span = SpanId::new();
+        let timestamp = crate::get_timestamp_ms();
+        let start_time = zeitstempel::now_awake();
+        let precise_timestamp = Utc::now().timestamp_millis() as u64;
+
+        let metric = self.clone();
+        crate::launch_with_glean(move |glean| {
+            metric.set_start(
+                glean,
+                span,
+                parent,
+                timestamp,
+                start_time,
+                precise_timestamp,
+            )
+        });
+        span
+    }
+
+    /// **Test-only API (exported for testing purposes).**
+    ///
+    /// Starts a new span synchronously.
+    ///
+    /// Use [`start`](Self::start) instead.
+    #[doc(hidden)]
+    pub fn start_sync(&self, glean: &Glean, parent: Option<SpanId>) -> SpanId {
+        let span = SpanId::new();
+        let timestamp = crate::get_timestamp_ms();
+        let start_time = zeitstempel::now_awake();
+        let precise_timestamp = Utc::now().timestamp_millis() as u64;
+
+        self.set_start(
+            glean,
+            span,
+            parent,
+            timestamp,
+            start_time,
+            precise_timestamp,
+        );
+        span
+    }
+
+    fn set_start(
+        &self,
+        glean: &Glean,
+        span: SpanId,
+        parent: Option<SpanId>,
+        timestamp: u64,
+        start_time: u64,
+        precise_timestamp: u64,
+    ) {
+        if !self.should_record(glean) {
+            return;
+        }
+
+        self.open_spans
+            .lock()
+            .unwrap()
+            .insert(span, OpenSpan { parent, start_time });
+
+        let extra = span_extra(span, parent, "start");
+        self.record(glean, timestamp, extra, precise_timestamp);
+    }
+
+    /// Ends a span.
+    ///
+    /// # Arguments
+    ///
+    /// * `span` - The [`SpanId`] returned by [`start`](Self::start).
+    /// * `extra` - A [`HashMap`] of `(key, value)` pairs to record with the end of the span.
+    ///             Keys must be one of the allowed extra keys.
+    ///
+    /// ## Notes
+    ///
+    /// Records an [`ErrorType::InvalidState`] error if the span is not running.
+    pub fn end(&self, span: SpanId, extra: HashMap<String, String>) {
+        let timestamp = crate::get_timestamp_ms();
+        let stop_time = zeitstempel::now_awake();
+        let precise_timestamp = Utc::now().timestamp_millis() as u64;
+
+        let metric = self.clone();
+        crate::launch_with_glean(move |glean| {
+            metric.set_end(glean, span, extra, timestamp, stop_time, precise_timestamp)
+        });
+    }
+
+    /// **Test-only API (exported for testing purposes).**
+    ///
+    /// Ends a span synchronously.
+    ///
+    /// Use [`end`](Self::end) instead.
+    #[doc(hidden)]
+    pub fn end_sync(&self, glean: &Glean, span: SpanId, extra: HashMap<String, String>) {
+        let timestamp = crate::get_timestamp_ms();
+        let stop_time = zeitstempel::now_awake();
+        let precise_timestamp = Utc::now().timestamp_millis() as u64;
+
+        self.set_end(glean, span, extra, timestamp, stop_time, precise_timestamp);
+    }
+
+    fn set_end(
+        &self,
+        glean: &Glean,
+        span: SpanId,
+        mut extra: HashMap<String, String>,
+        timestamp: u64,
+        stop_time: u64,
+        precise_timestamp: u64,
+    ) {
+        let open_span = self.open_spans.lock().unwrap().remove(&span);
+        if !self.should_record(glean) {
+            return;
+        }
+
+        let Some(open_span) = open_span else {
+            record_error(
+                glean,
+                self.meta(),
+                ErrorType::InvalidState,
+                "Span not running",
+                None,
+            );
+            return;
+        };
+
+        if let Some(key) = extra
+            .keys()
+            .find(|key| RESERVED_KEYS.contains(&key.as_str()))
+        {
+            let msg = format!("Extra key {} is reserved for spans", key);
+            record_error(glean, self.meta(), ErrorType::InvalidValue, msg, None);
+            return;
+        }
+
+        // The clock is monotonic, but let's not trust it blindly.
+        let duration = stop_time.saturating_sub(open_span.start_time);
+        extra.extend(span_extra(span, open_span.parent, "end"));
+        extra.insert(DURATION_KEY.to_string(), duration.to_string());
+        self.record(glean, timestamp, extra, precise_timestamp);
+    }
+
+    /// Records one of the span's events and triggers an upload if that submitted a ping.
+    fn record(
+        &self,
+        glean: &Glean,
+        timestamp: u64,
+        extra: HashMap<String, String>,
+        precise_timestamp: u64,
+    ) {
+        let sent = self
+            .event
+            .record_sync(glean, timestamp, extra, precise_timestamp);
+        if sent {
+            let state = crate::global_state().lock().unwrap();
+            if let Err(e) = state.callbacks.trigger_upload() {
+                log::error!("Triggering upload failed. Error: {}", e);
+            }
+        }
+    }
+
+    /// **Test-only API (exported for FFI purposes).**
+    ///
+    /// Get the vector of currently stored span records for this metric.
+    #[doc(hidden)]
+    pub fn get_value<'a, S: Into<Option<&'a str>>>(
+        &self,
+        glean: &Glean,
+        ping_name: S,
+    ) -> Option<Vec<RecordedEvent>> {
+        self.event.get_value(glean, ping_name)
+    }
+
+    /// **Exported for test purposes.**
+    ///
+    /// Gets the number of recorded errors for the given metric and error type.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - The type of error
+    ///
+    /// # Returns
+    ///
+    /// The number of errors reported.
+    pub fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32 {
+        crate::block_on_dispatcher();
+
+        crate::core::with_glean(|glean| {
+            test_get_num_recorded_errors(glean, self.meta(), error).unwrap_or(0)
+        })
+    }
+}
+
+/// Gets the extras identifying a span record.
+fn span_extra(span: SpanId, parent: Option<SpanId>, phase: &str) -> HashMap<String, String> {
+    let mut extra = HashMap::new();
+    extra.insert(SPAN_ID_KEY.to_string(), span.id.to_string());
+    if let Some(parent) = parent {
+        extra.insert(PARENT_ID_KEY.to_string(), parent.id.to_string());
+    }
+    extra.insert(PHASE_KEY.to_string(), phase.to_string());
+    extra
+}
+
+impl TestGetValue for SpanMetric {
+    type Output = Vec<RecordedEvent>;
+
+    /// **Test-only API (exported for FFI purposes).**
+    ///
+    /// Get the vector of currently stored span records for this metric.
+    ///
+    /// This doesn't clear the stored value.
+    ///
+    /// # Arguments
+    ///
+    /// * `ping_name` - the optional name of the ping to retrieve the metric
+    ///                 for. Defaults to the first value in `send_in_pings`.
+    fn test_get_value(&self, ping_name: Option<String>) -> Option<Vec<RecordedEvent>> {
+        crate::block_on_dispatcher();
+        crate::core::with_glean(|glean| self.get_value(glean, ping_name.as_deref()))
+    }
+}
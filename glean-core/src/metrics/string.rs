@@ -169,6 +169,8 @@ mod test {
             disabled: false,
             dynamic_label: None,
             in_session: false,
+            ldp_epsilon: None,
+            ldp_max_increment: None,
         });
 
         let sample_string = "0123456789".repeat(26);
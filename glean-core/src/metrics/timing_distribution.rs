@@ -20,12 +20,16 @@ use crate::Glean;
 use crate::{CommonMetricData, TestGetValue};
 
 // The base of the logarithm used to determine bucketing
-const LOG_BASE: f64 = 2.0;
+pub(crate) const LOG_BASE: f64 = 2.0;
 
-// The buckets per each order of magnitude of the logarithm.
-const BUCKETS_PER_MAGNITUDE: f64 = 8.0;
+// The default buckets per each order of magnitude of the logarithm.
+pub(crate) const BUCKETS_PER_MAGNITUDE: u32 = 8;
 
-// Maximum time, which means we retain a maximum of 316 buckets.
+// The range of buckets per order of magnitude a metric can be configured with.
+const MIN_BUCKETS_PER_MAGNITUDE: u32 = 1;
+const MAX_BUCKETS_PER_MAGNITUDE: u32 = 64;
+
+// Default maximum time, which means we retain a maximum of 316 buckets.
 // It is automatically adjusted based on the `time_unit` parameter
 // so that:
 //
@@ -63,6 +67,8 @@ impl From<usize> for TimerId {
 pub struct TimingDistributionMetric {
     meta: Arc<CommonMetricDataInternal>,
     time_unit: TimeUnit,
+    buckets_per_magnitude: u32,
+    max_sample_time: u64,
     next_id: Arc<AtomicUsize>,
     start_times: Arc<Mutex<HashMap<TimerId, u64>>>,
 }
@@ -73,15 +79,20 @@ impl ::malloc_size_of::MallocSizeOf for TimingDistributionMetric {
         // `size_of` should only be called on the main thread to avoid double-counting.
         self.meta.size_of(ops)
             + self.time_unit.size_of(ops)
+            + self.buckets_per_magnitude.size_of(ops)
+            + self.max_sample_time.size_of(ops)
             + self.next_id.size_of(ops)
             + self.start_times.lock().unwrap().size_of(ops)
     }
 }
 
-/// Create a snapshot of the histogram with a time unit.
+/// Create a snapshot of the histogram, with the custom maximum in nanoseconds it was recorded with, if any.
 ///
 /// The snapshot can be serialized into the payload format.
-pub(crate) fn snapshot(hist: &Histogram<Functional>) -> DistributionData {
+pub(crate) fn snapshot(
+    hist: &Histogram<Functional>,
+    max_sample_time: Option<u64>,
+) -> DistributionData {
     DistributionData {
         // **Caution**: This cannot use `Histogram::snapshot_values` and needs to use the more
         // specialized snapshot function.
@@ -92,6 +103,15 @@ pub(crate) fn snapshot(hist: &Histogram<Functional>) -> DistributionData {
             .collect(),
         sum: hist.sum() as i64,
         count: hist.count() as i64,
+        buckets_per_magnitude: Some(hist.buckets_per_magnitude(LOG_BASE))
+            .filter(|&n| n != BUCKETS_PER_MAGNITUDE),
+        upper_bounds: hist
+            .bucket_upper_bounds()
+            .into_iter()
+            .map(|(k, v)| (k as i64, v as i64))
+            .collect(),
+        max_sample_time,
+        max_bytes: None,
     }
 }
 
@@ -106,6 +126,8 @@ impl MetricType for TimingDistributionMetric {
         Self {
             meta: Arc::new(meta),
             time_unit: self.time_unit,
+            buckets_per_magnitude: self.buckets_per_magnitude,
+            max_sample_time: self.max_sample_time,
             next_id: Arc::new(AtomicUsize::new(1)),
             start_times: Arc::new(Mutex::new(Default::default())),
         }
@@ -117,6 +139,8 @@ impl MetricType for TimingDistributionMetric {
         Self {
             meta: Arc::new(meta),
             time_unit: self.time_unit,
+            buckets_per_magnitude: self.buckets_per_magnitude,
+            max_sample_time: self.max_sample_time,
             next_id: Arc::new(AtomicUsize::new(1)),
             start_times: Arc::new(Mutex::new(Default::default())),
         }
@@ -130,14 +154,131 @@ impl MetricType for TimingDistributionMetric {
 impl TimingDistributionMetric {
     /// Creates a new timing distribution metric.
     pub fn new(meta: CommonMetricData, time_unit: TimeUnit) -> Self {
+        Self::new_with_bucketing(meta, time_unit, None, None)
+    }
+
+    /// Creates a new timing distribution metric with a custom bucketing.
+    ///
+    /// # Arguments
+    ///
+    /// * `meta` - The metadata for the metric.
+    /// * `time_unit` - The unit samples are recorded in.
+    /// * `buckets_per_magnitude` - The number of buckets per power of 2,
+    ///   between 1 and 64. Higher values give a better precision.
+    ///   Defaults to 8 if `None`.
+    /// * `max_sample_time` - The longest sample that can be recorded, in `time_unit`.
+    ///   Longer samples are truncated. Defaults to 600,000,000,000 if `None`,
+    ///   which is 10 minutes for a `time_unit` of nanoseconds.
+    ///
+    /// ## Notes
+    ///
+    /// Invalid values fall back to the defaults.
+    /// A maximum sample time too large to be represented in nanoseconds is clamped.
+    pub fn new_with_bucketing(
+        meta: CommonMetricData,
+        time_unit: TimeUnit,
+        buckets_per_magnitude: Option<u32>,
+        max_sample_time: Option<u64>,
+    ) -> Self {
+        let meta: CommonMetricDataInternal = meta.into();
+        let buckets_per_magnitude = buckets_per_magnitude.unwrap_or(BUCKETS_PER_MAGNITUDE);
+        let buckets_per_magnitude = if (MIN_BUCKETS_PER_MAGNITUDE..=MAX_BUCKETS_PER_MAGNITUDE)
+            .contains(&buckets_per_magnitude)
+        {
+            buckets_per_magnitude
+        } else {
+            log::error!(
+                "Invalid buckets per magnitude {} for {}, using the default",
+                buckets_per_magnitude,
+                meta.base_identifier()
+            );
+            BUCKETS_PER_MAGNITUDE
+        };
+        // A custom maximum needs to fit into a `u64` when converted to nanoseconds.
+        let max_sample_time = match max_sample_time {
+            None => MAX_SAMPLE_TIME,
+            Some(0) => {
+                log::error!(
+                    "Invalid maximum sample time 0 for {}, using the default",
+                    meta.base_identifier()
+                );
+                MAX_SAMPLE_TIME
+            }
+            Some(max_sample_time) => {
+                let limit = u64::MAX / time_unit.as_nanos(1);
+                if max_sample_time > limit {
+                    log::error!(
+                        "Maximum sample time {} for {} is too large, using {}",
+                        max_sample_time,
+                        meta.base_identifier(),
+                        limit
+                    );
+                }
+                max_sample_time.min(limit)
+            }
+        };
+
         Self {
-            meta: Arc::new(meta.into()),
+            meta: Arc::new(meta),
             time_unit,
+            buckets_per_magnitude,
+            max_sample_time,
             next_id: Arc::new(AtomicUsize::new(1)),
             start_times: Arc::new(Mutex::new(Default::default())),
         }
     }
 
+    /// Creates an empty histogram with the bucketing of this metric.
+    fn new_histogram(&self) -> Histogram<Functional> {
+        Histogram::functional(LOG_BASE, self.buckets_per_magnitude as f64)
+    }
+
+    /// Gets the histogram to record into from the stored value.
+    ///
+    /// A histogram stored with a different bucketing, e.g. by an older version of the metric,
+    /// is re-bucketed, as samples can only be merged into matching buckets.
+    ///
+    /// # Returns
+    ///
+    /// The histogram and whether it had to be re-bucketed.
+    fn stored_histogram(&self, old_value: Option<Metric>) -> (Histogram<Functional>, bool) {
+        let hist = match old_value {
+            Some(Metric::TimingDistribution(hist))
+            | Some(Metric::BoundedTimingDistribution(hist, _)) => hist,
+            _ => return (self.new_histogram(), false),
+        };
+
+        if hist.buckets_per_magnitude(LOG_BASE) == self.buckets_per_magnitude {
+            (hist, false)
+        } else {
+            (
+                hist.rebucket(LOG_BASE, self.buckets_per_magnitude as f64),
+                true,
+            )
+        }
+    }
+
+    /// Wraps a histogram into the metric to store, along with a custom maximum in nanoseconds.
+    fn to_metric(&self, hist: Histogram<Functional>) -> Metric {
+        if self.max_sample_time == MAX_SAMPLE_TIME {
+            Metric::TimingDistribution(hist)
+        } else {
+            let max_sample_time = self.time_unit.as_nanos(self.max_sample_time);
+            Metric::BoundedTimingDistribution(hist, max_sample_time)
+        }
+    }
+
+    /// Records an error for a stored histogram that had to be re-bucketed.
+    fn record_rebucketed_error(&self, glean: &Glean) {
+        record_error(
+            glean,
+            &self.meta,
+            ErrorType::InvalidState,
+            "Re-bucketed samples stored with a different bucketing",
+            None,
+        );
+    }
+
     /// Starts tracking time for the provided metric.
     ///
     /// This records an error if it’s already tracking time (i.e.
@@ -240,7 +381,7 @@ impl TimingDistributionMetric {
         };
 
         let min_sample_time = self.time_unit.as_nanos(1);
-        let max_sample_time = self.time_unit.as_nanos(MAX_SAMPLE_TIME);
+        let max_sample_time = self.time_unit.as_nanos(self.max_sample_time);
 
         duration = if duration < min_sample_time {
             // If measurement is less than the minimum, just truncate. This is
@@ -268,17 +409,16 @@ impl TimingDistributionMetric {
         // We do the same for counters.
         // This should never happen in real app usage.
         if let Some(storage) = glean.storage_opt() {
-            storage.record_with(glean, &self.meta, |old_value| match old_value {
-                Some(Metric::TimingDistribution(mut hist)) => {
-                    hist.accumulate(duration);
-                    Metric::TimingDistribution(hist)
-                }
-                _ => {
-                    let mut hist = Histogram::functional(LOG_BASE, BUCKETS_PER_MAGNITUDE);
-                    hist.accumulate(duration);
-                    Metric::TimingDistribution(hist)
-                }
+            let mut rebucketed = false;
+            storage.record_with(glean, &self.meta, |old_value| {
+                let (mut hist, was_rebucketed) = self.stored_histogram(old_value);
+                rebucketed = was_rebucketed;
+                hist.accumulate(duration);
+                self.to_metric(hist)
             });
+            if rebucketed {
+                self.record_rebucketed_error(glean);
+            }
         } else {
             log::warn!(
                 "Couldn't get storage. Can't record timing distribution '{}'.",
@@ -327,7 +467,7 @@ impl TimingDistributionMetric {
     ///
     /// Discards any negative value in `samples` and report an [`ErrorType::InvalidValue`]
     /// for each of them. Reports an [`ErrorType::InvalidOverflow`] error for samples that
-    /// are longer than `MAX_SAMPLE_TIME`.
+    /// are longer than the maximum sample time of the metric.
     pub fn accumulate_samples(&self, samples: Vec<i64>) {
         let metric = self.clone();
         crate::launch_with_glean(move |glean| metric.accumulate_samples_sync(glean, &samples))
@@ -355,7 +495,7 @@ impl TimingDistributionMetric {
     ///
     /// Discards any negative value and reports an [`ErrorType::InvalidValue`].
     /// Reports an [`ErrorType::InvalidOverflow`] error if the sample is longer than
-    /// `MAX_SAMPLE_TIME`.
+    /// the maximum sample time of the metric.
     pub fn accumulate_single_sample(&self, sample: i64) {
         let metric = self.clone();
         crate::launch_with_glean(move |glean| metric.accumulate_samples_sync(glean, &[sample]))
@@ -373,13 +513,13 @@ impl TimingDistributionMetric {
 
         let mut num_negative_samples = 0;
         let mut num_too_long_samples = 0;
-        let max_sample_time = self.time_unit.as_nanos(MAX_SAMPLE_TIME);
+        let max_sample_time = self.time_unit.as_nanos(self.max_sample_time);
+
+        let mut rebucketed = false;
 
         glean.storage().record_with(glean, &self.meta, |old_value| {
-            let mut hist = match old_value {
-                Some(Metric::TimingDistribution(hist)) => hist,
-                _ => Histogram::functional(LOG_BASE, BUCKETS_PER_MAGNITUDE),
-            };
+            let (mut hist, was_rebucketed) = self.stored_histogram(old_value);
+            rebucketed = was_rebucketed;
 
             for &sample in samples.iter() {
                 if sample < 0 {
@@ -388,13 +528,13 @@ impl TimingDistributionMetric {
                     let mut sample = sample as u64;
 
                     // Check the range prior to converting the incoming unit to
-                    // nanoseconds, so we can compare against the constant
-                    // MAX_SAMPLE_TIME.
+                    // nanoseconds, so we can compare against the maximum
+                    // sample time of the metric.
                     if sample == 0 {
                         sample = 1;
-                    } else if sample > MAX_SAMPLE_TIME {
+                    } else if sample > self.max_sample_time {
                         num_too_long_samples += 1;
-                        sample = MAX_SAMPLE_TIME;
+                        sample = self.max_sample_time;
                     }
 
                     sample = self.time_unit.as_nanos(sample);
@@ -403,9 +543,13 @@ impl TimingDistributionMetric {
                 }
             }
 
-            Metric::TimingDistribution(hist)
+            self.to_metric(hist)
         });
 
+        if rebucketed {
+            self.record_rebucketed_error(glean);
+        }
+
         if num_negative_samples > 0 {
             let msg = format!("Accumulated {} negative samples", num_negative_samples);
             record_error(
@@ -441,7 +585,7 @@ impl TimingDistributionMetric {
     /// ## Notes
     ///
     /// Reports an [`ErrorType::InvalidOverflow`] error for samples that
-    /// are longer than `MAX_SAMPLE_TIME`.
+    /// are longer than the maximum sample time of the metric.
     pub fn accumulate_raw_samples_nanos(&self, samples: Vec<u64>) {
         let metric = self.clone();
         crate::launch_with_glean(move |glean| {
@@ -464,7 +608,7 @@ impl TimingDistributionMetric {
     /// ## Notes
     ///
     /// Reports an [`ErrorType::InvalidOverflow`] error if `duration` is longer than
-    /// `MAX_SAMPLE_TIME`.
+    /// the maximum sample time of the metric.
     ///
     /// The API client is responsible for ensuring that `duration` is derived from a
     /// monotonic clock source that behaves consistently over computer sleep across
@@ -491,13 +635,13 @@ impl TimingDistributionMetric {
 
         let mut num_too_long_samples = 0;
         let min_sample_time = self.time_unit.as_nanos(1);
-        let max_sample_time = self.time_unit.as_nanos(MAX_SAMPLE_TIME);
+        let max_sample_time = self.time_unit.as_nanos(self.max_sample_time);
+
+        let mut rebucketed = false;
 
         glean.storage().record_with(glean, &self.meta, |old_value| {
-            let mut hist = match old_value {
-                Some(Metric::TimingDistribution(hist)) => hist,
-                _ => Histogram::functional(LOG_BASE, BUCKETS_PER_MAGNITUDE),
-            };
+            let (mut hist, was_rebucketed) = self.stored_histogram(old_value);
+            rebucketed = was_rebucketed;
 
             for &sample in samples.iter() {
                 let mut sample = sample;
@@ -513,9 +657,13 @@ impl TimingDistributionMetric {
                 hist.accumulate(sample);
             }
 
-            Metric::TimingDistribution(hist)
+            self.to_metric(hist)
         });
 
+        if rebucketed {
+            self.record_rebucketed_error(glean);
+        }
+
         if num_too_long_samples > 0 {
             let msg = format!(
                 "{} samples are longer than the maximum of {}",
@@ -548,7 +696,8 @@ impl TimingDistributionMetric {
             &self.meta.identifier(glean),
             self.meta.inner.lifetime,
         ) {
-            Some(Metric::TimingDistribution(hist)) => Some(snapshot(&hist)),
+            Some(Metric::TimingDistribution(hist)) => Some(snapshot(&hist, None)),
+            Some(Metric::BoundedTimingDistribution(hist, max)) => Some(snapshot(&hist, Some(max))),
             _ => None,
         }
     }
@@ -572,6 +721,24 @@ impl TimingDistributionMetric {
         })
     }
 
+    /// **Exported for test purposes.**
+    ///
+    /// Estimates a percentile of the currently stored distribution.
+    /// See [`DistributionData::percentile`].
+    ///
+    /// # Arguments
+    ///
+    /// * `percentile` - The percentile to estimate, between `0` and `100`.
+    /// * `ping_name` - the optional name of the ping to retrieve the metric
+    ///                 for. Defaults to the first value in `send_in_pings`.
+    ///
+    /// # Returns
+    ///
+    /// The estimate or `None` if nothing is stored.
+    pub fn test_get_percentile(&self, percentile: f64, ping_name: Option<String>) -> Option<i64> {
+        self.test_get_value(ping_name)?.percentile(percentile)
+    }
+
     /// **Experimental:** Start a new histogram buffer associated with this timing distribution metric.
     ///
     /// A histogram buffer accumulates in-memory.
@@ -584,7 +751,7 @@ impl TimingDistributionMetric {
         let metric = self.clone();
         crate::launch_with_glean(move |glean| {
             if errors > 0 {
-                let max_sample_time = metric.time_unit.as_nanos(MAX_SAMPLE_TIME);
+                let max_sample_time = metric.time_unit.as_nanos(metric.max_sample_time);
                 let msg = format!(
                     "{} samples are longer than the maximum of {}",
                     errors, max_sample_time
@@ -598,17 +765,18 @@ impl TimingDistributionMetric {
                 );
             }
 
+            let mut rebucketed = false;
             glean
                 .storage()
-                .record_with(glean, &metric.meta, move |old_value| {
-                    let mut hist = match old_value {
-                        Some(Metric::TimingDistribution(hist)) => hist,
-                        _ => Histogram::functional(LOG_BASE, BUCKETS_PER_MAGNITUDE),
-                    };
-
+                .record_with(glean, &metric.meta, |old_value| {
+                    let (mut hist, was_rebucketed) = metric.stored_histogram(old_value);
+                    rebucketed = was_rebucketed;
                     hist.merge(&histogram);
-                    Metric::TimingDistribution(hist)
+                    metric.to_metric(hist)
                 });
+            if rebucketed {
+                metric.record_rebucketed_error(glean);
+            }
         });
     }
 }
@@ -650,7 +818,7 @@ pub struct LocalTimingDistribution<'a> {
 impl<'a> LocalTimingDistribution<'a> {
     /// Create a new histogram buffer referencing the timing distribution it will record into.
     fn new(metric: &'a TimingDistributionMetric) -> Self {
-        let histogram = Histogram::functional(LOG_BASE, BUCKETS_PER_MAGNITUDE);
+        let histogram = metric.new_histogram();
         Self {
             histogram,
             metric,
@@ -667,13 +835,13 @@ impl<'a> LocalTimingDistribution<'a> {
     /// Accumulation happens in-memory only.
     pub fn accumulate(&mut self, sample: u64) {
         // Check the range prior to converting the incoming unit to
-        // nanoseconds, so we can compare against the constant
-        // MAX_SAMPLE_TIME.
+        // nanoseconds, so we can compare against the maximum
+        // sample time of the metric.
         let sample = if sample == 0 {
             1
-        } else if sample > MAX_SAMPLE_TIME {
+        } else if sample > self.metric.max_sample_time {
             self.errors += 1;
-            MAX_SAMPLE_TIME
+            self.metric.max_sample_time
         } else {
             sample
         };
@@ -715,7 +883,7 @@ mod test {
             hist.accumulate(i);
         }
 
-        let snap = snapshot(&hist);
+        let snap = snapshot(&hist, None);
 
         let expected_json = json!({
             "sum": 55,
@@ -747,7 +915,7 @@ mod test {
         hist.accumulate(1116);
         hist.accumulate(1448);
 
-        let snap = snapshot(&hist);
+        let snap = snapshot(&hist, None);
 
         let expected_json = json!({
             "sum": 4612,
@@ -0,0 +1,335 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Filters applied to the metrics of a ping when it is collected,
+//! to leave data out of the payload that carries little information.
+
+use malloc_size_of_derive::MallocSizeOf;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+
+/// The label that labels beyond the limit of a labeled metric are summed up in.
+const OTHER_LABEL: &str = "__other__";
+
+/// Filters applied to the metrics of a ping when it is collected.
+///
+/// The default keeps all metrics.
+/// Filters can be declared for a ping in its `PingOptions`
+/// and overridden through Server Knobs.
+///
+/// The expected format of the Server Knobs override is:
+///
+/// ```json
+/// {
+///     "ping_snapshot_filters": {
+///         "ping-name": {
+///             "exclude_default_values": true,
+///             "max_labels_per_metric": 10,
+///             "strip_empty_distributions": true
+///         }
+///     }
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, MallocSizeOf)]
+#[serde(default)]
+pub struct PingSnapshotFilter {
+    /// Leave out values that are the default of their type:
+    /// zero numbers, `false`, empty strings and empty lists,
+    /// and objects that only contain default values.
+    /// This applies to the labels of labeled metrics individually.
+    pub exclude_default_values: bool,
+
+    /// The maximum number of labels reported for each labeled metric.
+    ///
+    /// For labeled counters, the labels with the largest values are kept
+    /// and the values of the other labels are summed up in the `__other__` label,
+    /// which counts towards the limit.
+    /// For other labeled metrics, the first labels in alphabetical order are kept.
+    /// Dual-labeled metrics are not limited. A limit of 0 is ignored.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_labels_per_metric: Option<u32>,
+
+    /// Leave out timing, memory and custom distributions without any samples.
+    pub strip_empty_distributions: bool,
+}
+
+impl PingSnapshotFilter {
+    /// Applies the filter to a snapshot of metrics, as returned by
+    /// [`StorageManager::snapshot_as_json`](crate::storage::StorageManager::snapshot_as_json).
+    ///
+    /// Metrics with local differential privacy, listed in the `ldp_epsilon` section,
+    /// are never filtered: leaving out some of their noisy values would bias the aggregates.
+    ///
+    /// # Returns
+    ///
+    /// The filtered snapshot, or `None` if no metrics are left.
+    pub(crate) fn apply(&self, snapshot: JsonValue) -> Option<JsonValue> {
+        if *self == Self::default() {
+            return Some(snapshot);
+        }
+
+        let JsonValue::Object(mut sections) = snapshot else {
+            return Some(snapshot);
+        };
+        let privatized: Vec<String> = sections
+            .get("ldp_epsilon")
+            .and_then(JsonValue::as_object)
+            .map(|epsilons| epsilons.keys().cloned().collect())
+            .unwrap_or_default();
+
+        for (section, metrics) in sections.iter_mut() {
+            // Not a metric type, but reported alongside them.
+            if section == "ldp_epsilon" {
+                continue;
+            }
+            let Some(metrics) = metrics.as_object_mut() else {
+                continue;
+            };
+
+            // How many levels of labels are between a metric and its values.
+            let depth = if section.starts_with("dual_labeled_") {
+                2
+            } else if section.starts_with("labeled_") {
+                1
+            } else {
+                0
+            };
+            let is_distribution = section.ends_with("timing_distribution")
+                || section.ends_with("memory_distribution")
+                || section.ends_with("custom_distribution");
+            let is_counter = section.ends_with("counter") || section.ends_with("counter64");
+
+            let exempt: Vec<(String, JsonValue)> = privatized
+                .iter()
+                .filter_map(|id| metrics.remove_entry(id))
+                .collect();
+
+            if self.exclude_default_values {
+                retain_values(metrics, depth, &|value| !is_default(value));
+            }
+            if self.strip_empty_distributions && is_distribution {
+                retain_values(metrics, depth, &|value| !is_empty_distribution(value));
+            }
+            if let (Some(max_labels), 1) = (self.max_labels_per_metric, depth) {
+                for labels in metrics.values_mut().filter_map(JsonValue::as_object_mut) {
+                    limit_labels(labels, max_labels as usize, is_counter);
+                }
+            }
+
+            metrics.extend(exempt);
+        }
+
+        sections.retain(|_, metrics| metrics.as_object().is_none_or(|m| !m.is_empty()));
+
+        if sections.is_empty() {
+            None
+        } else {
+            Some(JsonValue::Object(sections))
+        }
+    }
+}
+
+/// Removes values for which `keep` returns `false`,
+/// and labels and metrics that are left without values.
+///
+/// `depth` is the number of levels of labels between the entries of `map` and their values.
+fn retain_values(
+    map: &mut Map<String, JsonValue>,
+    depth: usize,
+    keep: &dyn Fn(&JsonValue) -> bool,
+) {
+    map.retain(|_, value| {
+        if depth == 0 {
+            return keep(value);
+        }
+        match value.as_object_mut() {
+            Some(labels) => {
+                retain_values(labels, depth - 1, keep);
+                !labels.is_empty()
+            }
+            None => true,
+        }
+    });
+}
+
+/// Whether a value is the default of its type.
+fn is_default(value: &JsonValue) -> bool {
+    match value {
+        JsonValue::Null => true,
+        JsonValue::Bool(b) => !b,
+        JsonValue::Number(n) => n.as_f64() == Some(0.0),
+        JsonValue::String(s) => s.is_empty(),
+        JsonValue::Array(a) => a.is_empty(),
+        JsonValue::Object(o) => o.values().all(is_default),
+    }
+}
+
+/// Whether a value is a distribution without samples.
+fn is_empty_distribution(value: &JsonValue) -> bool {
+    value
+        .get("values")
+        .and_then(JsonValue::as_object)
+        .is_some_and(|buckets| buckets.values().all(|count| count.as_i64() == Some(0)))
+}
+
+/// Limits the labels of a labeled metric to `max_labels`.
+///
+/// For counters, the labels with the largest values are kept
+/// and the other labels are summed up in the `__other__` label.
+/// Otherwise the first labels in alphabetical order are kept.
+fn limit_labels(labels: &mut Map<String, JsonValue>, max_labels: usize, is_counter: bool) {
+    if max_labels == 0 || labels.len() <= max_labels {
+        return;
+    }
+
+    let mut sorted: Vec<(String, JsonValue)> = std::mem::take(labels).into_iter().collect();
+    // Only counts add up to a meaningful total.
+    if !is_counter || !sorted.iter().all(|(_, value)| value.is_number()) {
+        sorted.sort_by(|(a, _), (b, _)| a.cmp(b));
+        labels.extend(sorted.into_iter().take(max_labels));
+        return;
+    }
+
+    // Sort by value, largest first. Ties are broken by label to keep the result stable.
+    sorted.sort_by(|(a_label, a), (b_label, b)| {
+        let a = a.as_f64().unwrap_or_default();
+        let b = b.as_f64().unwrap_or_default();
+        b.total_cmp(&a).then_with(|| a_label.cmp(b_label))
+    });
+
+    // Make room for the `__other__` label.
+    let other = sorted
+        .iter()
+        .position(|(label, _)| label == OTHER_LABEL)
+        .map(|index| sorted.remove(index).1);
+    let rest = sorted.split_off(max_labels - 1);
+    labels.extend(sorted);
+
+    let other = rest.iter().map(|(_, value)| value).chain(&other);
+    let all_integers = other.clone().all(JsonValue::is_i64);
+    let other = if all_integers {
+        JsonValue::from(other.filter_map(JsonValue::as_i64).sum::<i64>())
+    } else {
+        JsonValue::from(other.filter_map(JsonValue::as_f64).sum::<f64>())
+    };
+    labels.insert(OTHER_LABEL.to_string(), other);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn default_values_are_excluded() {
+        let filter = PingSnapshotFilter {
+            exclude_default_values: true,
+            ..Default::default()
+        };
+        let snapshot = json!({
+            "counter": { "a.zero": 0, "a.one": 1 },
+            "boolean": { "a.false": false },
+            "string": { "a.empty": "" },
+            "rate": { "a.rate": { "numerator": 0, "denominator": 0 } },
+            "labeled_counter": { "a.labeled": { "x": 0, "y": 2 }, "a.zeros": { "x": 0 } },
+            "dual_labeled_counter": { "a.dual": { "k": { "c": 0 } } },
+        });
+
+        assert_eq!(
+            Some(json!({
+                "counter": { "a.one": 1 },
+                "labeled_counter": { "a.labeled": { "y": 2 } },
+            })),
+            filter.apply(snapshot)
+        );
+
+        assert_eq!(None, filter.apply(json!({ "counter": { "a.zero": 0 } })));
+    }
+
+    #[test]
+    fn privatized_metrics_are_not_filtered() {
+        let filter = PingSnapshotFilter {
+            exclude_default_values: true,
+            max_labels_per_metric: Some(1),
+            ..Default::default()
+        };
+        let snapshot = json!({
+            "counter": { "a.noisy": 0, "a.zero": 0 },
+            "labeled_boolean": { "a.noisy_labeled": { "x": false, "y": true } },
+            "ldp_epsilon": { "a.noisy": 1.0, "a.noisy_labeled": 1.0 },
+        });
+
+        assert_eq!(
+            Some(json!({
+                "counter": { "a.noisy": 0 },
+                "labeled_boolean": { "a.noisy_labeled": { "x": false, "y": true } },
+                "ldp_epsilon": { "a.noisy": 1.0, "a.noisy_labeled": 1.0 },
+            })),
+            filter.apply(snapshot)
+        );
+    }
+
+    #[test]
+    fn empty_distributions_are_stripped() {
+        let filter = PingSnapshotFilter {
+            strip_empty_distributions: true,
+            ..Default::default()
+        };
+        let snapshot = json!({
+            "counter": { "a.zero": 0 },
+            "timing_distribution": {
+                "a.empty": { "values": {}, "sum": 0 },
+                "a.zeros": { "values": { "1": 0 }, "sum": 0 },
+                "a.full": { "values": { "1": 1 }, "sum": 1 },
+            },
+            "labeled_custom_distribution": {
+                "a.labeled": { "x": { "values": {}, "sum": 0 } },
+            },
+        });
+
+        assert_eq!(
+            Some(json!({
+                "counter": { "a.zero": 0 },
+                "timing_distribution": { "a.full": { "values": { "1": 1 }, "sum": 1 } },
+            })),
+            filter.apply(snapshot)
+        );
+    }
+
+    #[test]
+    fn labels_are_limited() {
+        let filter = PingSnapshotFilter {
+            max_labels_per_metric: Some(3),
+            ..Default::default()
+        };
+        let snapshot = json!({
+            "labeled_counter": {
+                "a.counter": { "a": 1, "b": 5, "c": 3, "d": 2, "__other__": 1 },
+                "a.small": { "a": 1 },
+            },
+            "labeled_boolean": {
+                "a.boolean": { "d": true, "c": true, "b": false, "a": true },
+            },
+            "labeled_quantity": {
+                "a.quantity": { "a": 1, "b": 5, "c": 3, "d": 2 },
+            },
+        });
+
+        assert_eq!(
+            Some(json!({
+                "labeled_counter": {
+                    "a.counter": { "b": 5, "c": 3, "__other__": 4 },
+                    "a.small": { "a": 1 },
+                },
+                "labeled_boolean": {
+                    "a.boolean": { "a": true, "b": false, "c": true },
+                },
+                "labeled_quantity": {
+                    "a.quantity": { "a": 1, "b": 5, "c": 3 },
+                },
+            })),
+            filter.apply(snapshot)
+        );
+    }
+}
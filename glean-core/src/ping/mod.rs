@@ -9,15 +9,20 @@ use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
 
 use log::info;
-use serde_json::{json, Value as JsonValue};
+use serde_json::{json, Map, Value as JsonValue};
 
 use crate::common_metric_data::{CommonMetricData, Lifetime};
 use crate::metrics::{CounterMetric, DatetimeMetric, Metric, MetricType, PingType, TimeUnit};
+use crate::pseudonymous_id;
 use crate::storage::{StorageManager, INTERNAL_STORAGE};
-use crate::upload::{HeaderMap, PingMetadata};
+use crate::upload::{gzip_content, HeaderMap, PingMetadata};
 use crate::util::{get_iso_time_string, local_now_with_offset};
 use crate::{Glean, Result, DELETION_REQUEST_PINGS_DIRECTORY, PENDING_PINGS_DIRECTORY};
 
+pub use filter::PingSnapshotFilter;
+
+mod filter;
+
 /// Holds everything you need to store or send a ping.
 pub struct Ping<'a> {
     /// The unique document id.
@@ -54,6 +59,69 @@ fn merge(a: &mut JsonValue, b: &JsonValue) {
     }
 }
 
+/// Checks whether the compressed `content` of a ping fits into `max_body_size` bytes.
+fn fits(ping_name: &str, content: &JsonValue, max_body_size: usize) -> bool {
+    let body = content.to_string();
+    // Compressing never meaningfully grows the body, so only large bodies need compressing.
+    if body.len() <= max_body_size {
+        return true;
+    }
+    let size = gzip_content(ping_name, body.as_bytes()).map_or(body.len(), |gzipped| gzipped.len());
+    size <= max_body_size
+}
+
+/// Spreads `metrics` over as many copies of `base` as needed to keep each part within `max_body_size`.
+///
+/// `metrics` are pairs of a metric type section and one of its metrics.
+/// Parts are halved until they fit.
+/// A single metric that doesn't fit is kept on its own and will be discarded on upload.
+fn split_metrics(
+    ping_name: &str,
+    base: &JsonValue,
+    metrics: &[(&String, (&String, &JsonValue))],
+    max_body_size: usize,
+    parts: &mut Vec<JsonValue>,
+) {
+    let mut sections = Map::new();
+    for &(section, (identifier, value)) in metrics {
+        sections.entry(section.clone()).or_insert_with(|| json!({}))[identifier] = value.clone();
+    }
+
+    let mut part = base.clone();
+    part["metrics"] = JsonValue::Object(sections);
+    if metrics.len() <= 1 || fits(ping_name, &part, max_body_size) {
+        parts.push(part);
+        return;
+    }
+
+    let (first, second) = metrics.split_at(metrics.len() / 2);
+    split_metrics(ping_name, base, first, max_body_size, parts);
+    split_metrics(ping_name, base, second, max_body_size, parts);
+}
+
+/// Spreads `events` over as many copies of `base` as needed to keep each part within `max_body_size`.
+///
+/// Parts are halved until they fit.
+/// A single event that doesn't fit is kept on its own and will be discarded on upload.
+fn split_events(
+    ping_name: &str,
+    base: &JsonValue,
+    events: &[JsonValue],
+    max_body_size: usize,
+    parts: &mut Vec<JsonValue>,
+) {
+    let mut part = base.clone();
+    part["events"] = json!(events);
+    if events.len() <= 1 || fits(ping_name, &part, max_body_size) {
+        parts.push(part);
+        return;
+    }
+
+    let (first, second) = events.split_at(events.len() / 2);
+    split_events(ping_name, base, first, max_body_size, parts);
+    split_events(ping_name, base, second, max_body_size, parts);
+}
+
 impl Default for PingMaker {
     fn default() -> Self {
         Self::new()
@@ -67,7 +135,9 @@ impl PingMaker {
     }
 
     /// Gets, and then increments, the sequence number for a given ping.
-    fn get_ping_seq(&self, glean: &Glean, storage_name: &str) -> usize {
+    ///
+    /// In `preview` mode the sequence number is not incremented.
+    fn get_ping_seq(&self, glean: &Glean, storage_name: &str, preview: bool) -> usize {
         // Don't attempt to increase sequence number for disabled ping
         if !glean.is_ping_enabled(storage_name) {
             return 0;
@@ -94,17 +164,22 @@ impl PingMaker {
         };
 
         // Increase to next sequence id
-        seq.add_sync(glean, 1);
+        if !preview {
+            seq.add_sync(glean, 1);
+        }
 
         current_seq as usize
     }
 
     /// Gets the formatted start and end times for this ping and update for the next ping.
+    ///
+    /// In `preview` mode the start time of the next ping is not updated.
     fn get_start_end_times(
         &self,
         glean: &Glean,
         storage_name: &str,
         time_unit: TimeUnit,
+        preview: bool,
     ) -> (String, String) {
         let start_time = DatetimeMetric::new(
             CommonMetricData {
@@ -125,7 +200,9 @@ impl PingMaker {
         let end_time_data = local_now_with_offset();
 
         // Update the start time with the current time.
-        start_time.set_sync_chrono(glean, end_time_data);
+        if !preview {
+            start_time.set_sync_chrono(glean, end_time_data);
+        }
 
         // Format the times.
         let start_time_data = get_iso_time_string(start_time_data, time_unit);
@@ -139,10 +216,12 @@ impl PingMaker {
         storage_name: &str,
         reason: Option<&str>,
         precision: TimeUnit,
+        preview: bool,
     ) -> JsonValue {
-        let (start_time, end_time) = self.get_start_end_times(glean, storage_name, precision);
+        let (start_time, end_time) =
+            self.get_start_end_times(glean, storage_name, precision, preview);
         let mut map = json!({
-            "seq": self.get_ping_seq(glean, storage_name),
+            "seq": self.get_ping_seq(glean, storage_name, preview),
             "start_time": start_time,
             "end_time": end_time,
         });
@@ -280,25 +359,137 @@ impl PingMaker {
         reason: Option<&str>,
         doc_id: &'a str,
         url_path: &'a str,
+    ) -> Option<Ping<'a>> {
+        self.assemble(glean, ping, reason, doc_id, url_path, false)
+    }
+
+    /// Collects a snapshot for the given ping, without changing any state.
+    ///
+    /// The payload is assembled just like for [`collect`](Self::collect),
+    /// but no store is cleared and the ping's sequence number and start time are not advanced.
+    ///
+    /// # Arguments
+    ///
+    /// * `glean` - the [`Glean`] instance to collect data from.
+    /// * `ping` - the ping to collect for.
+    /// * `reason` - an optional reason code to include in the ping.
+    ///
+    /// # Returns
+    ///
+    /// The ping payload the ping would have if it were collected now.
+    /// If there is no data stored for the ping, `None` is returned.
+    pub fn preview(
+        &self,
+        glean: &Glean,
+        ping: &PingType,
+        reason: Option<&str>,
+    ) -> Option<JsonValue> {
+        self.assemble(glean, ping, reason, "", "", true)
+            .map(|ping| ping.content)
+    }
+
+    /// Splits an oversized ping into multiple parts.
+    ///
+    /// A ping is oversized if its compressed body exceeds `max_body_size`.
+    /// Its metrics are spread over the first parts and its events over the remaining parts,
+    /// so that each part is small enough to be uploaded.
+    /// All parts share the `ping_info` of the ping, extended with
+    /// the ping's document id as `parent_id`, the 1-based `part` index and the number of `parts`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ping` - the collected ping.
+    /// * `max_body_size` - the maximum size in bytes the compressed ping body may have.
+    ///
+    /// # Returns
+    ///
+    /// The payloads of the parts, or `None` if the ping is not oversized
+    /// or can't be split because it holds only a single metric or event.
+    pub fn split_oversized(&self, ping: &Ping, max_body_size: usize) -> Option<Vec<JsonValue>> {
+        if !ping.includes_info_sections || fits(ping.name, &ping.content, max_body_size) {
+            return None;
+        }
+
+        let mut base = ping.content.clone();
+        let base_obj = base.as_object_mut()?;
+        let events = match base_obj.remove("events") {
+            Some(JsonValue::Array(events)) => events,
+            _ => vec![],
+        };
+        let metrics = match base_obj.remove("metrics") {
+            Some(JsonValue::Object(metrics)) => metrics,
+            _ => Map::new(),
+        };
+        let metrics = metrics
+            .iter()
+            .filter_map(|(section, metrics)| Some((section, metrics.as_object()?)))
+            .flat_map(|(section, metrics)| metrics.iter().map(move |metric| (section, metric)))
+            .collect::<Vec<_>>();
+        if metrics.len() + events.len() <= 1 {
+            return None;
+        }
+
+        let mut parts = vec![];
+        if !metrics.is_empty() {
+            split_metrics(ping.name, &base, &metrics, max_body_size, &mut parts);
+        }
+        if !events.is_empty() {
+            split_events(ping.name, &base, &events, max_body_size, &mut parts);
+        }
+
+        let part_count = parts.len();
+        for (index, part) in parts.iter_mut().enumerate() {
+            let ping_info = &mut part["ping_info"];
+            ping_info["parent_id"] = json!(ping.doc_id);
+            ping_info["part"] = json!(index + 1);
+            ping_info["parts"] = json!(part_count);
+        }
+
+        Some(parts)
+    }
+
+    fn assemble<'a>(
+        &self,
+        glean: &Glean,
+        ping: &'a PingType,
+        reason: Option<&str>,
+        doc_id: &'a str,
+        url_path: &'a str,
+        preview: bool,
     ) -> Option<Ping<'a>> {
         info!("Collecting {}", ping.name());
         let database = glean.storage();
 
         // HACK: Only for metrics pings we add the ping timings.
         // But we want that to persist until the next metrics ping is actually sent.
-        let write_samples = database.write_timings.replace(Vec::with_capacity(64));
-        if !write_samples.is_empty() {
-            glean
-                .database_metrics
-                .write_time
-                .accumulate_samples_sync(glean, &write_samples);
+        if !preview {
+            let write_samples = database.write_timings.replace(Vec::with_capacity(64));
+            if !write_samples.is_empty() {
+                glean
+                    .database_metrics
+                    .write_time
+                    .accumulate_samples_sync(glean, &write_samples);
+            }
+
+            let evicted = database.evicted_entries.replace(0);
+            if evicted > 0 {
+                glean
+                    .database_metrics
+                    .inactive_metrics_evicted
+                    .add_sync(glean, evicted);
+            }
         }
 
-        let mut metrics_data = StorageManager.snapshot_as_json(database, ping.name(), true);
+        let mut metrics_data = StorageManager
+            .snapshot_as_json(database, ping.name(), !preview)
+            .and_then(|snapshot| match glean.ping_snapshot_filter(ping) {
+                Some(filter) => filter.apply(snapshot),
+                None => Some(snapshot),
+            });
 
         let events_data = glean
             .event_storage()
-            .snapshot_as_json(glean, ping.name(), true);
+            .snapshot_as_json(glean, ping.name(), !preview);
 
         // We're adding the metric `glean.ping.uploader_capabilities` the most manual way here.
         // This avoids creating a `StringListMetric` and further indirection.
@@ -376,8 +567,23 @@ impl PingMaker {
         };
 
         let mut json = if ping.include_info_sections() {
-            let ping_info = self.get_ping_info(glean, ping.name(), reason, precision);
-            let client_info = self.get_client_info(glean, ping.include_client_id());
+            let mut ping_info = self.get_ping_info(glean, ping.name(), reason, precision, preview);
+            let mut client_info = self.get_client_info(glean, ping.include_client_id());
+            if let Some(rotation) = ping.pseudonymous_id() {
+                // Anything that continues from one ping to the next would link the windows.
+                ping_info["seq"] = json!(0);
+                ping_info["start_time"] = ping_info["end_time"].clone();
+                if let Some(client_info) = client_info.as_object_mut() {
+                    for field in pseudonymous_id::LINKING_CLIENT_INFO_FIELDS {
+                        client_info.remove(field);
+                    }
+                }
+
+                let now = local_now_with_offset();
+                if let Some(id) = pseudonymous_id::get(glean, ping.name(), rotation, now, preview) {
+                    client_info["pseudonymous_id"] = json!(id.to_string());
+                }
+            }
 
             json!({
                 "ping_info": ping_info,
@@ -536,16 +742,16 @@ mod test {
         let (mut glean, _t) = new_glean(None);
         let ping_maker = PingMaker::new();
 
-        assert_eq!(0, ping_maker.get_ping_seq(&glean, "store1"));
-        assert_eq!(1, ping_maker.get_ping_seq(&glean, "store1"));
+        assert_eq!(0, ping_maker.get_ping_seq(&glean, "store1", false));
+        assert_eq!(1, ping_maker.get_ping_seq(&glean, "store1", false));
 
         glean.set_upload_enabled(false);
-        assert_eq!(0, ping_maker.get_ping_seq(&glean, "store1"));
-        assert_eq!(0, ping_maker.get_ping_seq(&glean, "store1"));
+        assert_eq!(0, ping_maker.get_ping_seq(&glean, "store1", false));
+        assert_eq!(0, ping_maker.get_ping_seq(&glean, "store1", false));
 
         glean.set_upload_enabled(true);
-        assert_eq!(0, ping_maker.get_ping_seq(&glean, "store1"));
-        assert_eq!(1, ping_maker.get_ping_seq(&glean, "store1"));
+        assert_eq!(0, ping_maker.get_ping_seq(&glean, "store1", false));
+        assert_eq!(1, ping_maker.get_ping_seq(&glean, "store1", false));
     }
 
     #[test]
@@ -567,12 +773,13 @@ mod test {
             pings_enabled,
             event_threshold: Some(41),
             session_sample_rate: None,
+            ping_snapshot_filters: HashMap::new(),
         };
         glean.apply_server_knobs_config(config);
 
         // Verify complete config structure appears in ping_info
         let ping_maker = PingMaker::new();
-        let ping_info = ping_maker.get_ping_info(&glean, "store1", None, TimeUnit::Minute);
+        let ping_info = ping_maker.get_ping_info(&glean, "store1", None, TimeUnit::Minute, false);
 
         let server_knobs = &ping_info["server_knobs_config"];
         assert_eq!(server_knobs["metrics_enabled"]["test.counter"], true);
@@ -585,7 +792,7 @@ mod test {
         let (glean, _t) = new_glean(None);
 
         let ping_maker = PingMaker::new();
-        let ping_info = ping_maker.get_ping_info(&glean, "store1", None, TimeUnit::Minute);
+        let ping_info = ping_maker.get_ping_info(&glean, "store1", None, TimeUnit::Minute, false);
 
         assert!(ping_info.get("server_knobs_config").is_none());
     }
@@ -608,8 +815,8 @@ mod test {
 
         // Verify config appears in multiple different pings
         let ping_maker = PingMaker::new();
-        let ping_info1 = ping_maker.get_ping_info(&glean, "store1", None, TimeUnit::Minute);
-        let ping_info2 = ping_maker.get_ping_info(&glean, "store2", None, TimeUnit::Minute);
+        let ping_info1 = ping_maker.get_ping_info(&glean, "store1", None, TimeUnit::Minute, false);
+        let ping_info2 = ping_maker.get_ping_info(&glean, "store2", None, TimeUnit::Minute, false);
 
         assert_eq!(
             ping_info1["server_knobs_config"]["metrics_enabled"]["test.counter"],
@@ -639,7 +846,7 @@ mod test {
         glean.apply_server_knobs_config(config);
 
         let ping_maker = PingMaker::new();
-        let ping_info = ping_maker.get_ping_info(&glean, "store1", None, TimeUnit::Minute);
+        let ping_info = ping_maker.get_ping_info(&glean, "store1", None, TimeUnit::Minute, false);
 
         let server_knobs = &ping_info["server_knobs_config"];
         // metrics_enabled should be present
@@ -648,4 +855,120 @@ mod test {
         assert!(server_knobs.get("pings_enabled").is_none());
         assert!(server_knobs.get("event_threshold").is_none());
     }
+
+    fn test_ping(content: JsonValue) -> Ping<'static> {
+        Ping {
+            doc_id: "parent",
+            name: "store1",
+            url_path: "/submit/app/store1/1/parent",
+            content,
+            headers: HeaderMap::new(),
+            includes_info_sections: true,
+            schedules_pings: vec![],
+            uploader_capabilities: vec![],
+        }
+    }
+
+    #[test]
+    fn oversized_pings_are_split_by_events() {
+        let ping_maker = PingMaker::new();
+        // Random extras, so the events don't compress well.
+        let events = (0..200)
+            .map(|i| {
+                json!({
+                    "timestamp": i,
+                    "category": "cat",
+                    "name": "event",
+                    "extra": { "id": uuid::Uuid::new_v4().to_string() },
+                })
+            })
+            .collect::<Vec<_>>();
+        let ping = test_ping(json!({
+            "ping_info": { "seq": 3 },
+            "client_info": { "telemetry_sdk_build": "test" },
+            "metrics": { "counter": { "cat.counter": 1 } },
+            "events": events,
+        }));
+
+        assert!(ping_maker.split_oversized(&ping, 1024 * 1024).is_none());
+
+        let parts = ping_maker.split_oversized(&ping, 2048).unwrap();
+        assert!(parts.len() > 2);
+
+        // Metrics are only sent in the first part.
+        assert_eq!(1, parts[0]["metrics"]["counter"]["cat.counter"]);
+        assert!(parts[0].get("events").is_none());
+
+        let mut split_events = vec![];
+        for (index, part) in parts.iter().enumerate() {
+            assert!(fits("store1", part, 2048));
+            assert_eq!("parent", part["ping_info"]["parent_id"]);
+            assert_eq!(
+                index + 1,
+                part["ping_info"]["part"].as_u64().unwrap() as usize
+            );
+            assert_eq!(
+                parts.len(),
+                part["ping_info"]["parts"].as_u64().unwrap() as usize
+            );
+            assert_eq!(3, part["ping_info"]["seq"]);
+            assert_eq!("test", part["client_info"]["telemetry_sdk_build"]);
+            if index > 0 {
+                assert!(part.get("metrics").is_none());
+                split_events.extend(part["events"].as_array().unwrap().iter().cloned());
+            }
+        }
+        assert_eq!(events, split_events);
+    }
+
+    #[test]
+    fn oversized_metrics_are_split() {
+        let ping_maker = PingMaker::new();
+        // Random values, so the metrics don't compress well.
+        let strings = (0..100)
+            .map(|i| {
+                (
+                    format!("cat.string{i}"),
+                    json!(uuid::Uuid::new_v4().to_string()),
+                )
+            })
+            .collect::<Map<_, _>>();
+        let ping = test_ping(json!({
+            "ping_info": { "seq": 0 },
+            "metrics": {
+                "counter": { "cat.counter": 1 },
+                "string": strings,
+            },
+            "events": [{ "timestamp": 0, "category": "cat", "name": "event" }],
+        }));
+
+        let parts = ping_maker.split_oversized(&ping, 1024).unwrap();
+        assert!(parts.len() > 2);
+
+        let mut split_strings = Map::new();
+        for part in &parts {
+            assert!(fits("store1", part, 1024));
+            if let Some(strings) = part["metrics"]["string"].as_object() {
+                split_strings.extend(strings.clone());
+            }
+        }
+        assert_eq!(strings, split_strings);
+        assert_eq!(1, parts[0]["metrics"]["counter"]["cat.counter"]);
+
+        // The events follow the metrics.
+        let last = parts.last().unwrap();
+        assert!(last.get("metrics").is_none());
+        assert_eq!(1, last["events"].as_array().unwrap().len());
+    }
+
+    #[test]
+    fn pings_with_a_single_metric_are_not_split() {
+        let ping_maker = PingMaker::new();
+        let ping = test_ping(json!({
+            "ping_info": { "seq": 0 },
+            "metrics": { "string": { "cat.string": uuid::Uuid::new_v4().to_string() } },
+        }));
+
+        assert!(ping_maker.split_oversized(&ping, 10).is_none());
+    }
 }
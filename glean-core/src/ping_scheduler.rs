@@ -0,0 +1,454 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! # Custom Ping Scheduler
+//!
+//! Submits pings that declare a time-based schedule in their [`PingOptions`](crate::metrics::PingOptions).
+//! The "metrics" ping is scheduled separately, see [`scheduler`](crate::scheduler).
+//!
+//! The time and app build of the last scheduled submission are persisted per ping,
+//! so schedules are honoured across restarts.
+//! A single thread waits for the next ping to be due.
+
+use std::collections::HashSet;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+use chrono::prelude::*;
+use chrono::Days;
+use once_cell::sync::Lazy;
+
+use crate::metrics::{DatetimeMetric, PingTimeSchedule, PingType, StringMetric, TimeUnit};
+use crate::storage::INTERNAL_STORAGE;
+use crate::util::local_now_with_offset;
+use crate::{CommonMetricData, Glean, Lifetime};
+
+/// The reason used when a ping is submitted on schedule.
+const REASON_SCHEDULED: &str = "scheduled";
+/// The reason used when a ping is submitted because the app build changed.
+const REASON_UPGRADE: &str = "upgrade";
+
+#[derive(Default)]
+struct State {
+    /// Whether Glean is initialized and scheduled pings should be submitted.
+    active: bool,
+    /// Whether the scheduler thread is running.
+    running: bool,
+    /// Whether the registered pings changed and the scheduler thread needs to recheck them.
+    changed: bool,
+}
+
+static TASK_CONDVAR: Lazy<Arc<(Mutex<State>, Condvar)>> =
+    Lazy::new(|| Arc::new((Mutex::new(State::default()), Condvar::new())));
+
+/// Starts submitting scheduled pings.
+///
+/// The scheduler thread is only started once a ping with a schedule is registered.
+pub fn schedule(glean: &Glean) {
+    let (state, condvar) = &**TASK_CONDVAR;
+    let mut state = state.lock().unwrap();
+    state.active = true;
+
+    let has_scheduled_pings = glean
+        .get_registered_ping_names()
+        .into_iter()
+        .filter_map(|name| glean.get_ping_by_name(name))
+        .any(PingType::is_scheduled);
+    if has_scheduled_pings {
+        wake_or_start(&mut state, condvar);
+    }
+}
+
+/// Tells the scheduler thread to exit quickly and cleanly.
+pub fn cancel() {
+    let (state, condvar) = &**TASK_CONDVAR;
+    state.lock().unwrap().active = false;
+    condvar.notify_all();
+}
+
+/// Tells the scheduler to recheck the registered pings,
+/// because a ping with a schedule was registered.
+///
+/// Has no effect before Glean is initialized, the pings are checked then.
+pub fn reschedule() {
+    let (state, condvar) = &**TASK_CONDVAR;
+    let mut state = state.lock().unwrap();
+    if state.active {
+        wake_or_start(&mut state, condvar);
+    }
+}
+
+fn wake_or_start(state: &mut State, condvar: &Condvar) {
+    if state.running {
+        state.changed = true;
+        condvar.notify_all();
+    } else {
+        state.running = true;
+        state.changed = false;
+        start_scheduler();
+    }
+}
+
+fn start_scheduler() {
+    let pair = Arc::clone(&TASK_CONDVAR);
+    crate::thread::spawn("glean.ping_scheduler", move || {
+        let (state, condvar) = &*pair;
+        let mut checked_builds = HashSet::new();
+        loop {
+            let (dur, submitted) = {
+                let Some(glean) = crate::core::global_glean() else {
+                    log::warn!("Global Glean not present. Ping scheduler exiting.");
+                    state.lock().unwrap().running = false;
+                    break;
+                };
+                let glean = glean.lock().unwrap();
+                submit_due_pings(&glean, local_now_with_offset(), &mut checked_builds)
+            };
+
+            // `trigger_upload` might lock the Glean object, so it must not be held here.
+            if submitted {
+                let state = crate::global_state().lock().unwrap();
+                if let Err(e) = state.callbacks.trigger_upload() {
+                    log::error!("Triggering upload failed. Error: {}", e);
+                }
+            }
+
+            let mut state = state.lock().unwrap();
+            let Some(dur) = dur else {
+                // Pings that only submit on build changes need no further checks,
+                // until another ping is registered.
+                if !state.changed {
+                    log::info!("No pings scheduled. Ping scheduler exiting.");
+                    state.running = false;
+                    break;
+                }
+                state.changed = false;
+                continue;
+            };
+
+            log::info!("Checking scheduled pings again after {:?}", dur);
+            match condvar.wait_timeout_while(state, dur, |state| state.active && !state.changed) {
+                Err(err) => {
+                    log::warn!("Condvar wait failure. Ping scheduler exiting. {}", err);
+                    break;
+                }
+                Ok((mut state, _)) => {
+                    if !state.active {
+                        log::info!("Ping scheduler cancelled. Exiting.");
+                        state.running = false;
+                        break;
+                    }
+                    state.changed = false;
+                }
+            }
+        }
+    })
+    .expect("Unable to spawn the ping scheduler thread.");
+}
+
+/// Submits all registered pings that are due.
+///
+/// Pings that submit on build changes are checked once per run,
+/// the first time they are seen.
+///
+/// # Returns
+///
+/// The time until the next ping is due, if any ping is scheduled,
+/// and whether any ping was submitted.
+fn submit_due_pings(
+    glean: &Glean,
+    now: DateTime<FixedOffset>,
+    checked_builds: &mut HashSet<String>,
+) -> (Option<Duration>, bool) {
+    let mut next_due: Option<Duration> = None;
+    let mut submitted = false;
+
+    for name in glean.get_registered_ping_names() {
+        let Some(ping) = glean.get_ping_by_name(name) else {
+            continue;
+        };
+
+        if ping.submit_on_build_change() && checked_builds.insert(name.to_string()) {
+            submitted |= check_build(glean, ping, now);
+        }
+
+        let Some(schedule) = ping.time_schedule() else {
+            continue;
+        };
+
+        let last_sent_time_metric = get_last_sent_time_metric(name);
+        let last_sent = last_sent_time_metric.get_value(glean, INTERNAL_STORAGE);
+        let last_sent = match (schedule, last_sent) {
+            // Without a previous submission, the first interval starts now.
+            (PingTimeSchedule::Interval { .. }, None) => {
+                last_sent_time_metric.set_sync_chrono(glean, now);
+                Some(now)
+            }
+            (_, last_sent) => last_sent,
+        };
+
+        let mut dur = time_until_due(schedule, last_sent, now);
+        if dur.is_zero() {
+            log::info!("The '{}' ping is due, {}", name, now);
+            submitted |= submit(glean, ping, REASON_SCHEDULED);
+            last_sent_time_metric.set_sync_chrono(glean, now);
+            dur = time_until_due(schedule, Some(now), now);
+        }
+        next_due = Some(next_due.map_or(dur, |next| next.min(dur)));
+    }
+
+    (next_due, submitted)
+}
+
+/// Submits `ping` if the app build changed since it was last checked.
+///
+/// # Returns
+///
+/// Whether the ping was submitted.
+fn check_build(glean: &Glean, ping: &PingType, now: DateTime<FixedOffset>) -> bool {
+    let last_sent_build_metric = get_last_sent_build_metric(ping.name());
+    let last_sent_build = last_sent_build_metric.get_value(glean, Some(INTERNAL_STORAGE));
+    last_sent_build_metric.set_sync(glean, &glean.app_build);
+
+    match last_sent_build {
+        Some(last_sent_build) if last_sent_build != glean.app_build => {
+            log::info!("App build changed. Sending the '{}' ping", ping.name());
+            let submitted = submit(glean, ping, REASON_UPGRADE);
+            if ping.time_schedule().is_some() {
+                get_last_sent_time_metric(ping.name()).set_sync_chrono(glean, now);
+            }
+            submitted
+        }
+        _ => false,
+    }
+}
+
+/// Submits `ping`, with `reason` if the ping declares it.
+fn submit(glean: &Glean, ping: &PingType, reason: &str) -> bool {
+    let reason = ping
+        .reason_codes()
+        .iter()
+        .any(|code| code == reason)
+        .then_some(reason);
+    ping.submit_sync(glean, reason)
+}
+
+/// Returns the duration from `now` until the ping is due.
+/// If the ping is overdue, this is zero.
+fn time_until_due(
+    schedule: &PingTimeSchedule,
+    last_sent: Option<DateTime<FixedOffset>>,
+    now: DateTime<FixedOffset>,
+) -> Duration {
+    let due = match *schedule {
+        PingTimeSchedule::Daily { hour } => {
+            let now_local = now.naive_local();
+            let sent_today = last_sent.is_some_and(|d| d.date_naive() == now.date_naive());
+            let date = if sent_today {
+                // Doesn't save us from being an hour off on DST because
+                // chrono doesn't know when DST changes.
+                now_local.checked_add_days(Days::new(1)).unwrap().date()
+            } else {
+                now_local.date()
+            };
+            let due_local = date.and_hms_opt(u32::from(hour), 0, 0).unwrap();
+            return (due_local - now_local).to_std().unwrap_or(Duration::ZERO);
+        }
+        PingTimeSchedule::Interval { hours } => {
+            let Some(last_sent) = last_sent else {
+                return Duration::from_secs(u64::from(hours) * 60 * 60);
+            };
+            let due = chrono::Duration::try_hours(i64::from(hours))
+                .and_then(|interval| last_sent.checked_add_signed(interval));
+            match due {
+                Some(due) => due,
+                // Too far in the future to ever be due.
+                None => return Duration::MAX,
+            }
+        }
+    };
+
+    (due - now).to_std().unwrap_or(Duration::ZERO)
+}
+
+fn get_last_sent_time_metric(ping_name: &str) -> DatetimeMetric {
+    DatetimeMetric::new(
+        CommonMetricData {
+            name: format!("{}#last_sent_time", ping_name),
+            // We don't need a category, the name is already unique
+            category: "".into(),
+            send_in_pings: vec![INTERNAL_STORAGE.into()],
+            lifetime: Lifetime::User,
+            ..Default::default()
+        },
+        TimeUnit::Second,
+    )
+}
+
+fn get_last_sent_build_metric(ping_name: &str) -> StringMetric {
+    StringMetric::new(CommonMetricData {
+        name: format!("{}#last_sent_build", ping_name),
+        category: "".into(),
+        send_in_pings: vec![INTERNAL_STORAGE.into()],
+        lifetime: Lifetime::User,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::metrics::PingOptions;
+    use crate::tests::new_glean;
+
+    fn at(hour: u32, minute: u32) -> DateTime<FixedOffset> {
+        FixedOffset::east_opt(2 * 60 * 60)
+            .unwrap()
+            .with_ymd_and_hms(2024, 3, 10, hour, minute, 0)
+            .unwrap()
+    }
+
+    fn scheduled_ping(glean: &mut Glean, options: PingOptions) -> PingType {
+        let ping = PingType::new_with_options(
+            "scheduled",
+            true,
+            true,
+            true,
+            true,
+            true,
+            vec![],
+            vec![REASON_SCHEDULED.into(), REASON_UPGRADE.into()],
+            true,
+            vec![],
+            options,
+        );
+        glean.register_ping_type(&ping);
+        ping
+    }
+
+    #[test]
+    fn daily_schedules_are_in_localtime() {
+        let daily = PingTimeSchedule::Daily { hour: 4 };
+
+        // Not sent today, before the due time.
+        assert_eq!(
+            Duration::from_secs(2 * 60 * 60),
+            time_until_due(&daily, None, at(2, 0))
+        );
+        // Not sent today, after the due time.
+        assert_eq!(Duration::ZERO, time_until_due(&daily, None, at(5, 0)));
+        // Already sent today.
+        assert_eq!(
+            Duration::from_secs(23 * 60 * 60),
+            time_until_due(&daily, Some(at(4, 0)), at(5, 0))
+        );
+    }
+
+    #[test]
+    fn interval_schedules_count_from_the_last_submission() {
+        let interval = PingTimeSchedule::Interval { hours: 6 };
+
+        assert_eq!(
+            Duration::from_secs(6 * 60 * 60),
+            time_until_due(&interval, None, at(2, 0))
+        );
+        assert_eq!(
+            Duration::from_secs(60 * 60),
+            time_until_due(&interval, Some(at(1, 0)), at(6, 0))
+        );
+        assert_eq!(
+            Duration::ZERO,
+            time_until_due(&interval, Some(at(1, 0)), at(8, 0))
+        );
+
+        // Beyond the dates chrono can represent.
+        let interval = PingTimeSchedule::Interval { hours: u32::MAX };
+        assert_eq!(
+            Duration::MAX,
+            time_until_due(&interval, Some(at(1, 0)), at(8, 0))
+        );
+    }
+
+    #[test]
+    fn long_intervals_are_capped() {
+        let (mut glean, _t) = new_glean(None);
+        let ping = scheduled_ping(
+            &mut glean,
+            PingOptions {
+                time_schedule: Some(PingTimeSchedule::Interval { hours: u32::MAX }),
+                ..Default::default()
+            },
+        );
+
+        let Some(&PingTimeSchedule::Interval { hours }) = ping.time_schedule() else {
+            panic!("Unexpected schedule {:?}", ping.time_schedule());
+        };
+        assert!(hours < u32::MAX);
+        assert_eq!(
+            Duration::from_secs(u64::from(hours) * 60 * 60),
+            time_until_due(ping.time_schedule().unwrap(), Some(at(1, 0)), at(1, 0))
+        );
+    }
+
+    #[test]
+    fn due_pings_are_submitted_and_rescheduled() {
+        let (mut glean, _t) = new_glean(None);
+        scheduled_ping(
+            &mut glean,
+            PingOptions {
+                time_schedule: Some(PingTimeSchedule::Interval { hours: 6 }),
+                ..Default::default()
+            },
+        );
+        let last_sent_time = get_last_sent_time_metric("scheduled");
+        let mut checked_builds = HashSet::new();
+
+        // The first interval starts now.
+        let (next_due, submitted) = submit_due_pings(&glean, at(1, 0), &mut checked_builds);
+        assert_eq!(Some(Duration::from_secs(6 * 60 * 60)), next_due);
+        assert!(!submitted);
+        assert_eq!(
+            Some(at(1, 0)),
+            last_sent_time.get_value(&glean, INTERNAL_STORAGE)
+        );
+
+        let (next_due, submitted) = submit_due_pings(&glean, at(7, 0), &mut checked_builds);
+        assert_eq!(Some(Duration::from_secs(6 * 60 * 60)), next_due);
+        assert!(submitted);
+        assert_eq!(
+            Some(at(7, 0)),
+            last_sent_time.get_value(&glean, INTERNAL_STORAGE)
+        );
+    }
+
+    #[test]
+    fn build_changes_submit_once() {
+        let (mut glean, _t) = new_glean(None);
+        let ping = scheduled_ping(
+            &mut glean,
+            PingOptions {
+                submit_on_build_change: true,
+                ..Default::default()
+            },
+        );
+        let mut checked_builds = HashSet::new();
+
+        // The first run only remembers the build.
+        assert!(!check_build(&glean, &ping, at(1, 0)));
+        assert_eq!(
+            Some(glean.app_build.clone()),
+            get_last_sent_build_metric("scheduled").get_value(&glean, Some(INTERNAL_STORAGE))
+        );
+
+        get_last_sent_build_metric("scheduled").set_sync(&glean, "old-build");
+        let (next_due, submitted) = submit_due_pings(&glean, at(1, 0), &mut checked_builds);
+        assert_eq!(None, next_due);
+        assert!(submitted);
+
+        // Builds are only checked once per run.
+        get_last_sent_build_metric("scheduled").set_sync(&glean, "old-build");
+        let (_, submitted) = submit_due_pings(&glean, at(1, 0), &mut checked_builds);
+        assert!(!submitted);
+    }
+}
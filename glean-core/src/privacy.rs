@@ -0,0 +1,100 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Local differential privacy mechanisms.
+//!
+//! Metrics with an `ldp_epsilon` in their [`CommonMetricData`](crate::CommonMetricData)
+//! have noise added on-device, before anything is stored.
+//! The epsilon is reported alongside the data, so that aggregates can be debiased.
+
+use uuid::Uuid;
+
+use crate::session::uuid_to_sample_value;
+
+/// Whether `epsilon` is a usable privacy parameter.
+pub(crate) fn is_valid_epsilon(epsilon: f64) -> bool {
+    epsilon.is_finite() && epsilon > 0.0
+}
+
+/// A uniformly distributed random value in `[0, 1)`.
+fn random_unit() -> f64 {
+    uuid_to_sample_value(&Uuid::new_v4())
+}
+
+/// Randomized response.
+///
+/// Reports the true `value` with probability `e^ε / (1 + e^ε)`, and its negation otherwise.
+pub(crate) fn randomized_response(value: bool, epsilon: f64) -> bool {
+    let keep = epsilon.exp() / (1.0 + epsilon.exp());
+    if random_unit() < keep {
+        value
+    } else {
+        !value
+    }
+}
+
+/// Samples noise from the two-sided geometric distribution, the discrete Laplace mechanism.
+///
+/// `P(noise = k)` is proportional to `e^(-ε|k| / sensitivity)`, which is `ε`-differentially private
+/// for a change of up to `sensitivity`. The noise has a mean of 0.
+pub(crate) fn geometric_noise(epsilon: f64, sensitivity: u32) -> i32 {
+    // The difference of two geometric variables is two-sided geometric.
+    let alpha = (-epsilon / f64::from(sensitivity.max(1))).exp();
+    let geometric = || {
+        // `1 - u` is in `(0, 1]`, so the logarithm is finite.
+        let u = 1.0 - random_unit();
+        (u.ln() / alpha.ln()).floor()
+    };
+    (geometric() - geometric()).clamp(i32::MIN as f64, i32::MAX as f64) as i32
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn epsilon_must_be_positive_and_finite() {
+        assert!(is_valid_epsilon(0.5));
+        assert!(!is_valid_epsilon(0.0));
+        assert!(!is_valid_epsilon(-1.0));
+        assert!(!is_valid_epsilon(f64::NAN));
+        assert!(!is_valid_epsilon(f64::INFINITY));
+    }
+
+    #[test]
+    fn randomized_response_keeps_the_expected_share() {
+        let epsilon = 1.0f64;
+        let n = 20_000;
+        let kept = (0..n)
+            .filter(|_| randomized_response(true, epsilon))
+            .count() as f64;
+
+        let expected = epsilon.exp() / (1.0 + epsilon.exp());
+        assert!((kept / n as f64 - expected).abs() < 0.02, "{kept}");
+    }
+
+    #[test]
+    fn geometric_noise_is_centered() {
+        let epsilon = 1.0;
+        let n = 20_000;
+        let noise: Vec<i32> = (0..n).map(|_| geometric_noise(epsilon, 1)).collect();
+
+        let mean = noise.iter().map(|&x| x as f64).sum::<f64>() / n as f64;
+        assert!(mean.abs() < 0.1, "{mean}");
+        // With ε = 1 about 46% of the samples are 0.
+        let zeros = noise.iter().filter(|&&x| x == 0).count() as f64 / n as f64;
+        assert!((zeros - 0.462).abs() < 0.02, "{zeros}");
+    }
+
+    #[test]
+    fn geometric_noise_scales_with_the_sensitivity() {
+        let epsilon = 1.0;
+        let n = 20_000;
+        let zeros = (0..n).filter(|_| geometric_noise(epsilon, 10) == 0).count() as f64;
+
+        // With ε / sensitivity = 0.1 about 5% of the samples are 0.
+        let expected = (1.0 - (-0.1f64).exp()) / (1.0 + (-0.1f64).exp());
+        assert!((zeros / n as f64 - expected).abs() < 0.01, "{zeros}");
+    }
+}
@@ -0,0 +1,149 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! # Pseudonymous identifiers
+//!
+//! Pings can carry a rotating identifier instead of the `client_id`,
+//! see [`PingOptions::pseudonymous_id`](crate::metrics::PingOptions::pseudonymous_id).
+//! Pings of the same client carry the same identifier within a rotation window,
+//! but the identifiers of different windows or different pings can't be linked
+//! without the locally stored secret.
+//!
+//! The identifier is a name-based UUID (version 5) of the ping name and the window,
+//! with the secret as namespace.
+//! The secret is stored with the client's other internal data
+//! and is removed along with it when upload is disabled.
+//!
+//! Pings with a pseudonymous identifier carry no other data linking windows:
+//! their `ping_info.seq` is always 0, their `ping_info.start_time` is their `end_time`
+//! and the per-client fields of `client_info` are left out.
+
+use chrono::{DateTime, FixedOffset};
+use uuid::Uuid;
+
+use crate::metrics::{PseudonymousIdRotation, UuidMetric};
+use crate::storage::INTERNAL_STORAGE;
+use crate::{CommonMetricData, Glean, Lifetime};
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// The `client_info` fields left out of pings with a pseudonymous identifier,
+/// as they stay the same across windows or count up from one to the next.
+pub(crate) const LINKING_CLIENT_INFO_FIELDS: [&str; 3] =
+    ["first_run_date", "session_count", "session_id"];
+
+/// Gets the pseudonymous identifier of a ping for the window `now` is in.
+///
+/// # Arguments
+///
+/// * `glean` - The Glean instance.
+/// * `ping_name` - The name of the ping.
+/// * `rotation` - When the identifier rotates.
+/// * `now` - The time the ping is collected at.
+/// * `preview` - Whether the ping is only previewed.
+///   No secret is created then, so there might be no identifier.
+///
+/// # Returns
+///
+/// The identifier, or `None` if there is no window,
+/// e.g. because the identifier rotates per session and there is no session.
+pub(crate) fn get(
+    glean: &Glean,
+    ping_name: &str,
+    rotation: &PseudonymousIdRotation,
+    now: DateTime<FixedOffset>,
+    preview: bool,
+) -> Option<Uuid> {
+    let window = match *rotation {
+        PseudonymousIdRotation::Days { days } => {
+            let day = now.timestamp().div_euclid(SECONDS_PER_DAY);
+            format!("days/{}/{}", days, day / i64::from(days))
+        }
+        PseudonymousIdRotation::Session => {
+            let Some(session_id) = glean.session_manager().session_id() else {
+                log::info!("No active session. Not including a pseudonymous ID in '{ping_name}'.");
+                return None;
+            };
+            format!("session/{}", session_id)
+        }
+    };
+
+    let secret_metric = get_secret_metric();
+    let secret = match secret_metric.get_value(glean, INTERNAL_STORAGE) {
+        Some(secret) => secret,
+        None if preview => return None,
+        None => secret_metric.generate_and_set_sync(glean),
+    };
+
+    Some(Uuid::new_v5(
+        &secret,
+        format!("{}/{}", ping_name, window).as_bytes(),
+    ))
+}
+
+fn get_secret_metric() -> UuidMetric {
+    UuidMetric::new(CommonMetricData {
+        name: "pseudonymous_id_secret".into(),
+        // We don't need a category, the name is already unique
+        category: "".into(),
+        send_in_pings: vec![INTERNAL_STORAGE.into()],
+        lifetime: Lifetime::User,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tests::new_glean;
+    use chrono::TimeZone;
+
+    fn day(day: u32) -> DateTime<FixedOffset> {
+        FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2024, 3, day, 12, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn ids_rotate_after_the_given_days() {
+        let (glean, _t) = new_glean(None);
+        let rotation = PseudonymousIdRotation::Days { days: 7 };
+
+        // 2024-03-07 starts a 7-day window, counted from the Unix epoch.
+        let first = get(&glean, "custom", &rotation, day(7), false).unwrap();
+        assert_eq!(
+            Some(first),
+            get(&glean, "custom", &rotation, day(13), false)
+        );
+        assert_ne!(
+            Some(first),
+            get(&glean, "custom", &rotation, day(14), false)
+        );
+        assert_ne!(Some(first), get(&glean, "other", &rotation, day(7), false));
+    }
+
+    #[test]
+    fn ids_depend_on_the_secret() {
+        let (glean, _t) = new_glean(None);
+        let rotation = PseudonymousIdRotation::Days { days: 1 };
+
+        // Previewing doesn't create a secret.
+        assert_eq!(None, get(&glean, "custom", &rotation, day(1), true));
+
+        let id = get(&glean, "custom", &rotation, day(1), false).unwrap();
+        assert_eq!(Some(id), get(&glean, "custom", &rotation, day(1), true));
+
+        get_secret_metric().generate_and_set_sync(&glean);
+        assert_ne!(Some(id), get(&glean, "custom", &rotation, day(1), false));
+    }
+
+    #[test]
+    fn session_ids_need_a_session() {
+        let (glean, _t) = new_glean(None);
+        let rotation = PseudonymousIdRotation::Session;
+
+        assert_eq!(None, get(&glean, "custom", &rotation, day(1), false));
+    }
+}
@@ -12,12 +12,28 @@ use serde_json::{json, Value as JsonValue};
 
 use crate::database::Database;
 use crate::metrics::dual_labeled_counter::RECORD_SEPARATOR;
-use crate::metrics::Metric;
+use crate::metrics::{enum_string, Metric};
 use crate::Lifetime;
 
 // An internal ping name, not to be touched by anything else
 pub(crate) const INTERNAL_STORAGE: &str = "glean_internal_info";
 
+/// The JSON representation of a metric's data.
+///
+/// Enumerated strings are stored as an index, which is mapped back to the value here.
+fn metric_as_json(metric_id: &str, metric: &Metric) -> JsonValue {
+    match metric {
+        Metric::EnumString(index) => {
+            let base_id = metric_id
+                .split(['/', RECORD_SEPARATOR])
+                .next()
+                .unwrap_or(metric_id);
+            json!(enum_string::value_for(base_id, *index))
+        }
+        _ => metric.as_json(),
+    }
+}
+
 /// Snapshot metrics from the underlying database.
 pub struct StorageManager;
 
@@ -46,11 +62,11 @@ fn snapshot_labeled_metrics(
     let map = snapshot.entry(ping_section).or_default();
 
     // Safe unwrap, the function is only called when the id does contain a '/'
-    let (metric_id, label) = metric_id.split_once('/').unwrap();
+    let (base_id, label) = metric_id.split_once('/').unwrap();
 
-    let obj = map.entry(metric_id.into()).or_insert_with(|| json!({}));
+    let obj = map.entry(base_id.into()).or_insert_with(|| json!({}));
     let obj = obj.as_object_mut().unwrap(); // safe unwrap, we constructed the object above
-    obj.insert(label.into(), metric.as_json());
+    obj.insert(label.into(), metric_as_json(metric_id, metric));
 }
 
 /// Dual Labeled metrics are stored as `<metric id><\x1e><key><\x1e><category>`.
@@ -74,7 +90,7 @@ fn snapshot_dual_labeled_metrics(
         .unwrap(); // safe unwrap, we constructed the object above
     let key_obj = obj.entry(parts[1].to_string()).or_insert_with(|| json!({}));
     let key_obj = key_obj.as_object_mut().unwrap();
-    key_obj.insert(parts[2].into(), metric.as_json());
+    key_obj.insert(parts[2].into(), metric_as_json(metric_id, metric));
 }
 
 impl StorageManager {
@@ -122,13 +138,27 @@ impl StorageManager {
 
         let mut snapshotter = |metric_id: &[u8], metric: &Metric| {
             let metric_id = String::from_utf8_lossy(metric_id).into_owned();
+            if let Some(epsilon) = metric.ldp_epsilon() {
+                // Reported per metric, not per label.
+                let base_id = metric_id
+                    .split(['/', RECORD_SEPARATOR])
+                    .next()
+                    .unwrap_or(&metric_id);
+                // Labels are privatized separately, so the metric costs as much as its costliest label.
+                let map = snapshot.entry("ldp_epsilon".into()).or_default();
+                let reported = map.entry(base_id.into()).or_insert(json!(epsilon));
+                if reported.as_f64().is_some_and(|reported| reported < epsilon) {
+                    *reported = json!(epsilon);
+                }
+            }
             if metric_id.contains('/') {
                 snapshot_labeled_metrics(&mut snapshot, &metric_id, metric);
             } else if metric_id.split(RECORD_SEPARATOR).count() == 3 {
                 snapshot_dual_labeled_metrics(&mut snapshot, &metric_id, metric);
             } else {
+                let value = metric_as_json(&metric_id, metric);
                 let map = snapshot.entry(metric.ping_section().into()).or_default();
-                map.insert(metric_id, metric.as_json());
+                map.insert(metric_id, value);
             }
         };
 
@@ -55,4 +55,19 @@ pub trait CustomDistribution: TestGetValue<Output = DistributionData> {
     ///
     /// The number of errors recorded.
     fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32;
+
+    /// **Exported for test purposes.**
+    ///
+    /// Estimates a percentile of the currently stored distribution.
+    ///
+    /// # Arguments
+    ///
+    /// * `percentile` - The percentile to estimate, between `0` and `100`.
+    /// * `ping_name` - represents the optional name of the ping to retrieve the
+    ///   metric for. Defaults to the first value in `send_in_pings`.
+    ///
+    /// # Returns
+    ///
+    /// The estimate or `None` if nothing is stored.
+    fn test_get_percentile(&self, percentile: f64, ping_name: Option<String>) -> Option<i64>;
 }
@@ -0,0 +1,35 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::{ErrorType, TestGetValue};
+
+/// A description for the [`DistinctCountMetric`](crate::metrics::DistinctCountMetric) type.
+///
+/// When changing this trait, make sure all the operations are
+/// implemented in the related type in `../metrics/`.
+pub trait DistinctCount: TestGetValue<Output = i64> {
+    /// Adds a value to the set of distinct values seen.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value. Must not be empty.
+    ///
+    /// ## Notes
+    ///
+    /// Logs an error if the `value` is empty.
+    fn add<S: Into<std::string::String>>(&self, value: S);
+
+    /// **Exported for test purposes.**
+    ///
+    /// Gets the number of recorded errors for the given metric and error type.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - The type of error
+    ///
+    /// # Returns
+    ///
+    /// The number of errors reported.
+    fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32;
+}
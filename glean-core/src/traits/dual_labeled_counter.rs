@@ -2,7 +2,11 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::{CounterMetric, ErrorType, TestGetValue};
+use crate::metrics::DistributionData;
+use crate::{
+    BooleanMetric, Counter64Metric, CounterMetric, CustomDistributionMetric, ErrorType,
+    QuantityMetric, TestGetValue, TimingDistributionMetric,
+};
 use std::collections::HashMap;
 
 /// A description for the [`DualLabeledCounterMetric`](crate::metrics::DualLabeledCounterMetric) type.
@@ -36,3 +40,133 @@ pub trait DualLabeledCounter: TestGetValue<Output = HashMap<String, HashMap<Stri
     /// The number of errors reported.
     fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32;
 }
+
+/// A description for the [`DualLabeledCounter64Metric`](crate::metrics::DualLabeledCounter64Metric) type.
+///
+/// When changing this trait, make sure all the operations are
+/// implemented in the related type in `../metrics/`.
+pub trait DualLabeledCounter64:
+    TestGetValue<Output = HashMap<String, HashMap<String, i64>>>
+{
+    /// Gets a specific 64-bit counter for a given key/category pair.
+    ///
+    /// See [`DualLabeledCounter::get`] for how keys and categories are handled.
+    fn get(&self, key: &str, category: &str) -> Counter64Metric;
+
+    /// **Exported for test purposes.**
+    ///
+    /// Gets the number of recorded errors for the given metric and error type.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - The type of error
+    ///
+    /// # Returns
+    ///
+    /// The number of errors reported.
+    fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32;
+}
+
+/// A description for the [`DualLabeledQuantityMetric`](crate::metrics::DualLabeledQuantityMetric) type.
+///
+/// When changing this trait, make sure all the operations are
+/// implemented in the related type in `../metrics/`.
+pub trait DualLabeledQuantity:
+    TestGetValue<Output = HashMap<String, HashMap<String, i64>>>
+{
+    /// Gets a specific quantity for a given key/category pair.
+    ///
+    /// See [`DualLabeledCounter::get`] for how keys and categories are handled.
+    fn get(&self, key: &str, category: &str) -> QuantityMetric;
+
+    /// **Exported for test purposes.**
+    ///
+    /// Gets the number of recorded errors for the given metric and error type.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - The type of error
+    ///
+    /// # Returns
+    ///
+    /// The number of errors reported.
+    fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32;
+}
+
+/// A description for the [`DualLabeledBooleanMetric`](crate::metrics::DualLabeledBooleanMetric) type.
+///
+/// When changing this trait, make sure all the operations are
+/// implemented in the related type in `../metrics/`.
+pub trait DualLabeledBoolean:
+    TestGetValue<Output = HashMap<String, HashMap<String, bool>>>
+{
+    /// Gets a specific boolean for a given key/category pair.
+    ///
+    /// See [`DualLabeledCounter::get`] for how keys and categories are handled.
+    fn get(&self, key: &str, category: &str) -> BooleanMetric;
+
+    /// **Exported for test purposes.**
+    ///
+    /// Gets the number of recorded errors for the given metric and error type.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - The type of error
+    ///
+    /// # Returns
+    ///
+    /// The number of errors reported.
+    fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32;
+}
+
+/// A description for the [`DualLabeledCustomDistributionMetric`](crate::metrics::DualLabeledCustomDistributionMetric) type.
+///
+/// When changing this trait, make sure all the operations are
+/// implemented in the related type in `../metrics/`.
+pub trait DualLabeledCustomDistribution:
+    TestGetValue<Output = HashMap<String, HashMap<String, DistributionData>>>
+{
+    /// Gets a specific custom distribution for a given key/category pair.
+    ///
+    /// See [`DualLabeledCounter::get`] for how keys and categories are handled.
+    fn get(&self, key: &str, category: &str) -> CustomDistributionMetric;
+
+    /// **Exported for test purposes.**
+    ///
+    /// Gets the number of recorded errors for the given metric and error type.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - The type of error
+    ///
+    /// # Returns
+    ///
+    /// The number of errors reported.
+    fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32;
+}
+
+/// A description for the [`DualLabeledTimingDistributionMetric`](crate::metrics::DualLabeledTimingDistributionMetric) type.
+///
+/// When changing this trait, make sure all the operations are
+/// implemented in the related type in `../metrics/`.
+pub trait DualLabeledTimingDistribution:
+    TestGetValue<Output = HashMap<String, HashMap<String, DistributionData>>>
+{
+    /// Gets a specific timing distribution for a given key/category pair.
+    ///
+    /// See [`DualLabeledCounter::get`] for how keys and categories are handled.
+    fn get(&self, key: &str, category: &str) -> TimingDistributionMetric;
+
+    /// **Exported for test purposes.**
+    ///
+    /// Gets the number of recorded errors for the given metric and error type.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - The type of error
+    ///
+    /// # Returns
+    ///
+    /// The number of errors reported.
+    fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32;
+}
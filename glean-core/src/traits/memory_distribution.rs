@@ -20,7 +20,8 @@ pub trait MemoryDistribution: TestGetValue<Output = DistributionData> {
     ///
     /// ## Notes
     ///
-    /// Values bigger than 1 Terabyte (2<sup>40</sup> bytes) are truncated
+    /// Values bigger than the maximum of the metric, 1 Terabyte (2<sup>40</sup> bytes)
+    /// by default, are truncated
     /// and an `ErrorType::InvalidValue` error is recorded.
     fn accumulate(&self, sample: u64);
 
@@ -45,7 +46,8 @@ pub trait MemoryDistribution: TestGetValue<Output = DistributionData> {
     /// Discards any negative value in `samples` and report an [`ErrorType::InvalidValue`]
     /// for each of them.
     ///
-    /// Values bigger than 1 Terabyte (2<sup>40</sup> bytes) are truncated
+    /// Values bigger than the maximum of the metric, 1 Terabyte (2<sup>40</sup> bytes)
+    /// by default, are truncated
     /// and an [`ErrorType::InvalidValue`] error is recorded.
     fn accumulate_samples(&self, samples: Vec<i64>);
 
@@ -61,4 +63,19 @@ pub trait MemoryDistribution: TestGetValue<Output = DistributionData> {
     ///
     /// The number of errors recorded.
     fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32;
+
+    /// **Exported for test purposes.**
+    ///
+    /// Estimates a percentile of the currently stored distribution.
+    ///
+    /// # Arguments
+    ///
+    /// * `percentile` - The percentile to estimate, between `0` and `100`.
+    /// * `ping_name` - represents the optional name of the ping to retrieve the
+    ///   metric for. Defaults to the first value in `send_in_pings`.
+    ///
+    /// # Returns
+    ///
+    /// The estimate or `None` if nothing is stored.
+    fn test_get_percentile(&self, percentile: f64, ping_name: Option<String>) -> Option<i64>;
 }
@@ -13,17 +13,23 @@ pub extern crate serde as __serde;
 
 mod boolean;
 mod counter;
+mod counter64;
 mod custom_distribution;
 mod datetime;
+mod distinct_count;
 mod dual_labeled_counter;
+mod enum_string;
 mod event;
+mod gauge;
 mod labeled;
 mod memory_distribution;
 mod numerator;
 mod object;
 mod ping;
+mod quantile_distribution;
 mod quantity;
 mod rate;
+mod span;
 mod string;
 mod string_list;
 mod text;
@@ -34,20 +40,29 @@ mod uuid;
 
 pub use self::boolean::Boolean;
 pub use self::counter::Counter;
+pub use self::counter64::Counter64;
 pub use self::custom_distribution::CustomDistribution;
 pub use self::datetime::Datetime;
-pub use self::dual_labeled_counter::DualLabeledCounter;
+pub use self::distinct_count::DistinctCount;
+pub use self::dual_labeled_counter::{
+    DualLabeledBoolean, DualLabeledCounter, DualLabeledCounter64, DualLabeledCustomDistribution,
+    DualLabeledQuantity, DualLabeledTimingDistribution,
+};
+pub use self::enum_string::EnumString;
 pub use self::event::Event;
 pub use self::event::EventRecordingError;
 pub use self::event::ExtraKeys;
 pub use self::event::NoExtraKeys;
+pub use self::gauge::Gauge;
 pub use self::labeled::Labeled;
 pub use self::memory_distribution::MemoryDistribution;
 pub use self::numerator::Numerator;
 pub use self::object::{ObjectError, ObjectSerialize};
 pub use self::ping::Ping;
+pub use self::quantile_distribution::QuantileDistribution;
 pub use self::quantity::Quantity;
 pub use self::rate::Rate;
+pub use self::span::Span;
 pub use self::string::String;
 pub use self::string_list::StringList;
 pub use self::text::Text;
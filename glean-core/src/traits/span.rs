@@ -0,0 +1,50 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::collections::HashMap;
+
+use crate::event_database::RecordedEvent;
+use crate::metrics::SpanId;
+use crate::{ErrorType, TestGetValue};
+
+/// A description for the [`SpanMetric`](crate::metrics::SpanMetric) type.
+///
+/// When changing this trait, make sure all the operations are
+/// implemented in the related type in `../metrics/`.
+pub trait Span: TestGetValue<Output = Vec<RecordedEvent>> {
+    /// Starts a new span.
+    /// Multiple spans can run simultaneously.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent` - The span that caused this one, if any.
+    ///
+    /// # Returns
+    ///
+    /// A unique [`SpanId`] for the new span.
+    fn start(&self, parent: Option<SpanId>) -> SpanId;
+
+    /// Ends a span and records its duration.
+    ///
+    /// This will record an error if the span is not running.
+    ///
+    /// # Arguments
+    ///
+    /// * `span` - The [`SpanId`] returned by [`start`](Span::start).
+    /// * `extra` - A [`HashMap`] of `(key, value)` pairs to record with the end of the span.
+    fn end(&self, span: SpanId, extra: HashMap<String, String>);
+
+    /// **Exported for test purposes.**
+    ///
+    /// Gets the number of recorded errors for the given metric and error type.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - The type of error
+    ///
+    /// # Returns
+    ///
+    /// The number of errors reported.
+    fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32;
+}
@@ -65,7 +65,7 @@ pub trait TimingDistribution: TestGetValue<Output = DistributionData> {
     ///
     /// Discards any negative value in `samples` and report an [`ErrorType::InvalidValue`]
     /// for each of them. Reports an [`ErrorType::InvalidOverflow`] error for samples that
-    /// are longer than `MAX_SAMPLE_TIME`.
+    /// are longer than the maximum sample time of the metric.
     fn accumulate_samples(&self, samples: Vec<i64>);
 
     /// Accumulates precisely one signed sample in the metric.
@@ -90,7 +90,7 @@ pub trait TimingDistribution: TestGetValue<Output = DistributionData> {
     ///
     /// Discards any negative value and reports an [`ErrorType::InvalidValue`].
     /// Reports an [`ErrorType::InvalidOverflow`] error if the sample is longer than
-    /// `MAX_SAMPLE_TIME`.
+    /// the maximum sample time of the metric.
     fn accumulate_single_sample(&self, sample: i64);
 
     /// Accumulates the provided samples in the metric.
@@ -102,7 +102,7 @@ pub trait TimingDistribution: TestGetValue<Output = DistributionData> {
     /// ## Notes
     ///
     /// Reports an [`ErrorType::InvalidOverflow`] error for samples that
-    /// are longer than `MAX_SAMPLE_TIME`.
+    /// are longer than the maximum sample time of the metric.
     fn accumulate_raw_samples_nanos(&self, samples: Vec<u64>);
 
     /// Accumulates precisely one duration to the metric.
@@ -120,7 +120,7 @@ pub trait TimingDistribution: TestGetValue<Output = DistributionData> {
     /// ## Notes
     ///
     /// Reports an [`ErrorType::InvalidOverflow`] error if `duration` is longer than
-    /// `MAX_SAMPLE_TIME`.
+    /// the maximum sample time of the metric.
     ///
     /// The API client is responsible for ensuring that `duration` is derived from a
     /// monotonic clock source that behaves consistently over computer sleep across
@@ -140,4 +140,19 @@ pub trait TimingDistribution: TestGetValue<Output = DistributionData> {
     ///
     /// The number of errors recorded.
     fn test_get_num_recorded_errors(&self, error: ErrorType) -> i32;
+
+    /// **Exported for test purposes.**
+    ///
+    /// Estimates a percentile of the currently stored distribution.
+    ///
+    /// # Arguments
+    ///
+    /// * `percentile` - The percentile to estimate, between `0` and `100`.
+    /// * `ping_name` - represents the optional name of the ping to retrieve the
+    ///   metric for. Defaults to the first value in `send_in_pings`.
+    ///
+    /// # Returns
+    ///
+    /// The estimate or `None` if nothing is stored.
+    fn test_get_percentile(&self, percentile: f64, ping_name: Option<String>) -> Option<i64>;
 }
@@ -131,6 +131,43 @@ pub fn process_metadata(path: &str, metadata: &str) -> Option<PingMetadata> {
     None
 }
 
+/// Parses the contents of a ping file.
+///
+/// # Returns
+///
+/// The ping's payload, or `None` if the file is not formatted as expected.
+pub(crate) fn parse_ping_file(document_id: &str, file: File) -> Option<PingPayload> {
+    // The way the ping file is structured:
+    // first line should always have the path,
+    // second line should have the body with the ping contents in JSON format
+    // and third line might contain ping metadata e.g. additional headers.
+    let mut lines = BufReader::new(file).lines();
+    let (Some(Ok(path)), Some(Ok(body)), Ok(metadata)) =
+        (lines.next(), lines.next(), lines.next().transpose())
+    else {
+        return None;
+    };
+
+    let PingMetadata {
+        headers,
+        body_has_info_sections,
+        ping_name,
+        uploader_capabilities,
+    } = metadata
+        .and_then(|m| process_metadata(&path, &m))
+        .unwrap_or_default();
+    let ping_name = ping_name.unwrap_or_else(|| path.split('/').nth(3).unwrap_or("").into());
+    Some(PingPayload {
+        document_id: document_id.into(),
+        upload_path: path,
+        json_body: body,
+        headers,
+        body_has_info_sections: body_has_info_sections.unwrap_or(true),
+        ping_name,
+        uploader_capabilities: uploader_capabilities.unwrap_or_default(),
+    })
+}
+
 /// Manages the pings directories.
 #[derive(Debug, Clone, MallocSizeOf)]
 pub struct PingDirectoryManager {
@@ -212,41 +249,15 @@ impl PingDirectoryManager {
 
         log::info!("Processing ping at: {}", path.display());
 
-        // The way the ping file is structured:
-        // first line should always have the path,
-        // second line should have the body with the ping contents in JSON format
-        // and third line might contain ping metadata e.g. additional headers.
-        let mut lines = BufReader::new(file).lines();
-        if let (Some(Ok(path)), Some(Ok(body)), Ok(metadata)) =
-            (lines.next(), lines.next(), lines.next().transpose())
-        {
-            let PingMetadata {
-                headers,
-                body_has_info_sections,
-                ping_name,
-                uploader_capabilities,
-            } = metadata
-                .and_then(|m| process_metadata(&path, &m))
-                .unwrap_or_default();
-            let ping_name =
-                ping_name.unwrap_or_else(|| path.split('/').nth(3).unwrap_or("").into());
-            return Some(PingPayload {
-                document_id: document_id.into(),
-                upload_path: path,
-                json_body: body,
-                headers,
-                body_has_info_sections: body_has_info_sections.unwrap_or(true),
-                ping_name,
-                uploader_capabilities: uploader_capabilities.unwrap_or_default(),
-            });
-        } else {
+        let payload = parse_ping_file(document_id, file);
+        if payload.is_none() {
             log::warn!(
                 "Error processing ping file: {}. Ping file is not formatted as expected.",
                 document_id
             );
+            self.delete_file(document_id);
         }
-        self.delete_file(document_id);
-        None
+        payload
     }
 
     /// Processes both ping directories.
@@ -27,16 +27,20 @@ use malloc_size_of_derive::MallocSizeOf;
 use crate::error::ErrorKind;
 use crate::TimerId;
 use crate::{internal_metrics::UploadMetrics, Glean};
+pub(crate) use directory::parse_ping_file;
 pub use directory::process_metadata;
 use directory::{PingDirectoryManager, PingPayloadsByDirectory};
 use policy::Policy;
 use request::create_date_header_value;
+pub(crate) use request::gzip_content;
 
 pub use directory::{PingMetadata, PingPayload};
+pub use otlp::OtlpExport;
 pub use request::{HeaderMap, PingRequest};
 pub use result::{UploadResult, UploadTaskAction};
 
 mod directory;
+mod otlp;
 mod policy;
 mod request;
 mod result;
@@ -406,6 +410,11 @@ impl PingUploadManager {
         }
     }
 
+    /// Gets the maximum size in bytes a ping body may have to be eligible for upload.
+    pub(crate) fn max_ping_body_size(&self) -> usize {
+        self.policy.max_ping_body_size()
+    }
+
     /// Enqueue a ping for upload.
     pub fn enqueue_ping(&self, glean: &Glean, ping: PingPayload) {
         let mut queue = self
@@ -2128,4 +2137,65 @@ mod test {
             UploadResult::http_status(200),
         );
     }
+
+    #[test]
+    fn oversized_pings_are_stored_as_multiple_documents() {
+        let (mut glean, dir) = new_glean(None);
+        glean
+            .upload_manager
+            .policy
+            .set_max_ping_body_size(Some(2048));
+
+        let ping_type = PingType::new(
+            "test",
+            true,
+            /* send_if_empty */ false,
+            true,
+            true,
+            true,
+            vec![],
+            vec![],
+            true,
+            vec![],
+        );
+        glean.register_ping_type(&ping_type);
+
+        let event = crate::metrics::EventMetric::new(
+            crate::CommonMetricData {
+                name: "event".into(),
+                category: "test".into(),
+                send_in_pings: vec!["test".into()],
+                lifetime: crate::Lifetime::Ping,
+                ..Default::default()
+            },
+            vec!["id".into()],
+        );
+        // Random extras, so the events don't compress well.
+        for i in 0..200 {
+            let extra = [("id".to_string(), Uuid::new_v4().to_string())].into();
+            event.record_sync(&glean, i, extra, 0);
+        }
+
+        assert!(ping_type.submit_sync(&glean, None));
+
+        let directory_manager = PingDirectoryManager::new(dir.path());
+        let pending_pings = directory_manager.process_dirs().pending_pings;
+        let parts: Vec<serde_json::Value> = pending_pings
+            .iter()
+            .map(|(_, ping)| serde_json::from_str(&ping.json_body).unwrap())
+            .collect();
+        assert!(parts.len() > 2, "only {} parts", parts.len());
+
+        let parent_id = &parts[0]["ping_info"]["parent_id"];
+        let mut indices: Vec<u64> = parts
+            .iter()
+            .map(|part| {
+                assert_eq!(parent_id, &part["ping_info"]["parent_id"]);
+                assert_eq!(parts.len() as u64, part["ping_info"]["parts"]);
+                part["ping_info"]["part"].as_u64().unwrap()
+            })
+            .collect();
+        indices.sort_unstable();
+        assert_eq!((1..=parts.len() as u64).collect::<Vec<_>>(), indices);
+    }
 }
@@ -0,0 +1,530 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Conversion of assembled pings into OpenTelemetry (OTLP) payloads.
+//!
+//! The payloads use the [OTLP/HTTP JSON encoding] and can be posted as-is
+//! to the `/v1/metrics` and `/v1/logs` endpoints of an OTLP collector.
+//!
+//! * Counters become monotonic delta sums.
+//! * Quantities become gauges.
+//! * Timing, memory and custom distributions become delta histograms.
+//! * Events become log records.
+//!
+//! Labels of labeled metrics are reported as the `glean.label` attribute,
+//! keys and categories of dual-labeled metrics as `glean.key` and `glean.category`.
+//! All other metric types have no OTLP equivalent and are skipped.
+//!
+//! The client id is not exported unless asked for,
+//! as the collector is usually not operated by the data platform.
+//!
+//! [OTLP/HTTP JSON encoding]: https://opentelemetry.io/docs/specs/otlp/#json-protobuf-encoding
+
+use std::io::Read;
+
+use chrono::{DateTime, FixedOffset};
+use flate2::read::GzDecoder;
+use serde_json::{json, Map, Value as JsonValue};
+
+use crate::histogram::{Bucketing, Functional};
+use crate::metrics::{memory_distribution, timing_distribution};
+
+/// The `aggregationTemporality` of all exported sums and histograms.
+///
+/// Each data point covers the time between the start and end time of its ping.
+/// Only metrics with a ping lifetime are reset when a ping is sent,
+/// so metrics meant for export should use that lifetime.
+/// As the ping doesn't tell the lifetime of its metrics,
+/// the others would be counted again with every ping.
+const AGGREGATION_TEMPORALITY_DELTA: i32 = 1;
+
+/// The `severityNumber` of exported log records.
+const SEVERITY_NUMBER_INFO: i32 = 9;
+
+/// The OTLP equivalent of a metric type.
+#[derive(Clone, Copy)]
+enum MetricKind {
+    Sum,
+    Gauge,
+    /// A distribution, with the default buckets per magnitude of its functional bucketing.
+    ///
+    /// `None` for custom distributions, whose bucketing is not part of the ping.
+    Histogram(Option<u32>),
+}
+
+impl MetricKind {
+    /// Gets the OTLP kind and unit of the metric type reported in `section`.
+    fn from_section(section: &str) -> Option<(Self, Option<&'static str>)> {
+        match section {
+            "counter" | "counter64" => Some((MetricKind::Sum, None)),
+            "quantity" => Some((MetricKind::Gauge, None)),
+            "timing_distribution" => Some((
+                MetricKind::Histogram(Some(timing_distribution::BUCKETS_PER_MAGNITUDE)),
+                Some("ns"),
+            )),
+            "memory_distribution" => Some((
+                MetricKind::Histogram(Some(memory_distribution::BUCKETS_PER_MAGNITUDE)),
+                Some("By"),
+            )),
+            "custom_distribution" => Some((MetricKind::Histogram(None), None)),
+            _ => None,
+        }
+    }
+}
+
+/// A ping, converted into OTLP payloads.
+#[derive(Debug)]
+pub struct OtlpExport {
+    /// The `ExportMetricsServiceRequest`, if the ping contains any exportable metrics.
+    pub metrics: Option<JsonValue>,
+    /// The `ExportLogsServiceRequest`, if the ping contains any events.
+    pub logs: Option<JsonValue>,
+}
+
+impl OtlpExport {
+    /// Converts a ping body, as passed to an uploader.
+    ///
+    /// # Arguments
+    ///
+    /// * `service_name` - The `service.name` to report the data for, usually the application id.
+    /// * `ping_name` - The name of the ping.
+    /// * `body` - The ping body. It may be gzip encoded.
+    /// * `include_client_id` - Whether to report the client id as the `glean.client_id` attribute.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the body is not a valid JSON ping.
+    pub fn from_body(
+        service_name: &str,
+        ping_name: &str,
+        body: &[u8],
+        include_client_id: bool,
+    ) -> Option<Self> {
+        let mut gz = GzDecoder::new(body);
+        let mut decoded = String::with_capacity(body.len());
+
+        let ping = match gz.read_to_string(&mut decoded) {
+            Ok(_) => serde_json::from_str(&decoded).ok()?,
+            Err(_) => serde_json::from_slice(body).ok()?,
+        };
+        Some(Self::from_ping(
+            service_name,
+            ping_name,
+            &ping,
+            include_client_id,
+        ))
+    }
+
+    /// Converts an assembled ping, as returned by the ping maker.
+    ///
+    /// # Arguments
+    ///
+    /// * `service_name` - The `service.name` to report the data for, usually the application id.
+    /// * `ping_name` - The name of the ping.
+    /// * `ping` - The JSON payload of the ping.
+    /// * `include_client_id` - Whether to report the client id as the `glean.client_id` attribute.
+    pub fn from_ping(
+        service_name: &str,
+        ping_name: &str,
+        ping: &JsonValue,
+        include_client_id: bool,
+    ) -> Self {
+        let ping_info = &ping["ping_info"];
+        let start_time = parse_time(&ping_info["start_time"]);
+        let end_time = parse_time(&ping_info["end_time"]);
+
+        let attributes = resource_attributes(service_name, ping_name, ping, include_client_id);
+        let resource = json!({ "attributes": attributes });
+        let scope = json!({
+            "name": "glean",
+            "version": ping["client_info"]["telemetry_sdk_build"],
+        });
+
+        let metrics = convert_metrics(&ping["metrics"], start_time, end_time);
+        let metrics = (!metrics.is_empty()).then(|| {
+            json!({
+                "resourceMetrics": [{
+                    "resource": resource,
+                    "scopeMetrics": [{ "scope": scope, "metrics": metrics }],
+                }],
+            })
+        });
+
+        let log_records = convert_events(&ping["events"], start_time, end_time);
+        let logs = (!log_records.is_empty()).then(|| {
+            json!({
+                "resourceLogs": [{
+                    "resource": resource,
+                    "scopeLogs": [{ "scope": scope, "logRecords": log_records }],
+                }],
+            })
+        });
+
+        Self { metrics, logs }
+    }
+}
+
+/// Parses a `ping_info` time into nanoseconds since the UNIX epoch.
+///
+/// Returns 0, meaning "unknown" in OTLP, if the time can't be parsed.
+fn parse_time(value: &JsonValue) -> u64 {
+    value
+        .as_str()
+        .and_then(|time| {
+            // `ping_info` times are truncated to minutes, which RFC 3339 doesn't allow.
+            DateTime::parse_from_rfc3339(time)
+                .or_else(|_| DateTime::<FixedOffset>::parse_from_str(time, "%Y-%m-%dT%H:%M%:z"))
+                .ok()
+        })
+        .and_then(|time| time.timestamp_nanos_opt())
+        .map_or(0, |nanos| nanos.max(0) as u64)
+}
+
+/// Builds an OTLP attribute with a string value.
+fn attribute(key: &str, value: &str) -> JsonValue {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+fn resource_attributes(
+    service_name: &str,
+    ping_name: &str,
+    ping: &JsonValue,
+    include_client_id: bool,
+) -> Vec<JsonValue> {
+    let mut attributes = vec![
+        attribute("service.name", service_name),
+        attribute("telemetry.sdk.name", "glean"),
+        attribute("glean.ping.name", ping_name),
+    ];
+
+    let client_info = &ping["client_info"];
+    let optional = [
+        ("service.version", "app_display_version"),
+        ("os.type", "os"),
+        ("os.version", "os_version"),
+    ];
+    for (key, field) in optional {
+        if let Some(value) = client_info[field].as_str() {
+            attributes.push(attribute(key, value));
+        }
+    }
+
+    if include_client_id {
+        if let Some(client_id) = client_info["client_id"].as_str() {
+            attributes.push(attribute("glean.client_id", client_id));
+        }
+    }
+
+    attributes
+}
+
+/// Converts the `metrics` section of a ping into OTLP metrics.
+fn convert_metrics(metrics: &JsonValue, start_time: u64, end_time: u64) -> Vec<JsonValue> {
+    let Some(sections) = metrics.as_object() else {
+        return vec![];
+    };
+
+    let mut result = vec![];
+    for (section, metrics) in sections {
+        let (base_section, label_keys): (&str, &[&str]) =
+            if let Some(base) = section.strip_prefix("dual_labeled_") {
+                (base, &["glean.key", "glean.category"])
+            } else if let Some(base) = section.strip_prefix("labeled_") {
+                (base, &["glean.label"])
+            } else {
+                (section, &[])
+            };
+        let Some((kind, unit)) = MetricKind::from_section(base_section) else {
+            continue;
+        };
+        let Some(metrics) = metrics.as_object() else {
+            continue;
+        };
+
+        for (identifier, value) in metrics {
+            let mut data_points = vec![];
+            collect_data_points(kind, value, label_keys, &mut vec![], &mut data_points);
+            for data_point in &mut data_points {
+                if !matches!(kind, MetricKind::Gauge) {
+                    data_point["startTimeUnixNano"] = json!(start_time.to_string());
+                }
+                data_point["timeUnixNano"] = json!(end_time.to_string());
+            }
+            if data_points.is_empty() {
+                continue;
+            }
+
+            let mut metric = json!({ "name": identifier });
+            if let Some(unit) = unit {
+                metric["unit"] = json!(unit);
+            }
+            match kind {
+                MetricKind::Sum => {
+                    metric["sum"] = json!({
+                        "dataPoints": data_points,
+                        "aggregationTemporality": AGGREGATION_TEMPORALITY_DELTA,
+                        "isMonotonic": true,
+                    })
+                }
+                MetricKind::Gauge => metric["gauge"] = json!({ "dataPoints": data_points }),
+                MetricKind::Histogram(_) => {
+                    metric["histogram"] = json!({
+                        "dataPoints": data_points,
+                        "aggregationTemporality": AGGREGATION_TEMPORALITY_DELTA,
+                    })
+                }
+            }
+            result.push(metric);
+        }
+    }
+
+    result
+}
+
+/// Walks down the labels of a metric value and converts each leaf into a data point.
+fn collect_data_points(
+    kind: MetricKind,
+    value: &JsonValue,
+    label_keys: &[&str],
+    attributes: &mut Vec<JsonValue>,
+    data_points: &mut Vec<JsonValue>,
+) {
+    if let Some((label_key, label_keys)) = label_keys.split_first() {
+        let Some(labels) = value.as_object() else {
+            return;
+        };
+        for (label, value) in labels {
+            attributes.push(attribute(label_key, label));
+            collect_data_points(kind, value, label_keys, attributes, data_points);
+            attributes.pop();
+        }
+        return;
+    }
+
+    let data_point = match kind {
+        MetricKind::Sum | MetricKind::Gauge => value
+            .as_i64()
+            .map(|value| json!({ "asInt": value.to_string() })),
+        MetricKind::Histogram(buckets_per_magnitude) => {
+            histogram_data_point(value, buckets_per_magnitude)
+        }
+    };
+    if let Some(mut data_point) = data_point {
+        data_point["attributes"] = json!(attributes);
+        data_points.push(data_point);
+    }
+}
+
+/// Converts a distribution into a histogram data point.
+///
+/// Glean buckets are keyed by their inclusive lower bound and end before the next bucket,
+/// OTLP buckets end at their inclusive upper bound.
+/// As samples are integers, a Glean bucket `[min, next)` becomes the OTLP bucket `(min - 1, next - 1]`,
+/// with empty buckets filling the gaps between the recorded ones.
+///
+/// The edges of timing and memory distribution buckets are computed from their functional bucketing,
+/// with `default_buckets_per_magnitude` unless the ping reports another one.
+/// For custom distributions the bucketing is not part of the ping,
+/// so each bucket is taken to end where the next recorded bucket starts.
+fn histogram_data_point(
+    value: &JsonValue,
+    default_buckets_per_magnitude: Option<u32>,
+) -> Option<JsonValue> {
+    let sum = value["sum"].as_i64()?;
+    let mut buckets = value["values"]
+        .as_object()?
+        .iter()
+        .filter_map(|(bucket, count)| Some((bucket.parse::<i64>().ok()?, count.as_i64()?)))
+        .collect::<Vec<_>>();
+    buckets.sort_unstable();
+
+    let bucketing = default_buckets_per_magnitude.map(|default| {
+        let buckets_per_magnitude = value["buckets_per_magnitude"]
+            .as_u64()
+            .unwrap_or(default.into());
+        Functional::new(timing_distribution::LOG_BASE, buckets_per_magnitude as f64)
+    });
+
+    let mut explicit_bounds = vec![];
+    let mut bucket_counts = vec![];
+    let mut previous_end = None;
+    for (index, &(min, count)) in buckets.iter().enumerate() {
+        if previous_end != Some(min) {
+            explicit_bounds.push((min - 1) as f64);
+            bucket_counts.push(0);
+        }
+
+        let end = match &bucketing {
+            Some(bucketing) => u64::try_from(min)
+                .ok()
+                .and_then(|min| bucketing.bucket_upper_bound(min))
+                .and_then(|end| i64::try_from(end).ok()),
+            None => buckets.get(index + 1).map(|&(next, _)| next),
+        };
+        bucket_counts.push(count);
+        match end {
+            Some(end) => explicit_bounds.push((end - 1) as f64),
+            // An unbounded bucket is the last one.
+            None => break,
+        }
+        previous_end = end;
+    }
+    if bucket_counts.len() == explicit_bounds.len() {
+        bucket_counts.push(0);
+    }
+
+    let count: i64 = bucket_counts.iter().sum();
+    let bucket_counts = bucket_counts
+        .iter()
+        .map(|count| count.to_string())
+        .collect::<Vec<_>>();
+
+    Some(json!({
+        "count": count.to_string(),
+        "sum": sum as f64,
+        "bucketCounts": bucket_counts,
+        "explicitBounds": explicit_bounds,
+    }))
+}
+
+/// Converts the `events` section of a ping into OTLP log records.
+///
+/// Event timestamps are relative to the ping's start time,
+/// unless precise timestamps were recorded.
+fn convert_events(events: &JsonValue, start_time: u64, end_time: u64) -> Vec<JsonValue> {
+    let Some(events) = events.as_array() else {
+        return vec![];
+    };
+
+    events
+        .iter()
+        .filter_map(|event| {
+            let category = event["category"].as_str()?;
+            let name = event["name"].as_str()?;
+            let identifier = format!("{}.{}", category, name);
+
+            let extra = event["extra"].as_object().cloned().unwrap_or_else(Map::new);
+            let timestamp = extra
+                .get("glean_timestamp")
+                .and_then(|ts| ts.as_str()?.parse::<u64>().ok())
+                .map(|ms| ms * 1_000_000)
+                .unwrap_or_else(|| {
+                    let relative = event["timestamp"].as_u64().unwrap_or(0);
+                    start_time + relative * 1_000_000
+                });
+
+            let mut attributes = vec![attribute("event.name", &identifier)];
+            for (key, value) in &extra {
+                if key == "glean_timestamp" {
+                    continue;
+                }
+                if let Some(value) = value.as_str() {
+                    attributes.push(attribute(&format!("glean.extra.{}", key), value));
+                }
+            }
+
+            Some(json!({
+                "timeUnixNano": timestamp.to_string(),
+                "observedTimeUnixNano": end_time.to_string(),
+                "severityNumber": SEVERITY_NUMBER_INFO,
+                "severityText": "INFO",
+                "body": { "stringValue": identifier },
+                "attributes": attributes,
+            }))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_truncated_ping_times() {
+        assert_eq!(
+            1_700_000_040_000_000_000,
+            parse_time(&json!("2023-11-14T22:14+00:00"))
+        );
+        assert_eq!(0, parse_time(&json!("not a time")));
+    }
+
+    #[test]
+    fn converts_custom_distributions_into_histograms() {
+        let value = json!({ "sum": 30, "values": { "10": 1, "2": 2, "20": 0 } });
+        let data_point = histogram_data_point(&value, None).unwrap();
+
+        assert_eq!(json!("3"), data_point["count"]);
+        assert_eq!(json!(30.0), data_point["sum"]);
+        assert_eq!(json!(["0", "2", "1", "0"]), data_point["bucketCounts"]);
+        assert_eq!(json!([1.0, 9.0, 19.0]), data_point["explicitBounds"]);
+    }
+
+    #[test]
+    fn functional_bucket_edges_are_computed() {
+        // With 2 buckets per magnitude, the samples fall into [2, 4) and [1024, 1448).
+        let value = json!({
+            "sum": 1026,
+            "values": { "2": 1, "1024": 1 },
+            "buckets_per_magnitude": 2,
+        });
+        let data_point = histogram_data_point(&value, Some(8)).unwrap();
+
+        assert_eq!(json!("2"), data_point["count"]);
+        assert_eq!(json!(["0", "1", "0", "1", "0"]), data_point["bucketCounts"]);
+        assert_eq!(
+            json!([1.0, 3.0, 1023.0, 1447.0]),
+            data_point["explicitBounds"]
+        );
+
+        // Without a reported bucketing, the default one is used.
+        let value = json!({ "sum": 3, "values": { "1": 1, "2": 1 } });
+        let data_point = histogram_data_point(&value, Some(8)).unwrap();
+        assert_eq!(json!(["0", "1", "1", "0"]), data_point["bucketCounts"]);
+        assert_eq!(json!([0.0, 1.0, 2.0]), data_point["explicitBounds"]);
+    }
+
+    #[test]
+    fn client_id_is_only_exported_when_asked_for() {
+        let ping = json!({ "client_info": { "client_id": "c0ffee", "os": "Linux" } });
+        let has_client_id = |include_client_id| {
+            resource_attributes("app", "metrics", &ping, include_client_id)
+                .iter()
+                .any(|attr| attr["key"] == "glean.client_id")
+        };
+
+        assert!(!has_client_id(false));
+        assert!(has_client_id(true));
+    }
+
+    #[test]
+    fn labeled_metrics_carry_their_labels() {
+        let metrics = json!({
+            "labeled_counter": { "cat.name": { "a": 1, "b": 2 } },
+            "dual_labeled_counter": { "cat.dual": { "k": { "c": 3 } } },
+            "string": { "cat.str": "skipped" },
+        });
+        let metrics = convert_metrics(&metrics, 1, 2);
+        assert_eq!(2, metrics.len());
+
+        let dual = metrics.iter().find(|m| m["name"] == "cat.dual").unwrap();
+        assert_eq!(
+            json!(AGGREGATION_TEMPORALITY_DELTA),
+            dual["sum"]["aggregationTemporality"]
+        );
+        let data_point = &dual["sum"]["dataPoints"][0];
+        assert_eq!(json!("3"), data_point["asInt"]);
+        assert_eq!(json!("1"), data_point["startTimeUnixNano"]);
+        assert_eq!(json!("2"), data_point["timeUnixNano"]);
+        assert_eq!(
+            json!([
+                attribute("glean.key", "k"),
+                attribute("glean.category", "c")
+            ]),
+            data_point["attributes"]
+        );
+
+        let labeled = metrics.iter().find(|m| m["name"] == "cat.name").unwrap();
+        assert_eq!(2, labeled["sum"]["dataPoints"].as_array().unwrap().len());
+    }
+}
@@ -44,7 +44,7 @@ fn create_x_telemetry_agent_header_value(
 }
 
 /// Attempt to gzip the contents of a ping.
-fn gzip_content(path: &str, content: &[u8]) -> Option<Vec<u8>> {
+pub(crate) fn gzip_content(path: &str, content: &[u8]) -> Option<Vec<u8>> {
     let mut gzipper = GzEncoder::new(Vec::new(), Compression::default());
 
     // Attempt to add the content to the gzipper.
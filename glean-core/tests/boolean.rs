@@ -9,6 +9,7 @@ use serde_json::json;
 
 use glean_core::metrics::*;
 use glean_core::storage::StorageManager;
+use glean_core::{test_get_num_recorded_errors, ErrorType};
 use glean_core::{CommonMetricData, Lifetime};
 
 // SKIPPED from glean-ac: string deserializer should correctly parse integers
@@ -89,3 +90,117 @@ fn set_properly_sets_the_value_in_all_stores() {
 
 // SKIPPED from glean-ac: booleans are serialized in the correct JSON format
 // Completely redundant with other tests.
+
+#[test]
+fn privatized_boolean_reports_its_epsilon() {
+    let (glean, _t) = new_glean(None);
+
+    let metric = BooleanMetric::new(CommonMetricData {
+        name: "boolean_metric".into(),
+        category: "telemetry".into(),
+        send_in_pings: vec!["store1".into()],
+        lifetime: Lifetime::Ping,
+        ldp_epsilon: Some(1.5),
+        ..Default::default()
+    });
+
+    metric.set_sync(&glean, true);
+    // The stored value went through randomized response, so it can be either.
+    assert!(metric.get_value(&glean, Some("store1")).is_some());
+
+    let snapshot = StorageManager
+        .snapshot_as_json(glean.storage(), "store1", true)
+        .unwrap();
+    assert!(snapshot["boolean"]["telemetry.boolean_metric"].is_boolean());
+    assert_eq!(
+        json!({"telemetry.boolean_metric": 1.5}),
+        snapshot["ldp_epsilon"]
+    );
+}
+
+#[test]
+fn privatized_labeled_boolean_reports_its_epsilon_once() {
+    let (glean, _t) = new_glean(None);
+
+    let labeled = LabeledBoolean::new(
+        LabeledMetricData::Common {
+            cmd: CommonMetricData {
+                name: "labeled_metric".into(),
+                category: "telemetry".into(),
+                send_in_pings: vec!["store1".into()],
+                lifetime: Lifetime::Ping,
+                ldp_epsilon: Some(0.5),
+                ..Default::default()
+            },
+        },
+        Some(vec!["label1".into(), "label2".into()]),
+    );
+
+    labeled.get("label1").set_sync(&glean, true);
+    labeled.get("label2").set_sync(&glean, false);
+
+    let snapshot = StorageManager
+        .snapshot_as_json(glean.storage(), "store1", true)
+        .unwrap();
+    let values = &snapshot["labeled_boolean"]["telemetry.labeled_metric"];
+    assert_eq!(2, values.as_object().unwrap().len());
+    assert_eq!(
+        json!({"telemetry.labeled_metric": 0.5}),
+        snapshot["ldp_epsilon"]
+    );
+}
+
+#[test]
+fn invalid_epsilon_records_nothing() {
+    let (glean, _t) = new_glean(None);
+
+    let metric = BooleanMetric::new(CommonMetricData {
+        name: "boolean_metric".into(),
+        category: "telemetry".into(),
+        send_in_pings: vec!["store1".into()],
+        lifetime: Lifetime::Ping,
+        ldp_epsilon: Some(0.0),
+        ..Default::default()
+    });
+
+    metric.set_sync(&glean, true);
+    assert!(metric.get_value(&glean, Some("store1")).is_none());
+    assert_eq!(
+        Ok(1),
+        test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidValue)
+    );
+}
+
+#[test]
+fn dual_labeled_boolean_is_serialized_in_its_own_section() {
+    let (glean, _t) = new_glean(None);
+    let dual_labeled = DualLabeledBooleanMetric::new(
+        CommonMetricData {
+            name: "feature_enabled".into(),
+            category: "telemetry".into(),
+            send_in_pings: vec!["store1".into()],
+            disabled: false,
+            lifetime: Lifetime::Ping,
+            ..Default::default()
+        },
+        None,
+        Some(vec!["release".into()]),
+    );
+
+    dual_labeled.get("sync", "release").set_sync(&glean, true);
+    dual_labeled.get("sync", "beta").set_sync(&glean, false);
+
+    let snapshot = StorageManager
+        .snapshot_as_json(glean.storage(), "store1", true)
+        .unwrap();
+    assert_eq!(
+        json!({
+            "dual_labeled_boolean": {
+                "telemetry.feature_enabled": {
+                    "sync": { "release": true, "__other__": false }
+                }
+            }
+        }),
+        snapshot
+    );
+}
@@ -25,6 +25,8 @@ fn clientid_metric() -> UuidMetric {
         disabled: false,
         dynamic_label: None,
         in_session: false,
+        ldp_epsilon: None,
+        ldp_max_increment: None,
     })
 }
 
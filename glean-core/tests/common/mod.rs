@@ -5,7 +5,7 @@
 // #[allow(dead_code)] is required on this module as a workaround for
 // https://github.com/rust-lang/rust/issues/46379
 #![allow(dead_code)]
-use glean_core::{Glean, PingType, Result};
+use glean_core::{Glean, PingOptions, PingType, Result};
 
 use std::fs::{read_dir, File};
 use std::io::{BufRead, BufReader};
@@ -39,20 +39,13 @@ pub fn tempdir() -> (tempfile::TempDir, String) {
 
 pub const GLOBAL_APPLICATION_ID: &str = "org.mozilla.glean.test.app";
 
-/// Creates a new instance of Glean with a temporary directory, with `upload_enabled` specified.
-///
-/// We need to keep the `TempDir` alive, so that it's not deleted before we stop using it.
-pub fn new_glean_with_upload(
-    tempdir: Option<tempfile::TempDir>,
+/// The configuration tests create Glean with, with `upload_enabled` specified.
+pub fn test_configuration(
+    data_path: &Path,
     upload_enabled: bool,
-) -> (Glean, tempfile::TempDir) {
-    let dir = match tempdir {
-        Some(tempdir) => tempdir,
-        None => tempfile::tempdir().unwrap(),
-    };
-
-    let cfg = glean_core::InternalConfiguration {
-        data_path: dir.path().display().to_string(),
+) -> glean_core::InternalConfiguration {
+    glean_core::InternalConfiguration {
+        data_path: data_path.display().to_string(),
         application_id: GLOBAL_APPLICATION_ID.into(),
         language_binding_name: "Rust".into(),
         upload_enabled,
@@ -71,10 +64,27 @@ pub fn new_glean_with_upload(
         ping_lifetime_max_time: 0,
         max_pending_pings_count: None,
         max_pending_pings_directory_size: None,
+        database_size_budget: None,
+        event_store_limits: None,
         session_mode: glean_core::SessionMode::Auto,
         session_sample_rate: 1.0,
         session_inactivity_timeout_ms: 1_800_000,
+    }
+}
+
+/// Creates a new instance of Glean with a temporary directory, with `upload_enabled` specified.
+///
+/// We need to keep the `TempDir` alive, so that it's not deleted before we stop using it.
+pub fn new_glean_with_upload(
+    tempdir: Option<tempfile::TempDir>,
+    upload_enabled: bool,
+) -> (Glean, tempfile::TempDir) {
+    let dir = match tempdir {
+        Some(tempdir) => tempdir,
+        None => tempfile::tempdir().unwrap(),
     };
+
+    let cfg = test_configuration(dir.path(), upload_enabled);
     let mut glean = Glean::new(cfg).unwrap();
 
     // store{1,2} is used throughout tests
@@ -108,6 +118,7 @@ pub struct PingBuilder {
     reason_codes: Vec<String>,
     follows_collection_enabled: bool,
     uploader_capabilities: Vec<String>,
+    options: PingOptions,
 }
 
 impl PingBuilder {
@@ -123,11 +134,12 @@ impl PingBuilder {
             reason_codes: vec![],
             follows_collection_enabled: true,
             uploader_capabilities: vec![],
+            options: PingOptions::default(),
         }
     }
 
     pub fn build(self) -> PingType {
-        PingType::new(
+        PingType::new_with_options(
             self.name,
             self.include_client_id,
             self.send_if_empty,
@@ -138,6 +150,7 @@ impl PingBuilder {
             self.reason_codes,
             self.follows_collection_enabled,
             self.uploader_capabilities,
+            self.options,
         )
     }
 
@@ -170,6 +183,16 @@ impl PingBuilder {
         self.reason_codes = value;
         self
     }
+
+    pub fn with_include_client_id(mut self, value: bool) -> Self {
+        self.include_client_id = value;
+        self
+    }
+
+    pub fn with_options(mut self, value: PingOptions) -> Self {
+        self.options = value;
+        self
+    }
 }
 
 /// Gets a vector of the currently queued pings.
@@ -172,3 +172,83 @@ fn saturates_at_boundary() {
 
     assert_eq!(i32::MAX, counter.get_value(&glean, Some("store1")).unwrap());
 }
+
+#[test]
+fn privatized_counter_adds_noise_and_reports_its_epsilon() {
+    let (glean, _t) = new_glean(None);
+
+    let counter = CounterMetric::new(CommonMetricData {
+        name: "counter_metric".into(),
+        category: "telemetry".into(),
+        send_in_pings: vec!["store1".into()],
+        lifetime: Lifetime::Ping,
+        ldp_epsilon: Some(1.0),
+        ..Default::default()
+    });
+
+    for _ in 0..1000 {
+        counter.add_sync(&glean, 1);
+    }
+
+    // The noise has a mean of 0 and a variance of about 1.84 per increment,
+    // so the sum stays well within 10 standard deviations of the true count.
+    let value = counter.get_value(&glean, Some("store1")).unwrap();
+    assert!((value - 1000).abs() < 430, "{value}");
+
+    let snapshot = StorageManager
+        .snapshot_as_json(glean.storage(), "store1", true)
+        .unwrap();
+    assert_eq!(
+        json!(value),
+        snapshot["counter"]["telemetry.counter_metric"]
+    );
+    // Every increment spends the epsilon.
+    assert_eq!(
+        json!({"telemetry.counter_metric": 1000.0}),
+        snapshot["ldp_epsilon"]
+    );
+}
+
+#[test]
+fn privatized_counter_clamps_increments_to_the_declared_maximum() {
+    let (glean, _t) = new_glean(None);
+
+    let counter = CounterMetric::new(CommonMetricData {
+        name: "counter_metric".into(),
+        category: "telemetry".into(),
+        send_in_pings: vec!["store1".into()],
+        lifetime: Lifetime::Ping,
+        ldp_epsilon: Some(1.0),
+        ldp_max_increment: Some(10),
+        ..Default::default()
+    });
+
+    counter.add_sync(&glean, 1_000_000);
+
+    // The noise is scaled to the maximum of 10, with a standard deviation of about 14.
+    let value = counter.get_value(&glean, Some("store1")).unwrap();
+    assert!((value - 10).abs() < 200, "{value}");
+    // Clamping isn't reported, as the error would reveal the true increment.
+    assert!(test_get_num_recorded_errors(&glean, counter.meta(), ErrorType::InvalidValue).is_err());
+}
+
+#[test]
+fn privatized_counter_rejects_invalid_epsilon() {
+    let (glean, _t) = new_glean(None);
+
+    let counter = CounterMetric::new(CommonMetricData {
+        name: "counter_metric".into(),
+        category: "telemetry".into(),
+        send_in_pings: vec!["store1".into()],
+        lifetime: Lifetime::Ping,
+        ldp_epsilon: Some(f64::NAN),
+        ..Default::default()
+    });
+
+    counter.add_sync(&glean, 1);
+    assert!(counter.get_value(&glean, Some("store1")).is_none());
+    assert_eq!(
+        Ok(1),
+        test_get_num_recorded_errors(&glean, counter.meta(), ErrorType::InvalidValue)
+    );
+}
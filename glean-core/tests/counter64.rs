@@ -0,0 +1,146 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+mod common;
+use crate::common::*;
+
+use serde_json::json;
+
+use glean_core::metrics::*;
+use glean_core::storage::StorageManager;
+use glean_core::{test_get_num_recorded_errors, ErrorType};
+use glean_core::{CommonMetricData, LabeledMetricData, Lifetime};
+
+fn counter64_meta(lifetime: Lifetime) -> CommonMetricData {
+    CommonMetricData {
+        name: "bytes_sent".into(),
+        category: "telemetry".into(),
+        send_in_pings: vec!["store1".into()],
+        disabled: false,
+        lifetime,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn counter64_serializer_should_correctly_serialize_counters() {
+    let (mut tempdir, _) = tempdir();
+
+    {
+        let (glean, dir) = new_glean(Some(tempdir));
+        tempdir = dir;
+
+        let metric = Counter64Metric::new(counter64_meta(Lifetime::User));
+        metric.add_sync(&glean, i32::MAX as i64);
+        metric.add_sync(&glean, i32::MAX as i64);
+    }
+
+    // Make a new Glean instance here, which should force reloading of the data from disk
+    // so we can ensure it persisted, because it has User lifetime
+    {
+        let (glean, _t) = new_glean(Some(tempdir));
+        let snapshot = StorageManager
+            .snapshot_as_json(glean.storage(), "store1", true)
+            .unwrap();
+        assert_eq!(
+            json!({"counter64": {"telemetry.bytes_sent": 2 * (i32::MAX as i64)}}),
+            snapshot
+        );
+    }
+}
+
+#[test]
+fn counter64_and_counter_do_not_share_values() {
+    let (glean, _t) = new_glean(None);
+
+    let counter = CounterMetric::new(counter64_meta(Lifetime::Ping));
+    counter.add_sync(&glean, 1);
+
+    // A 64-bit counter doesn't pick up a 32-bit value stored under the same identifier.
+    let metric = Counter64Metric::new(counter64_meta(Lifetime::Ping));
+    assert_eq!(None, metric.get_value(&glean, "store1"));
+    metric.add_sync(&glean, 5);
+    assert_eq!(Some(5), metric.get_value(&glean, "store1"));
+}
+
+#[test]
+fn counter64_records_overflow() {
+    let (glean, _t) = new_glean(None);
+    let metric = Counter64Metric::new(counter64_meta(Lifetime::Ping));
+
+    metric.add_sync(&glean, i64::MAX - 1);
+    metric.add_sync(&glean, 2);
+
+    assert_eq!(Some(i64::MAX), metric.get_value(&glean, "store1"));
+    assert_eq!(
+        Ok(1),
+        test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidOverflow)
+    );
+}
+
+#[test]
+fn counter64_rejects_negative_values() {
+    let (glean, _t) = new_glean(None);
+    let metric = Counter64Metric::new(counter64_meta(Lifetime::Ping));
+
+    metric.add_sync(&glean, -1);
+    metric.add_sync(&glean, 0);
+
+    assert_eq!(None, metric.get_value(&glean, "store1"));
+    assert_eq!(
+        Ok(1),
+        test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidValue)
+    );
+}
+
+#[test]
+fn labeled_counter64_is_serialized_in_its_own_section() {
+    let (glean, _t) = new_glean(None);
+    let labeled = LabeledCounter64::new(
+        LabeledMetricData::Common {
+            cmd: counter64_meta(Lifetime::Ping),
+        },
+        Some(vec!["wifi".into()]),
+    );
+
+    labeled.get("wifi").add_sync(&glean, 1 << 40);
+    labeled.get("cellular").add_sync(&glean, 1);
+
+    let snapshot = StorageManager
+        .snapshot_as_json(glean.storage(), "store1", true)
+        .unwrap();
+    assert_eq!(
+        json!({
+            "labeled_counter64": {
+                "telemetry.bytes_sent": { "wifi": 1_i64 << 40, "__other__": 1 }
+            }
+        }),
+        snapshot
+    );
+}
+
+#[test]
+fn dual_labeled_counter64_is_serialized_in_its_own_section() {
+    let (glean, _t) = new_glean(None);
+    let dual_labeled = DualLabeledCounter64Metric::new(
+        counter64_meta(Lifetime::Ping),
+        Some(vec!["upload".into()]),
+        None,
+    );
+
+    dual_labeled.get("upload", "wifi").add_sync(&glean, 1 << 40);
+    dual_labeled.get("upload", "wifi").add_sync(&glean, 1);
+
+    let snapshot = StorageManager
+        .snapshot_as_json(glean.storage(), "store1", true)
+        .unwrap();
+    assert_eq!(
+        json!({
+            "dual_labeled_counter64": {
+                "telemetry.bytes_sent": { "upload": { "wifi": (1_i64 << 40) + 1 } }
+            }
+        }),
+        snapshot
+    );
+}
@@ -417,3 +417,106 @@ mod exponential {
         assert!(snapshot.is_some());
     }
 }
+
+#[test]
+fn dual_labeled_custom_distribution_shares_bucketing() {
+    let (glean, _t) = new_glean(None);
+    let dual_labeled = DualLabeledCustomDistributionMetric::new(
+        CommonMetricData {
+            name: "frame_sizes".into(),
+            category: "telemetry".into(),
+            send_in_pings: vec!["store1".into()],
+            disabled: false,
+            lifetime: Lifetime::Ping,
+            ..Default::default()
+        },
+        1,
+        100,
+        10,
+        HistogramType::Linear,
+        None,
+        None,
+    );
+
+    dual_labeled
+        .get("video", "hd")
+        .accumulate_samples_sync(&glean, &[1, 2, 3]);
+    dual_labeled
+        .get("video", "sd")
+        .accumulate_samples_sync(&glean, &[50]);
+
+    let hd = dual_labeled
+        .get("video", "hd")
+        .get_value(&glean, "store1")
+        .unwrap();
+    assert_eq!(6, hd.sum);
+    assert_eq!(3, hd.count);
+
+    let sd = dual_labeled
+        .get("video", "sd")
+        .get_value(&glean, "store1")
+        .unwrap();
+    assert_eq!(50, sd.sum);
+    assert_eq!(1, sd.count);
+
+    let snapshot = StorageManager
+        .snapshot_as_json(glean.storage(), "store1", true)
+        .unwrap();
+    assert_eq!(
+        json!(6),
+        snapshot["dual_labeled_custom_distribution"]["telemetry.frame_sizes"]["video"]["hd"]["sum"]
+    );
+}
+
+fn linear_metric(name: &str) -> CustomDistributionMetric {
+    CustomDistributionMetric::new(
+        CommonMetricData {
+            name: name.into(),
+            category: "telemetry".into(),
+            send_in_pings: vec!["store1".into()],
+            disabled: false,
+            lifetime: Lifetime::Ping,
+            ..Default::default()
+        },
+        1,
+        100,
+        100,
+        HistogramType::Linear,
+    )
+}
+
+#[test]
+fn distribution_data_statistics() {
+    let (glean, _t) = new_glean(None);
+    let metric = linear_metric("statistics");
+
+    assert_eq!(None, metric.get_value(&glean, "store1"));
+
+    metric.accumulate_samples_sync(&glean, &(1..=10).collect::<Vec<_>>());
+    let data = metric.get_value(&glean, "store1").unwrap();
+
+    assert_eq!(Some(5.5), data.mean());
+    assert_eq!(Some(1), data.percentile(0.0));
+    assert_eq!(Some(5), data.percentile(50.0));
+    assert_eq!(Some(9), data.percentile(90.0));
+    assert_eq!(Some(10), data.percentile(100.0));
+    assert_eq!(None, data.percentile(-1.0));
+    assert_eq!(None, data.percentile(100.1));
+}
+
+#[test]
+fn distribution_data_ascii_histogram() {
+    let (glean, _t) = new_glean(None);
+    let metric = linear_metric("histogram");
+
+    metric.accumulate_samples_sync(&glean, &[1, 2, 2, 3, 3, 3, 3, 30]);
+    let data = metric.get_value(&glean, "store1").unwrap();
+
+    let expected = concat!(
+        " 1 | #    1\n",
+        " 2 | ##   2\n",
+        " 3 | #### 4\n",
+        "30 | #    1\n",
+    );
+    assert_eq!(expected, data.ascii_histogram(4));
+}
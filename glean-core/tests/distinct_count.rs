@@ -0,0 +1,81 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+mod common;
+use crate::common::*;
+
+use glean_core::metrics::*;
+use glean_core::storage::StorageManager;
+use glean_core::{test_get_num_recorded_errors, ErrorType};
+use glean_core::{CommonMetricData, Lifetime};
+
+fn distinct_count_metric(lifetime: Lifetime) -> DistinctCountMetric {
+    DistinctCountMetric::new(CommonMetricData {
+        name: "distinct_count_metric".into(),
+        category: "telemetry".into(),
+        send_in_pings: vec!["store1".into()],
+        disabled: false,
+        lifetime,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn distinct_count_serializer_should_correctly_serialize_sketches() {
+    let (mut tempdir, _) = tempdir();
+
+    {
+        // We give tempdir to the `new_glean` function...
+        let (glean, dir) = new_glean(Some(tempdir));
+        // And then we get it back once that function returns.
+        tempdir = dir;
+
+        let metric = distinct_count_metric(Lifetime::User);
+        metric.add_sync(&glean, "example.com");
+        metric.add_sync(&glean, "mozilla.org");
+        metric.add_sync(&glean, "example.com");
+    }
+
+    // Make a new Glean instance here, which should force reloading of the data from disk
+    // so we can ensure it persisted, because it has User lifetime
+    {
+        let (glean, _t) = new_glean(Some(tempdir));
+        let snapshot = StorageManager
+            .snapshot_as_json(glean.storage(), "store1", true)
+            .unwrap();
+
+        let sketch = &snapshot["distinct_count"]["telemetry.distinct_count_metric"];
+        assert_eq!(2, sketch["estimate"]);
+        assert_eq!(10, sketch["precision"]);
+        // Only the non-empty registers are reported.
+        assert_eq!(2, sketch["registers"].as_object().unwrap().len());
+    }
+}
+
+#[test]
+fn distinct_values_are_counted_once() {
+    let (glean, _t) = new_glean(None);
+    let metric = distinct_count_metric(Lifetime::Ping);
+
+    for i in 0..50 {
+        metric.add_sync(&glean, &format!("value-{}", i % 10));
+    }
+
+    let hll = metric.get_value(&glean, "store1").unwrap();
+    assert_eq!(10, hll.estimate());
+}
+
+#[test]
+fn distinct_count_must_not_add_empty_values() {
+    let (glean, _t) = new_glean(None);
+    let metric = distinct_count_metric(Lifetime::Application);
+
+    metric.add_sync(&glean, "");
+    assert!(metric.get_value(&glean, "store1").is_none());
+
+    assert_eq!(
+        Ok(1),
+        test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidValue)
+    );
+}
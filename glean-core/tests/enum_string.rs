@@ -0,0 +1,114 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+mod common;
+use crate::common::*;
+
+use serde_json::json;
+
+use glean_core::metrics::*;
+use glean_core::storage::StorageManager;
+use glean_core::{test_get_num_recorded_errors, ErrorType};
+use glean_core::{CommonMetricData, Lifetime};
+
+fn enum_string_metric(lifetime: Lifetime) -> EnumStringMetric {
+    EnumStringMetric::new(
+        CommonMetricData {
+            name: "theme".into(),
+            category: "telemetry".into(),
+            send_in_pings: vec!["store1".into()],
+            disabled: false,
+            lifetime,
+            ..Default::default()
+        },
+        vec!["light".into(), "dark".into(), "system".into()],
+    )
+}
+
+#[test]
+fn enum_string_serializer_should_report_the_value() {
+    let (mut tempdir, _) = tempdir();
+
+    {
+        let (glean, dir) = new_glean(Some(tempdir));
+        tempdir = dir;
+
+        let metric = enum_string_metric(Lifetime::User);
+        metric.set_sync(&glean, "dark");
+    }
+
+    // Make a new Glean instance here, which should force reloading of the data from disk
+    // so we can ensure it persisted, because it has User lifetime
+    {
+        let (glean, _t) = new_glean(Some(tempdir));
+        let snapshot = StorageManager
+            .snapshot_as_json(glean.storage(), "store1", true)
+            .unwrap();
+        assert_eq!(
+            json!({"enum_string": {"telemetry.theme": "dark"}}),
+            snapshot
+        );
+    }
+}
+
+#[test]
+fn enum_string_records_allowed_values() {
+    let (glean, _t) = new_glean(None);
+    let metric = enum_string_metric(Lifetime::Ping);
+
+    metric.set_sync(&glean, "light");
+    assert_eq!(Some("light".into()), metric.get_value(&glean, "store1"));
+
+    metric.set_sync(&glean, "system");
+    assert_eq!(Some("system".into()), metric.get_value(&glean, "store1"));
+
+    assert!(test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidValue).is_err());
+}
+
+#[test]
+fn unknown_values_are_recorded_as_other() {
+    let (glean, _t) = new_glean(None);
+    let metric = enum_string_metric(Lifetime::Ping);
+
+    metric.set_sync(&glean, "Dark");
+
+    assert_eq!(Some("__other__".into()), metric.get_value(&glean, "store1"));
+    assert_eq!(
+        Ok(1),
+        test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidValue)
+    );
+
+    let snapshot = StorageManager
+        .snapshot_as_json(glean.storage(), "store1", true)
+        .unwrap();
+    assert_eq!("__other__", snapshot["enum_string"]["telemetry.theme"]);
+}
+
+#[test]
+fn only_the_index_of_the_value_is_stored() {
+    let (glean, _t) = new_glean(None);
+    let metric = enum_string_metric(Lifetime::Ping);
+
+    metric.set_sync(&glean, "dark");
+    assert_eq!(
+        Some(Metric::EnumString(2)),
+        StorageManager.snapshot_metric(
+            glean.storage(),
+            "store1",
+            "telemetry.theme",
+            Lifetime::Ping
+        )
+    );
+
+    metric.set_sync(&glean, "unknown");
+    assert_eq!(
+        Some(Metric::EnumString(0)),
+        StorageManager.snapshot_metric(
+            glean.storage(),
+            "store1",
+            "telemetry.theme",
+            Lifetime::Ping
+        )
+    );
+}
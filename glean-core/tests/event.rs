@@ -555,6 +555,8 @@ fn with_event_timestamps() {
         ping_lifetime_max_time: 0,
         max_pending_pings_count: None,
         max_pending_pings_directory_size: None,
+        database_size_budget: None,
+        event_store_limits: None,
         session_mode: glean_core::SessionMode::Auto,
         session_sample_rate: 1.0,
         session_inactivity_timeout_ms: 1_800_000,
@@ -626,3 +628,183 @@ fn doesnt_crash_on_inaccessible_event_store_file() {
         assert!(events[0].extra.is_none());
     }
 }
+
+fn new_glean_with_event_store_limits(
+    limits: glean_core::EventStoreLimits,
+) -> (Glean, tempfile::TempDir) {
+    let dir = tempfile::tempdir().unwrap();
+    let cfg = glean_core::InternalConfiguration {
+        event_store_limits: Some(limits),
+        ..test_configuration(dir.path(), true)
+    };
+    let mut glean = Glean::new(cfg).unwrap();
+    _ = new_test_ping(&mut glean, "store1");
+    (glean, dir)
+}
+
+fn numbered_event(store_name: &str) -> (EventMetric, CommonMetricData) {
+    let meta = CommonMetricData {
+        name: "click".into(),
+        category: "ui".into(),
+        send_in_pings: vec![store_name.into()],
+        lifetime: Lifetime::Ping,
+        ..Default::default()
+    };
+    let event = EventMetric::new(meta.clone(), vec!["number".into()]);
+    (event, meta)
+}
+
+fn record_numbered(glean: &Glean, event: &EventMetric, range: std::ops::Range<u64>) {
+    for i in range {
+        let extra = [("number".to_string(), i.to_string())].into();
+        event.record_sync(glean, i, extra, 0);
+    }
+}
+
+#[test]
+fn custom_ping_is_submitted_when_its_store_is_full() {
+    let (mut glean, _t) = new_glean_with_event_store_limits(glean_core::EventStoreLimits {
+        max_events: Some(5),
+        max_bytes: None,
+        drop_oldest: false,
+    });
+    glean.register_ping_type(
+        &PingBuilder::new("custom")
+            .with_reasons(vec!["max_capacity".to_string()])
+            .build(),
+    );
+
+    let (event, _) = numbered_event("custom");
+    record_numbered(&glean, &event, 0..7);
+
+    let pings = get_queued_pings(glean.get_data_path()).unwrap();
+    assert_eq!(1, pings.len());
+    let (url, json, _) = &pings[0];
+    assert!(url.contains("/custom/"));
+    assert_eq!(5, json["events"].as_array().unwrap().len());
+    assert_eq!("max_capacity", json["ping_info"]["reason"]);
+
+    let remaining = event.get_value(&glean, "custom").unwrap();
+    assert_eq!(2, remaining.len());
+    assert_eq!("5", remaining[0].extra.as_ref().unwrap()["number"]);
+}
+
+#[test]
+fn oldest_events_are_dropped_when_configured() {
+    let (glean, _t) = new_glean_with_event_store_limits(glean_core::EventStoreLimits {
+        max_events: Some(10),
+        max_bytes: None,
+        drop_oldest: true,
+    });
+
+    let (event, meta) = numbered_event("store1");
+    record_numbered(&glean, &event, 0..11);
+
+    // Trimmed down to 90% of the limit.
+    let events = event.get_value(&glean, "store1").unwrap();
+    assert_eq!(9, events.len());
+    assert_eq!("2", events[0].extra.as_ref().unwrap()["number"]);
+    assert_eq!("10", events[8].extra.as_ref().unwrap()["number"]);
+    assert_eq!(
+        Ok(2),
+        test_get_num_recorded_errors(&glean, &meta.into(), ErrorType::InvalidOverflow)
+    );
+}
+
+#[test]
+fn oldest_events_are_dropped_when_store_exceeds_byte_limit() {
+    let max_bytes = 1000;
+    let (glean, t) = new_glean_with_event_store_limits(glean_core::EventStoreLimits {
+        max_events: None,
+        max_bytes: Some(max_bytes),
+        drop_oldest: true,
+    });
+
+    let (event, meta) = numbered_event("store1");
+    record_numbered(&glean, &event, 0..200);
+
+    let size = fs::metadata(t.path().join("events").join("store1"))
+        .unwrap()
+        .len();
+    assert!(size <= max_bytes, "store has {size} bytes");
+
+    let events = event.get_value(&glean, "store1").unwrap();
+    assert_eq!(
+        "199",
+        events.last().unwrap().extra.as_ref().unwrap()["number"]
+    );
+    let dropped = test_get_num_recorded_errors(&glean, &meta.into(), ErrorType::InvalidOverflow);
+    assert_eq!(Ok(200 - events.len() as i32), dropped);
+}
+
+#[test]
+fn typed_extras_are_validated() {
+    let (glean, _t) = new_glean(None);
+
+    let store_name = "store1";
+    let metric = EventMetric::new_with_extra_types(
+        CommonMetricData {
+            name: "download".into(),
+            category: "telemetry".into(),
+            send_in_pings: vec![store_name.into()],
+            disabled: false,
+            lifetime: Lifetime::Ping,
+            ..Default::default()
+        },
+        HashMap::from([
+            ("source".to_string(), ExtraKeyType::String),
+            ("size".to_string(), ExtraKeyType::Quantity),
+            ("resumed".to_string(), ExtraKeyType::Boolean),
+        ]),
+    );
+
+    let extra = HashMap::from([
+        ("source".to_string(), "menu".to_string()),
+        ("size".to_string(), "1024".to_string()),
+        ("resumed".to_string(), "false".to_string()),
+    ]);
+    metric.record_sync(&glean, 1000, extra.clone(), 0);
+
+    // Values that don't match their type drop the event.
+    for (key, value) in [
+        ("size", "big"),
+        ("size", "-1"),
+        ("size", "1.5"),
+        ("resumed", "yes"),
+        ("resumed", "True"),
+    ] {
+        let extra = HashMap::from([(key.to_string(), value.to_string())]);
+        metric.record_sync(&glean, 2000, extra, 0);
+    }
+
+    let events = metric.get_value(&glean, store_name).unwrap();
+    assert_eq!(1, events.len());
+    assert_eq!(Some(extra), events[0].extra);
+    assert_eq!(
+        Ok(5),
+        test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidType)
+    );
+    assert!(test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidValue).is_err());
+}
+
+#[test]
+fn oldest_events_are_dropped_when_the_full_store_cant_be_submitted() {
+    let (glean, _t) = new_glean_with_event_store_limits(glean_core::EventStoreLimits {
+        max_events: Some(10),
+        max_bytes: None,
+        drop_oldest: false,
+    });
+
+    // Always enabled, but not a ping that can be submitted.
+    let (event, meta) = numbered_event("all-pings");
+    record_numbered(&glean, &event, 0..20);
+
+    let events = event.get_value(&glean, "all-pings").unwrap();
+    assert!(events.len() <= 10, "store has {} events", events.len());
+    assert_eq!(
+        "19",
+        events.last().unwrap().extra.as_ref().unwrap()["number"]
+    );
+    let dropped = test_get_num_recorded_errors(&glean, &meta.into(), ErrorType::InvalidOverflow);
+    assert_eq!(Ok(20 - events.len() as i32), dropped);
+}
@@ -0,0 +1,92 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+mod common;
+use crate::common::*;
+
+use serde_json::json;
+
+use glean_core::metrics::*;
+use glean_core::storage::StorageManager;
+use glean_core::{CommonMetricData, Lifetime};
+
+fn gauge_metric(lifetime: Lifetime) -> GaugeMetric {
+    GaugeMetric::new(CommonMetricData {
+        name: "open_tabs".into(),
+        category: "telemetry".into(),
+        send_in_pings: vec!["store1".into()],
+        disabled: false,
+        lifetime,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn gauge_serializer_should_correctly_serialize_gauges() {
+    let (mut tempdir, _) = tempdir();
+
+    {
+        let (glean, dir) = new_glean(Some(tempdir));
+        tempdir = dir;
+
+        let metric = gauge_metric(Lifetime::User);
+        metric.set_sync(&glean, 3);
+        metric.set_sync(&glean, 7);
+        metric.set_sync(&glean, 5);
+    }
+
+    // Make a new Glean instance here, which should force reloading of the data from disk
+    // so we can ensure it persisted, because it has User lifetime
+    {
+        let (glean, _t) = new_glean(Some(tempdir));
+        let snapshot = StorageManager
+            .snapshot_as_json(glean.storage(), "store1", true)
+            .unwrap();
+        assert_eq!(
+            json!({
+                "gauge": {
+                    "telemetry.open_tabs": {"count": 3, "sum": 15, "min": 3, "max": 7, "last": 5}
+                }
+            }),
+            snapshot
+        );
+    }
+}
+
+#[test]
+fn gauge_aggregates_all_values_set() {
+    let (glean, _t) = new_glean(None);
+    let metric = gauge_metric(Lifetime::Ping);
+
+    for value in [10, -4, 22, 8] {
+        metric.set_sync(&glean, value);
+    }
+
+    let data = metric.get_value(&glean, "store1").unwrap();
+    assert_eq!(4, data.count);
+    assert_eq!(36, data.sum);
+    assert_eq!(-4, data.min);
+    assert_eq!(22, data.max);
+    assert_eq!(8, data.last);
+    assert_eq!(9.0, data.mean());
+}
+
+#[test]
+fn gauge_starts_over_after_the_ping_is_collected() {
+    let (glean, _t) = new_glean(None);
+    let metric = gauge_metric(Lifetime::Ping);
+
+    metric.set_sync(&glean, 100);
+    metric.set_sync(&glean, 1);
+
+    // Taking a snapshot with clearing resets the ping lifetime data.
+    StorageManager
+        .snapshot_as_json(glean.storage(), "store1", true)
+        .unwrap();
+    assert_eq!(None, metric.get_value(&glean, "store1"));
+
+    metric.set_sync(&glean, 5);
+    let data = metric.get_value(&glean, "store1").unwrap();
+    assert_eq!(GaugeData::new(5), data);
+}
@@ -166,6 +166,8 @@ fn can_create_labeled_memory_distribution_metric() {
                 ..Default::default()
             },
             unit: MemoryUnit::Byte,
+            buckets_per_magnitude: None,
+            max_bytes: None,
         },
         Some(vec!["label1".into()]),
     );
@@ -201,6 +203,8 @@ fn can_create_labeled_timing_distribution_metric() {
                 ..Default::default()
             },
             unit: TimeUnit::Nanosecond,
+            buckets_per_magnitude: None,
+            max_sample_time: None,
         },
         Some(vec!["label1".into()]),
     );
@@ -256,6 +260,43 @@ fn can_create_labeled_quantity_metric() {
     );
 }
 
+#[test]
+fn can_create_labeled_gauge_metric() {
+    let (glean, _t) = new_glean(None);
+    let labeled = LabeledGauge::new(
+        LabeledMetricData::Common {
+            cmd: CommonMetricData {
+                name: "labeled_metric".into(),
+                category: "telemetry".into(),
+                send_in_pings: vec!["store1".into()],
+                disabled: false,
+                lifetime: Lifetime::Ping,
+                ..Default::default()
+            },
+        },
+        Some(vec!["label1".into()]),
+    );
+
+    let metric = labeled.get("label1");
+    metric.set_sync(&glean, 42);
+    metric.set_sync(&glean, 2);
+
+    let snapshot = StorageManager
+        .snapshot_as_json(glean.storage(), "store1", true)
+        .unwrap();
+
+    assert_eq!(
+        json!({
+            "labeled_gauge": {
+                "telemetry.labeled_metric": {
+                    "label1": {"count": 2, "sum": 44, "min": 2, "max": 42, "last": 2},
+                },
+            }
+        }),
+        snapshot
+    );
+}
+
 #[test]
 fn can_use_multiple_labels() {
     let (glean, _t) = new_glean(None);
@@ -690,3 +731,139 @@ fn overrun_the_label_count_with_a_single_label() {
     assert_eq!(json!(23), cached_labels["label-2"]);
     assert_eq!(json!(null), cached_labels["__other__"]);
 }
+
+#[test]
+fn can_create_labeled_rate_and_text_metrics() {
+    let (glean, _t) = new_glean(None);
+    let cmd = |name: &str| CommonMetricData {
+        name: name.into(),
+        category: "telemetry".into(),
+        send_in_pings: vec!["store1".into()],
+        disabled: false,
+        lifetime: Lifetime::Ping,
+        ..Default::default()
+    };
+
+    let rate = LabeledRate::new(
+        LabeledMetricData::Common {
+            cmd: cmd("labeled_rate"),
+        },
+        Some(vec!["label1".into()]),
+    );
+    rate.get("label1").add_to_numerator_sync(&glean, 1);
+    rate.get("label1").add_to_denominator_sync(&glean, 4);
+
+    let text = LabeledText::new(
+        LabeledMetricData::Common {
+            cmd: cmd("labeled_text"),
+        },
+        None,
+    );
+    text.get("crash").set_sync(&glean, "stack trace");
+
+    let snapshot = StorageManager
+        .snapshot_as_json(glean.storage(), "store1", true)
+        .unwrap();
+
+    assert_eq!(
+        json!({
+            "labeled_rate": {
+                "telemetry.labeled_rate": { "label1": {"numerator": 1, "denominator": 4} },
+            },
+            "labeled_text": {
+                "telemetry.labeled_text": { "crash": "stack trace" },
+            }
+        }),
+        snapshot
+    );
+}
+
+#[test]
+fn labeled_timespans_time_independently() {
+    let (glean, _t) = new_glean(None);
+    let labeled = LabeledTimespan::new(
+        LabeledMetricData::Timespan {
+            cmd: CommonMetricData {
+                name: "labeled_timespan".into(),
+                category: "telemetry".into(),
+                send_in_pings: vec!["store1".into()],
+                disabled: false,
+                lifetime: Lifetime::Ping,
+                ..Default::default()
+            },
+            unit: TimeUnit::Nanosecond,
+        },
+        Some(vec!["cold".into(), "warm".into()]),
+    );
+
+    labeled.get("cold").set_start(&glean, 0);
+    labeled.get("warm").set_start(&glean, 10);
+    labeled.get("warm").set_stop(&glean, 15);
+    labeled.get("cold").set_stop(&glean, 100);
+
+    let snapshot = StorageManager
+        .snapshot_as_json(glean.storage(), "store1", true)
+        .unwrap();
+
+    assert_eq!(
+        json!({
+            "labeled_timespan": {
+                "telemetry.labeled_timespan": {
+                    "cold": {"value": 100, "time_unit": "nanosecond"},
+                    "warm": {"value": 5, "time_unit": "nanosecond"},
+                },
+            }
+        }),
+        snapshot
+    );
+    assert!(test_get_num_recorded_errors(
+        &glean,
+        labeled.get("cold").meta(),
+        ErrorType::InvalidState
+    )
+    .is_err());
+}
+
+#[test]
+fn can_create_labeled_datetime_metric() {
+    let (glean, _t) = new_glean(None);
+    let labeled = LabeledDatetime::new(
+        LabeledMetricData::Datetime {
+            cmd: CommonMetricData {
+                name: "labeled_datetime".into(),
+                category: "telemetry".into(),
+                send_in_pings: vec!["store1".into()],
+                disabled: false,
+                lifetime: Lifetime::Ping,
+                ..Default::default()
+            },
+            unit: TimeUnit::Day,
+        },
+        None,
+    );
+
+    let value = Datetime {
+        year: 1983,
+        month: 4,
+        day: 13,
+        hour: 12,
+        minute: 9,
+        second: 14,
+        nanosecond: 0,
+        offset_seconds: 0,
+    };
+    labeled.get("install").set_sync(&glean, Some(value));
+
+    let snapshot = StorageManager
+        .snapshot_as_json(glean.storage(), "store1", true)
+        .unwrap();
+
+    assert_eq!(
+        json!({
+            "labeled_datetime": {
+                "telemetry.labeled_datetime": { "install": "1983-04-13+00:00" },
+            }
+        }),
+        snapshot
+    );
+}
@@ -190,3 +190,119 @@ fn the_accumulate_samples_api_correctly_handles_negative_values() {
         test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidValue)
     );
 }
+
+#[test]
+fn custom_bucketing_is_reported_and_clamps_samples() {
+    let (glean, _t) = new_glean(None);
+    let metric = MemoryDistributionMetric::new_with_bucketing(
+        CommonMetricData {
+            name: "allocation_size".into(),
+            category: "telemetry".into(),
+            send_in_pings: vec!["store1".into()],
+            disabled: false,
+            lifetime: Lifetime::Ping,
+            ..Default::default()
+        },
+        MemoryUnit::Byte,
+        Some(4),
+        Some(1024),
+    );
+
+    metric.accumulate_samples_sync(&glean, vec![100, 2048]);
+
+    let data = metric.get_value(&glean, "store1").unwrap();
+    assert_eq!(1124, data.sum);
+    assert_eq!(Some(4), data.buckets_per_magnitude);
+    assert_eq!(
+        Ok(1),
+        test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidValue)
+    );
+
+    let snapshot = StorageManager
+        .snapshot_as_json(glean.storage(), "store1", true)
+        .unwrap();
+    assert_eq!(
+        json!(4),
+        snapshot["memory_distribution"]["telemetry.allocation_size"]["buckets_per_magnitude"]
+    );
+    assert_eq!(
+        json!(1024),
+        snapshot["memory_distribution"]["telemetry.allocation_size"]["max_bytes"]
+    );
+}
+
+#[test]
+fn samples_stored_with_a_different_bucketing_are_rebucketed() {
+    let (glean, _t) = new_glean(None);
+    let meta = CommonMetricData {
+        name: "allocation_size".into(),
+        category: "telemetry".into(),
+        send_in_pings: vec!["store1".into()],
+        disabled: false,
+        lifetime: Lifetime::Ping,
+        ..Default::default()
+    };
+
+    let metric = MemoryDistributionMetric::new(meta.clone(), MemoryUnit::Byte);
+    metric.accumulate_samples_sync(&glean, vec![100, 1000]);
+
+    // The same metric, with its bucketing changed in a newer version.
+    let metric =
+        MemoryDistributionMetric::new_with_bucketing(meta, MemoryUnit::Byte, Some(4), None);
+    metric.accumulate_samples_sync(&glean, vec![10000]);
+
+    let data = metric.get_value(&glean, "store1").unwrap();
+    assert_eq!(11100, data.sum);
+    assert_eq!(3, data.count);
+    assert_eq!(Some(4), data.buckets_per_magnitude);
+    assert_eq!(None, data.max_bytes);
+    assert_eq!(
+        Ok(1),
+        test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidState)
+    );
+
+    let snapshot = StorageManager
+        .snapshot_as_json(glean.storage(), "store1", true)
+        .unwrap();
+    assert_eq!(
+        None,
+        snapshot["memory_distribution"]["telemetry.allocation_size"].get("max_bytes")
+    );
+}
+
+#[test]
+fn percentiles_are_interpolated_within_buckets() {
+    let (glean, _t) = new_glean(None);
+
+    let metric = MemoryDistributionMetric::new(
+        CommonMetricData {
+            name: "distribution".into(),
+            category: "telemetry".into(),
+            send_in_pings: vec!["store1".into()],
+            disabled: false,
+            lifetime: Lifetime::Ping,
+            ..Default::default()
+        },
+        MemoryUnit::Byte,
+    );
+
+    metric.accumulate_samples_sync(&glean, (1..=1000).collect());
+    let data = metric.get_value(&glean, "store1").unwrap();
+
+    for percentile in [10, 50, 90, 99] {
+        let estimate = data.percentile(percentile as f64).unwrap();
+        let expected = percentile * 10;
+        // Well within the width of a bucket, which is about 4% of its minimum.
+        assert!(
+            (estimate - expected).abs() <= expected / 50,
+            "p{percentile}: {estimate}"
+        );
+    }
+
+    // Rather than at the minimum of its bucket, a single sample is placed in the middle.
+    let metric = metric.with_name("single".into());
+    metric.accumulate_sync(&glean, 100_000);
+    let data = metric.get_value(&glean, "store1").unwrap();
+    let upper = data.upper_bounds[&96785];
+    assert_eq!(Some((96785 + upper) / 2), data.percentile(50.0));
+}
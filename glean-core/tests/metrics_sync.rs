@@ -24,6 +24,7 @@ static DEFINITION_ONLY: &[&str] = &[
     /* adhoc in src/ping/mod.rs */
     "glean.ping.uploader_capabilities",
     /* adhoc events */
+    "glean.migration",
     "glean.restarted",
     "glean.session_end",
     "glean.session_start",
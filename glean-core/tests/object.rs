@@ -9,6 +9,7 @@ use serde_json::json;
 
 use glean_core::metrics::*;
 use glean_core::storage::StorageManager;
+use glean_core::{test_get_num_recorded_errors, ErrorType};
 use glean_core::{CommonMetricData, Lifetime};
 
 #[test]
@@ -102,3 +103,156 @@ fn getting_data_json_encoded() {
 
     assert_eq!(obj_str, object.get_value(&glean, Some("store1")).unwrap());
 }
+
+fn balloons_metric() -> ObjectMetric {
+    ObjectMetric::new_with_schema(
+        CommonMetricData {
+            name: "balloons".into(),
+            category: "party".into(),
+            send_in_pings: vec!["store1".into()],
+            disabled: false,
+            lifetime: Lifetime::Ping,
+            ..Default::default()
+        },
+        json!({
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "colour": { "type": "string" },
+                    "diameter": { "type": "number" }
+                }
+            }
+        })
+        .to_string(),
+    )
+}
+
+#[test]
+fn values_matching_the_schema_are_recorded() {
+    let (glean, _t) = new_glean(None);
+    let metric = balloons_metric();
+
+    let balloons = json!([
+        { "colour": "red", "diameter": 5 },
+        { "colour": "blue", "diameter": null },
+        {}
+    ]);
+    metric.set_sync(&glean, balloons.clone());
+
+    let data: serde_json::Value =
+        serde_json::from_str(&metric.get_value(&glean, "store1").unwrap()).unwrap();
+    assert_eq!(balloons, data);
+    assert!(test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidValue).is_err());
+}
+
+#[test]
+fn values_not_matching_the_schema_are_rejected() {
+    let (glean, _t) = new_glean(None);
+    let metric = balloons_metric();
+
+    // Not an array.
+    metric.set_sync(&glean, json!({ "colour": "red" }));
+    // Wrong type for a property.
+    metric.set_sync(&glean, json!([{ "colour": 5 }]));
+    // Undeclared property.
+    metric.set_sync(&glean, json!([{ "colour": "red", "shape": "heart" }]));
+
+    assert_eq!(None, metric.get_value(&glean, "store1"));
+    assert_eq!(
+        Ok(3),
+        test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidValue)
+    );
+}
+
+#[test]
+fn invalid_schema_rejects_all_values() {
+    let (glean, _t) = new_glean(None);
+    let metric = ObjectMetric::new_with_schema(
+        CommonMetricData {
+            name: "broken".into(),
+            category: "party".into(),
+            send_in_pings: vec!["store1".into()],
+            disabled: false,
+            lifetime: Lifetime::Ping,
+            ..Default::default()
+        },
+        "{ \"type\": \"tuple\" }".into(),
+    );
+
+    metric.set_sync(&glean, json!([1, 2]));
+
+    assert_eq!(None, metric.get_value(&glean, "store1"));
+    assert_eq!(
+        Ok(1),
+        test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidValue)
+    );
+}
+
+#[test]
+fn deeply_nested_or_large_values_are_rejected_with_a_schema() {
+    let (glean, _t) = new_glean(None);
+
+    // A schema that allows objects nested 12 levels deep.
+    let mut schema = json!({"type": "object", "properties": {"s": {"type": "string"}}});
+    for _ in 0..11 {
+        schema = json!({"type": "object", "properties": {"s": {"type": "string"}, "a": schema}});
+    }
+    let metric = ObjectMetric::new_with_schema(
+        CommonMetricData {
+            name: "checked".into(),
+            category: "party".into(),
+            send_in_pings: vec!["store1".into()],
+            disabled: false,
+            lifetime: Lifetime::Ping,
+            ..Default::default()
+        },
+        schema.to_string(),
+    );
+
+    let mut nested = json!({});
+    for _ in 0..10 {
+        nested = json!({ "a": nested });
+    }
+    metric.set_sync(&glean, nested);
+    assert_eq!(
+        Ok(1),
+        test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidValue)
+    );
+
+    metric.set_sync(&glean, json!({ "s": "a".repeat(100 * 1024) }));
+    assert_eq!(
+        Ok(1),
+        test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidOverflow)
+    );
+
+    assert_eq!(None, metric.get_value(&glean, "store1"));
+}
+
+#[test]
+fn values_without_a_schema_are_not_limited() {
+    let (glean, _t) = new_glean(None);
+    let metric = ObjectMetric::new(CommonMetricData {
+        name: "unchecked".into(),
+        category: "party".into(),
+        send_in_pings: vec!["store1".into()],
+        disabled: false,
+        lifetime: Lifetime::Ping,
+        ..Default::default()
+    });
+
+    let mut nested = json!(1);
+    for _ in 0..11 {
+        nested = json!([nested]);
+    }
+    metric.set_sync(&glean, nested);
+    assert!(metric.get_value(&glean, "store1").is_some());
+
+    metric.set_sync(&glean, json!(["a".repeat(100 * 1024)]));
+    assert!(metric.get_value(&glean, "store1").is_some());
+
+    assert!(test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidValue).is_err());
+    assert!(
+        test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidOverflow).is_err()
+    );
+}
@@ -8,9 +8,10 @@ use crate::common::*;
 use std::collections::HashMap;
 
 use glean_core::metrics::*;
-use glean_core::CommonMetricData;
+use glean_core::ping::PingSnapshotFilter;
 use glean_core::LabeledMetricData;
 use glean_core::Lifetime;
+use glean_core::{CommonMetricData, Glean};
 
 #[test]
 fn write_ping_to_disk() {
@@ -323,3 +324,194 @@ fn database_write_timings_get_recorded() {
         "writing should take some time"
     );
 }
+
+#[test]
+fn previewing_a_ping_does_not_change_state() {
+    let (mut glean, _t) = new_glean(None);
+
+    let ping = new_test_ping(&mut glean, "store1");
+    let counter = CounterMetric::new(CommonMetricData {
+        name: "counter".into(),
+        category: "local".into(),
+        send_in_pings: vec!["store1".into()],
+        lifetime: Lifetime::Ping,
+        ..Default::default()
+    });
+    let event = EventMetric::new(
+        CommonMetricData {
+            name: "name".into(),
+            category: "category".into(),
+            send_in_pings: vec!["store1".into()],
+            ..Default::default()
+        },
+        vec![],
+    );
+
+    assert!(glean.preview_ping("store1").is_none());
+    assert!(glean.preview_ping("unknown").is_none());
+
+    counter.add_sync(&glean, 3);
+    event.record_sync(&glean, 0, HashMap::new(), 0);
+
+    let first = glean.preview_ping("store1").unwrap();
+    let second = glean.preview_ping("store1").unwrap();
+    assert_eq!(first["metrics"], second["metrics"]);
+    assert_eq!(first["events"], second["events"]);
+    assert_eq!(0, first["ping_info"]["seq"]);
+    assert_eq!(0, second["ping_info"]["seq"]);
+    assert_eq!(3, first["metrics"]["counter"]["local.counter"]);
+    assert_eq!(1, first["events"].as_array().unwrap().len());
+    assert!(first["client_info"]["telemetry_sdk_build"].is_string());
+
+    // Nothing was queued and the data is still there.
+    assert!(get_queued_pings(glean.get_data_path()).is_err());
+    assert_eq!(Some(3), counter.get_value(&glean, "store1"));
+    assert_eq!(1, event.get_value(&glean, "store1").unwrap().len());
+
+    // The submitted ping contains the previewed data.
+    assert!(ping.submit_sync(&glean, None));
+    let pings = get_queued_pings(glean.get_data_path()).unwrap();
+    assert_eq!(1, pings.len());
+    let payload = &pings[0].1;
+    assert_eq!(first["metrics"], payload["metrics"]);
+    assert_eq!(first["events"], payload["events"]);
+    assert_eq!(0, payload["ping_info"]["seq"]);
+}
+
+#[test]
+fn pseudonymous_ids_replace_the_client_id() {
+    let (mut glean, _t) = new_glean(None);
+
+    let ping = PingBuilder::new("pseudonymous")
+        .with_send_if_empty(true)
+        .with_include_client_id(false)
+        .with_options(PingOptions {
+            pseudonymous_id: Some(PseudonymousIdRotation::Days { days: 1 }),
+            ..Default::default()
+        })
+        .build();
+    glean.register_ping_type(&ping);
+
+    assert!(ping.submit_sync(&glean, None));
+    assert!(ping.submit_sync(&glean, None));
+    let pings = get_queued_pings(glean.get_data_path()).unwrap();
+    assert_eq!(2, pings.len());
+    let client_info = &pings[0].1["client_info"];
+    assert!(client_info.get("client_id").is_none());
+    let id = client_info["pseudonymous_id"].as_str().unwrap().to_string();
+    // Pings in the same window carry the same ID.
+    // This could only fail if the test runs across midnight (UTC).
+    assert_eq!(id, pings[1].1["client_info"]["pseudonymous_id"]);
+
+    // Nothing else links the pings of different windows.
+    for (_, payload, _) in &pings {
+        assert!(payload["client_info"].get("first_run_date").is_none());
+        assert_eq!(0, payload["ping_info"]["seq"]);
+        assert_eq!(
+            payload["ping_info"]["end_time"],
+            payload["ping_info"]["start_time"]
+        );
+    }
+
+    // The secret is removed with all other client data.
+    glean.set_upload_enabled(false);
+    glean.set_upload_enabled(true);
+    assert!(ping.submit_sync(&glean, None));
+    let pings = get_queued_pings(glean.get_data_path()).unwrap();
+    assert_eq!(1, pings.len());
+    assert_ne!(id, pings[0].1["client_info"]["pseudonymous_id"]);
+}
+
+#[test]
+fn pseudonymous_ids_exclude_the_client_id() {
+    let (mut glean, _t) = new_glean(None);
+
+    let ping = PingBuilder::new("pseudonymous")
+        .with_send_if_empty(true)
+        .with_include_client_id(true)
+        .with_options(PingOptions {
+            pseudonymous_id: Some(PseudonymousIdRotation::Days { days: 1 }),
+            ..Default::default()
+        })
+        .build();
+    glean.register_ping_type(&ping);
+
+    assert!(ping.submit_sync(&glean, None));
+    let pings = get_queued_pings(glean.get_data_path()).unwrap();
+    assert_eq!(1, pings.len());
+    let client_info = &pings[0].1["client_info"];
+    assert!(client_info.get("client_id").is_none());
+    assert!(client_info["pseudonymous_id"].is_string());
+}
+
+#[test]
+fn snapshot_filters_minimize_the_payload() {
+    let (mut glean, _t) = new_glean(None);
+
+    let ping = PingBuilder::new("filtered")
+        .with_options(PingOptions {
+            snapshot_filter: Some(PingSnapshotFilter {
+                exclude_default_values: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+        .build();
+    glean.register_ping_type(&ping);
+
+    let meta = |name: &str| CommonMetricData {
+        name: name.into(),
+        category: "local".into(),
+        send_in_pings: vec!["filtered".into()],
+        lifetime: Lifetime::Ping,
+        ..Default::default()
+    };
+    let labeled = LabeledCounter::new(
+        LabeledMetricData::Common {
+            cmd: meta("labeled"),
+        },
+        None,
+    );
+    let zero = QuantityMetric::new(meta("zero"));
+    let record = |glean: &Glean| {
+        zero.set_sync(glean, 0);
+        for (label, amount) in [("a", 1), ("b", 2), ("c", 3)] {
+            labeled.get(label).add_sync(glean, amount);
+        }
+    };
+
+    // Only default values are recorded, so there's nothing to send.
+    zero.set_sync(&glean, 0);
+    assert!(!ping.submit_sync(&glean, None));
+
+    record(&glean);
+    assert!(ping.submit_sync(&glean, None));
+    let pings = get_queued_pings(glean.get_data_path()).unwrap();
+    assert_eq!(1, pings.len());
+    assert_eq!(
+        serde_json::json!({ "labeled_counter": { "local.labeled": { "a": 1, "b": 2, "c": 3 } } }),
+        pings[0].1["metrics"]
+    );
+
+    // Server Knobs replace the declared filter.
+    let remote_settings_config = serde_json::json!({
+        "ping_snapshot_filters": { "filtered": { "max_labels_per_metric": 2 } }
+    })
+    .to_string();
+    glean
+        .apply_server_knobs_config(RemoteSettingsConfig::try_from(remote_settings_config).unwrap());
+
+    record(&glean);
+    assert!(ping.submit_sync(&glean, None));
+    let pings = get_queued_pings(glean.get_data_path()).unwrap();
+    let payload = pings
+        .iter()
+        .map(|(_, payload, _)| payload)
+        .find(|payload| payload["ping_info"]["seq"] == 1)
+        .unwrap();
+    assert_eq!(0, payload["metrics"]["quantity"]["local.zero"]);
+    assert_eq!(
+        serde_json::json!({ "c": 3, "__other__": 3 }),
+        payload["metrics"]["labeled_counter"]["local.labeled"]
+    );
+}
@@ -0,0 +1,91 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+mod common;
+use crate::common::*;
+
+use glean_core::metrics::*;
+use glean_core::storage::StorageManager;
+use glean_core::{test_get_num_recorded_errors, ErrorType};
+use glean_core::{CommonMetricData, Lifetime};
+
+fn quantile_distribution_metric(lifetime: Lifetime) -> QuantileDistributionMetric {
+    QuantileDistributionMetric::new(
+        CommonMetricData {
+            name: "latency".into(),
+            category: "telemetry".into(),
+            send_in_pings: vec!["store1".into()],
+            disabled: false,
+            lifetime,
+            ..Default::default()
+        },
+        0.01,
+    )
+}
+
+#[test]
+fn serializer_should_correctly_serialize_quantile_distribution() {
+    let (mut tempdir, _) = tempdir();
+
+    {
+        let (glean, dir) = new_glean(Some(tempdir));
+        tempdir = dir;
+
+        let metric = quantile_distribution_metric(Lifetime::User);
+        metric.accumulate_samples_sync(&glean, &[0, 100, 100, 1000]);
+    }
+
+    // Make a new Glean instance here, which should force reloading of the data from disk
+    // so we can ensure it persisted, because it has User lifetime
+    {
+        let (glean, _t) = new_glean(Some(tempdir));
+        let snapshot = StorageManager
+            .snapshot_as_json(glean.storage(), "store1", true)
+            .unwrap();
+
+        let sketch = &snapshot["quantile_distribution"]["telemetry.latency"];
+        assert_eq!(0.01, sketch["relative_accuracy"]);
+        assert_eq!(4, sketch["count"]);
+        assert_eq!(1200, sketch["sum"]);
+        assert_eq!(1, sketch["zero_count"]);
+        // 100 and 1000 end up in one bin each.
+        let bins = sketch["bins"].as_object().unwrap();
+        assert_eq!(2, bins.len());
+        assert_eq!(3, bins.values().map(|v| v.as_u64().unwrap()).sum::<u64>());
+    }
+}
+
+#[test]
+fn percentiles_are_within_the_configured_accuracy() {
+    let (glean, _t) = new_glean(None);
+    let metric = quantile_distribution_metric(Lifetime::Ping);
+
+    let samples: Vec<i64> = (1..=1000).collect();
+    metric.accumulate_samples_sync(&glean, &samples);
+
+    let sketch = metric.get_value(&glean, "store1").unwrap();
+    assert_eq!(1000, sketch.count());
+    for (quantile, expected) in [(0.5, 500.0), (0.95, 950.0), (0.99, 990.0)] {
+        let estimate = sketch.quantile(quantile).unwrap();
+        assert!(
+            (estimate - expected).abs() <= expected * 0.01 + 1.0,
+            "p{quantile}: expected {expected}, got {estimate}"
+        );
+    }
+}
+
+#[test]
+fn negative_samples_are_rejected() {
+    let (glean, _t) = new_glean(None);
+    let metric = quantile_distribution_metric(Lifetime::Ping);
+
+    metric.accumulate_samples_sync(&glean, &[-1, 10, -2]);
+
+    let sketch = metric.get_value(&glean, "store1").unwrap();
+    assert_eq!(1, sketch.count());
+    assert_eq!(
+        Ok(2),
+        test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidValue)
+    );
+}
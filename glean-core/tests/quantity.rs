@@ -116,3 +116,40 @@ fn quantities_must_not_set_when_passed_negative() {
         test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidValue)
     );
 }
+
+#[test]
+fn dual_labeled_quantity_is_serialized_in_its_own_section() {
+    let (glean, _t) = new_glean(None);
+    let dual_labeled = DualLabeledQuantityMetric::new(
+        CommonMetricData {
+            name: "display_size".into(),
+            category: "telemetry".into(),
+            send_in_pings: vec!["store1".into()],
+            disabled: false,
+            lifetime: Lifetime::Ping,
+            ..Default::default()
+        },
+        Some(vec!["width".into(), "height".into()]),
+        None,
+    );
+
+    dual_labeled.get("width", "primary").set_sync(&glean, 1920);
+    dual_labeled.get("height", "primary").set_sync(&glean, 1080);
+    dual_labeled.get("depth", "primary").set_sync(&glean, 24);
+
+    let snapshot = StorageManager
+        .snapshot_as_json(glean.storage(), "store1", true)
+        .unwrap();
+    assert_eq!(
+        json!({
+            "dual_labeled_quantity": {
+                "telemetry.display_size": {
+                    "width": { "primary": 1920 },
+                    "height": { "primary": 1080 },
+                    "__other__": { "primary": 24 }
+                }
+            }
+        }),
+        snapshot
+    );
+}
@@ -43,6 +43,8 @@ fn session_cfg(
         ping_lifetime_max_time: 0,
         max_pending_pings_count: None,
         max_pending_pings_directory_size: None,
+        database_size_budget: None,
+        event_store_limits: None,
         session_mode: mode,
         session_sample_rate: sample_rate,
         session_inactivity_timeout_ms: timeout_ms,
@@ -0,0 +1,171 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+mod common;
+use crate::common::*;
+
+use std::collections::HashMap;
+
+use glean_core::metrics::*;
+use glean_core::{
+    test_get_num_recorded_errors, CommonMetricData, ErrorType, Glean, InternalConfiguration,
+    Lifetime, SessionMode,
+};
+
+fn span_metric(name: &str, store_name: &str, in_session: bool) -> SpanMetric {
+    SpanMetric::new(
+        CommonMetricData {
+            name: name.into(),
+            category: "telemetry".into(),
+            send_in_pings: vec![store_name.into()],
+            disabled: false,
+            lifetime: Lifetime::Ping,
+            in_session,
+            ..Default::default()
+        },
+        vec!["outcome".into()],
+    )
+}
+
+#[test]
+fn start_and_end_record_paired_events() {
+    let (glean, _t) = new_glean(None);
+    let metric = span_metric("span", "store1", false);
+
+    let span = metric.start_sync(&glean, None);
+    let mut extra = HashMap::new();
+    extra.insert("outcome".to_string(), "success".to_string());
+    metric.end_sync(&glean, span, extra);
+
+    let events = metric.get_value(&glean, "store1").unwrap();
+    assert_eq!(2, events.len());
+    assert!(events[0].timestamp <= events[1].timestamp);
+
+    let start = events[0].extra.as_ref().unwrap();
+    assert_eq!(span.id.to_string(), start["span_id"]);
+    assert_eq!("start", start["span_phase"]);
+    assert!(!start.contains_key("parent_span_id"));
+    assert!(!start.contains_key("duration_ns"));
+
+    let end = events[1].extra.as_ref().unwrap();
+    assert_eq!(span.id.to_string(), end["span_id"]);
+    assert_eq!("end", end["span_phase"]);
+    assert_eq!("success", end["outcome"]);
+    assert!(end["duration_ns"].parse::<u64>().is_ok());
+
+    assert!(test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidState).is_err());
+}
+
+#[test]
+fn child_spans_refer_to_their_parent() {
+    let (glean, _t) = new_glean(None);
+    let action = span_metric("action", "store1", false);
+    let work = span_metric("work", "store1", false);
+
+    let parent = action.start_sync(&glean, None);
+    let child = work.start_sync(&glean, Some(parent));
+    work.end_sync(&glean, child, HashMap::new());
+    action.end_sync(&glean, parent, HashMap::new());
+
+    assert_ne!(parent, child);
+
+    let events = work.get_value(&glean, "store1").unwrap();
+    assert_eq!(2, events.len());
+    for event in events {
+        let extra = event.extra.unwrap();
+        assert_eq!(child.id.to_string(), extra["span_id"]);
+        assert_eq!(parent.id.to_string(), extra["parent_span_id"]);
+    }
+
+    let events = action.get_value(&glean, "store1").unwrap();
+    assert_eq!(2, events.len());
+    for event in events {
+        assert!(!event.extra.unwrap().contains_key("parent_span_id"));
+    }
+}
+
+#[test]
+fn ending_an_unknown_span_records_an_error() {
+    let (glean, _t) = new_glean(None);
+    let metric = span_metric("span", "store1", false);
+
+    let span = metric.start_sync(&glean, None);
+    metric.end_sync(&glean, span, HashMap::new());
+    // A span can only be ended once.
+    metric.end_sync(&glean, span, HashMap::new());
+
+    assert_eq!(2, metric.get_value(&glean, "store1").unwrap().len());
+    assert_eq!(
+        Ok(1),
+        test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidState)
+    );
+}
+
+#[test]
+fn reserved_extra_keys_are_rejected() {
+    let (glean, _t) = new_glean(None);
+    let metric = span_metric("span", "store1", false);
+
+    let span = metric.start_sync(&glean, None);
+    let mut extra = HashMap::new();
+    extra.insert("duration_ns".to_string(), "0".to_string());
+    metric.end_sync(&glean, span, extra);
+
+    // Only the start record is stored.
+    assert_eq!(1, metric.get_value(&glean, "store1").unwrap().len());
+    assert_eq!(
+        Ok(1),
+        test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidValue)
+    );
+}
+
+#[test]
+fn spans_carry_session_metadata() {
+    let (_t, data_path) = tempdir();
+    let cfg = InternalConfiguration {
+        data_path,
+        application_id: GLOBAL_APPLICATION_ID.into(),
+        language_binding_name: "Rust".into(),
+        upload_enabled: true,
+        max_events: None,
+        delay_ping_lifetime_io: false,
+        app_build: "Unknown".into(),
+        use_core_mps: false,
+        trim_data_to_registered_pings: false,
+        log_level: None,
+        rate_limit: None,
+        enable_event_timestamps: false,
+        experimentation_id: None,
+        enable_internal_pings: true,
+        ping_schedule: Default::default(),
+        ping_lifetime_threshold: 0,
+        ping_lifetime_max_time: 0,
+        max_pending_pings_count: None,
+        max_pending_pings_directory_size: None,
+        database_size_budget: None,
+        event_store_limits: None,
+        session_mode: SessionMode::Auto,
+        session_sample_rate: 1.0,
+        session_inactivity_timeout_ms: 1_800_000,
+    };
+    let mut glean = Glean::new(cfg).unwrap();
+    glean.handle_client_active();
+
+    let metric = span_metric("span", "events", true);
+    let span = metric.start_sync(&glean, None);
+    metric.end_sync(&glean, span, HashMap::new());
+
+    let events = metric.get_value(&glean, "events").unwrap();
+    assert_eq!(2, events.len());
+    let start = events[0]
+        .session
+        .as_ref()
+        .expect("start should be in a session");
+    let end = events[1]
+        .session
+        .as_ref()
+        .expect("end should be in a session");
+    assert_eq!(start.session_id, end.session_id);
+    assert!(start.event_seq < end.event_seq);
+}
@@ -473,3 +473,194 @@ fn timing_distribution_is_tracked_across_upload_toggle() {
     // Make sure that the error has been recorded
     assert!(test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidState).is_err());
 }
+
+#[test]
+fn dual_labeled_timing_distribution_is_serialized_in_its_own_section() {
+    let (glean, _t) = new_glean(None);
+    let dual_labeled = DualLabeledTimingDistributionMetric::new(
+        CommonMetricData {
+            name: "load_time".into(),
+            category: "telemetry".into(),
+            send_in_pings: vec!["store1".into()],
+            disabled: false,
+            lifetime: Lifetime::Ping,
+            ..Default::default()
+        },
+        TimeUnit::Nanosecond,
+        Some(vec!["cold".into()]),
+        Some(vec!["main".into()]),
+    );
+
+    dual_labeled
+        .get("cold", "main")
+        .accumulate_raw_samples_nanos_sync(&glean, &[1000, 2000]);
+    dual_labeled
+        .get("warm", "main")
+        .accumulate_raw_samples_nanos_sync(&glean, &[500]);
+
+    let snapshot = StorageManager
+        .snapshot_as_json(glean.storage(), "store1", true)
+        .unwrap();
+    let section = &snapshot["dual_labeled_timing_distribution"]["telemetry.load_time"];
+    assert_eq!(json!(3000), section["cold"]["main"]["sum"]);
+    assert_eq!(json!(500), section["__other__"]["main"]["sum"]);
+}
+
+#[test]
+fn custom_bucketing_is_reported_and_clamps_samples() {
+    let (glean, _t) = new_glean(None);
+    let metric = TimingDistributionMetric::new_with_bucketing(
+        CommonMetricData {
+            name: "kernel_time".into(),
+            category: "telemetry".into(),
+            send_in_pings: vec!["store1".into()],
+            disabled: false,
+            lifetime: Lifetime::Ping,
+            ..Default::default()
+        },
+        TimeUnit::Nanosecond,
+        Some(32),
+        Some(1000),
+    );
+
+    metric.accumulate_raw_samples_nanos_sync(&glean, &[10, 20, 5000]);
+
+    let data = metric.get_value(&glean, "store1").unwrap();
+    assert_eq!(1030, data.sum);
+    assert_eq!(Some(32), data.buckets_per_magnitude);
+    assert_eq!(
+        Ok(1),
+        test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidOverflow)
+    );
+
+    let snapshot = StorageManager
+        .snapshot_as_json(glean.storage(), "store1", true)
+        .unwrap();
+    assert_eq!(
+        json!(32),
+        snapshot["timing_distribution"]["telemetry.kernel_time"]["buckets_per_magnitude"]
+    );
+    assert_eq!(
+        json!(1000),
+        snapshot["timing_distribution"]["telemetry.kernel_time"]["max_sample_time"]
+    );
+}
+
+#[test]
+fn samples_stored_with_a_different_bucketing_are_rebucketed() {
+    let (glean, _t) = new_glean(None);
+    let meta = CommonMetricData {
+        name: "kernel_time".into(),
+        category: "telemetry".into(),
+        send_in_pings: vec!["store1".into()],
+        disabled: false,
+        lifetime: Lifetime::Ping,
+        ..Default::default()
+    };
+
+    let metric = TimingDistributionMetric::new(meta.clone(), TimeUnit::Nanosecond);
+    metric.accumulate_raw_samples_nanos_sync(&glean, &[100, 1000]);
+
+    // The same metric, with its bucketing changed in a newer version.
+    let metric =
+        TimingDistributionMetric::new_with_bucketing(meta, TimeUnit::Nanosecond, Some(4), None);
+    metric.accumulate_raw_samples_nanos_sync(&glean, &[10000]);
+
+    let data = metric.get_value(&glean, "store1").unwrap();
+    assert_eq!(11100, data.sum);
+    assert_eq!(3, data.count);
+    assert_eq!(Some(4), data.buckets_per_magnitude);
+    assert_eq!(
+        Ok(1),
+        test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidState)
+    );
+}
+
+#[test]
+fn long_timings_can_be_recorded_with_a_larger_maximum() {
+    let (glean, _t) = new_glean(None);
+    let metric = TimingDistributionMetric::new_with_bucketing(
+        CommonMetricData {
+            name: "background_job".into(),
+            category: "telemetry".into(),
+            send_in_pings: vec!["store1".into()],
+            disabled: false,
+            lifetime: Lifetime::Ping,
+            ..Default::default()
+        },
+        TimeUnit::Nanosecond,
+        None,
+        Some(1000 * 1000 * 1000 * 60 * 60 * 2),
+    );
+
+    // One hour is above the default maximum of 10 minutes for nanosecond timings.
+    let hour = 1000 * 1000 * 1000 * 60 * 60;
+    metric.accumulate_samples_sync(&glean, &[hour]);
+
+    let data = metric.get_value(&glean, "store1").unwrap();
+    assert_eq!(hour, data.sum);
+    // The default bucketing is not reported.
+    assert_eq!(None, data.buckets_per_magnitude);
+    assert_eq!(Some(1000 * 1000 * 1000 * 60 * 60 * 2), data.max_sample_time);
+    assert!(
+        test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidOverflow).is_err()
+    );
+}
+
+#[test]
+fn too_large_maximums_are_clamped_to_what_fits_in_nanoseconds() {
+    let (glean, _t) = new_glean(None);
+    let metric = TimingDistributionMetric::new_with_bucketing(
+        CommonMetricData {
+            name: "uptime".into(),
+            category: "telemetry".into(),
+            send_in_pings: vec!["store1".into()],
+            disabled: false,
+            lifetime: Lifetime::Ping,
+            ..Default::default()
+        },
+        TimeUnit::Second,
+        None,
+        Some(u64::MAX),
+    );
+
+    metric.accumulate_samples_sync(&glean, &[i64::MAX]);
+
+    let max_seconds = u64::MAX / 1_000_000_000;
+    let data = metric.get_value(&glean, "store1").unwrap();
+    assert_eq!(max_seconds * 1_000_000_000, data.sum as u64);
+    assert_eq!(
+        Ok(1),
+        test_get_num_recorded_errors(&glean, metric.meta(), ErrorType::InvalidOverflow)
+    );
+}
+
+#[test]
+fn percentiles_are_interpolated_within_buckets() {
+    let (glean, _t) = new_glean(None);
+
+    let metric = TimingDistributionMetric::new(
+        CommonMetricData {
+            name: "distribution".into(),
+            category: "telemetry".into(),
+            send_in_pings: vec!["store1".into()],
+            disabled: false,
+            lifetime: Lifetime::Ping,
+            ..Default::default()
+        },
+        TimeUnit::Microsecond,
+    );
+
+    metric.accumulate_samples_sync(&glean, &(1..=1000).collect::<Vec<_>>());
+    let data = metric.get_value(&glean, "store1").unwrap();
+
+    for percentile in [10, 50, 90, 99] {
+        let estimate = data.percentile(percentile as f64).unwrap();
+        let expected = percentile * 10 * 1000;
+        // Well within the width of a bucket, which is about 9% of its minimum.
+        assert!(
+            (estimate - expected).abs() <= expected / 25,
+            "p{percentile}: {estimate}"
+        );
+    }
+}
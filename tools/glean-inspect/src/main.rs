@@ -0,0 +1,106 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Dumps the content of a Glean data directory as JSON, without modifying it.
+//!
+//! Usage: `glean-inspect <data-dir> [metrics|events|pings]`
+//!
+//! Without a section, all sections are printed as one object.
+//! The output format is stable:
+//!
+//! * `metrics`: a list of `{lifetime, storage, id, type, value}` objects,
+//!   where `type` is the ping section the metric is reported in and
+//!   `value` is the metric as it would appear in a ping.
+//! * `events`: an object mapping each event store to its recorded events.
+//! * `pings`: a list of `{directory, document_id, ping_name, path, headers, body}` objects.
+
+use std::env;
+use std::process;
+
+use glean_core::inspect::{self, DataDir};
+use serde_json::{Map, Value, json};
+
+fn metrics(data: &DataDir) -> glean_core::Result<Value> {
+    let metrics = data
+        .metrics()?
+        .into_iter()
+        .map(|metric| {
+            json!({
+                "lifetime": metric.lifetime,
+                "storage": metric.storage_name,
+                "id": metric.metric_id,
+                "type": metric.value.ping_section(),
+                "value": metric.value.as_json(),
+            })
+        })
+        .collect();
+    Ok(Value::Array(metrics))
+}
+
+fn events(data: &DataDir) -> glean_core::Result<Value> {
+    let mut stores = Map::new();
+    for store in data.event_stores()? {
+        stores.insert(store.name, serde_json::to_value(store.events)?);
+    }
+    Ok(Value::Object(stores))
+}
+
+fn pings(data: &DataDir) -> glean_core::Result<Value> {
+    let pings = data
+        .pending_pings()?
+        .into_iter()
+        .map(|ping| {
+            let payload = ping.payload;
+            // Keep the body as a string if it isn't valid JSON, so nothing gets lost.
+            let body = serde_json::from_str(&payload.json_body)
+                .unwrap_or(Value::String(payload.json_body));
+            json!({
+                "directory": ping.directory,
+                "document_id": payload.document_id,
+                "ping_name": payload.ping_name,
+                "path": payload.upload_path,
+                "headers": payload.headers,
+                "body": body,
+            })
+        })
+        .collect();
+    Ok(Value::Array(pings))
+}
+
+fn run(path: &str, section: Option<&str>) -> glean_core::Result<Value> {
+    let data = inspect::open_readonly(path)?;
+    match section {
+        Some("metrics") => metrics(&data),
+        Some("events") => events(&data),
+        Some("pings") => pings(&data),
+        Some(other) => {
+            eprintln!("Unknown section: {other}. Expected one of: metrics, events, pings.");
+            process::exit(2);
+        }
+        None => Ok(json!({
+            "metrics": metrics(&data)?,
+            "events": events(&data)?,
+            "pings": pings(&data)?,
+        })),
+    }
+}
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let Some(path) = args.next() else {
+        eprintln!("Usage: glean-inspect <data-dir> [metrics|events|pings]");
+        process::exit(2);
+    };
+    let section = args.next();
+
+    match run(&path, section.as_deref()) {
+        Ok(output) => {
+            println!("{}", serde_json::to_string_pretty(&output).unwrap());
+        }
+        Err(e) => {
+            eprintln!("Failed to inspect {path}: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -8,6 +8,7 @@ use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use weedle::Definition::*;
 use weedle::argument::Argument::{self, Single};
+use weedle::attribute::{ExtendedAttribute, ExtendedAttributeList, IdentifierOrString};
 use weedle::interface::InterfaceMember;
 
 use type_resolver::TypeResolver;
@@ -74,6 +75,27 @@ impl FnArgs {
     }
 }
 
+/// The name of a constructor, as given by its `[Name=...]` attribute.
+///
+/// Defaults to `new`.
+fn constructor_name<'a>(attributes: &Option<ExtendedAttributeList<'a>>) -> &'a str {
+    let Some(attributes) = attributes else {
+        return "new";
+    };
+    attributes
+        .body
+        .list
+        .iter()
+        .find_map(|attr| match attr {
+            ExtendedAttribute::Ident(attr) if attr.lhs_identifier.0 == "Name" => match attr.rhs {
+                IdentifierOrString::Identifier(ident) => Some(ident.0),
+                IdentifierOrString::String(s) => Some(s.0),
+            },
+            _ => None,
+        })
+        .unwrap_or("new")
+}
+
 pub fn generate(content: &str) -> String {
     let parsed = weedle::parse(content).unwrap();
 
@@ -114,12 +136,14 @@ pub fn generate(content: &str) -> String {
         for member in members.body {
             match member {
                 InterfaceMember::Constructor(ctor) => {
+                    let name = constructor_name(&ctor.attributes);
+                    let fn_ident = format_ident!("{}", name);
                     let extern_fn_ident =
-                        format_ident!("uniffi_glean_core_fn_constructor_{}_new", structname);
+                        format_ident!("uniffi_glean_core_fn_constructor_{}_{}", structname, name);
                     let all_args = FnArgs::from_args(&ctor.args.body.list);
                     let (arg_names, fn_args, extern_fn_args, destroys) = all_args.consume();
                     fns.push(quote! {
-                        pub fn new(#(#fn_args,)*) -> Self {
+                        pub fn #fn_ident(#(#fn_args,)*) -> Self {
                             unsafe {
                                 #(
                                     let #arg_names = uniffi::FfiConverter::<crate::UniFfiTag>::lower(#arg_names);